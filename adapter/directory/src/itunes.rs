@@ -0,0 +1,155 @@
+//! Client of the iTunes Search API
+//!
+//! See <https://performance-partners.apple.com/search-api>
+
+use std::borrow::Cow;
+
+use anyhow::Context;
+use entertainarr_domain::podcast::entity::PodcastDirectoryEntry;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    #[serde(default = "Config::default_base_url")]
+    pub base_url: Cow<'static, str>,
+    /// Two letters code of the store to search in
+    #[serde(default)]
+    pub country: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url: Self::default_base_url(),
+            country: None,
+        }
+    }
+}
+
+impl Config {
+    pub const fn default_base_url() -> Cow<'static, str> {
+        Cow::Borrowed("https://itunes.apple.com")
+    }
+
+    pub(crate) fn build(self) -> Itunes {
+        Itunes {
+            base_url: self.base_url.trim_end_matches('/').to_string(),
+            country: self.country,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Itunes {
+    base_url: String,
+    country: Option<String>,
+}
+
+impl Itunes {
+    pub(crate) async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<PodcastDirectoryEntry>> {
+        let limit = limit.to_string();
+        let mut params = vec![
+            ("media", "podcast"),
+            ("entity", "podcast"),
+            ("term", query),
+            ("limit", limit.as_str()),
+        ];
+        if let Some(ref country) = self.country {
+            params.push(("country", country.as_str()));
+        }
+        let res = client
+            .get(format!("{}/search", self.base_url))
+            .query(&params)
+            .send()
+            .await
+            .context("unable to send request")?;
+        let res = res.error_for_status().context("invalid response status")?;
+        let payload: SearchResponse = res.json().await.context("unable to read response")?;
+        Ok(payload
+            .results
+            .into_iter()
+            .filter_map(SearchResult::into_entry)
+            .collect())
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<SearchResult>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResult {
+    collection_name: Option<String>,
+    artist_name: Option<String>,
+    feed_url: Option<String>,
+    artwork_url_600: Option<String>,
+    artwork_url_100: Option<String>,
+    collection_view_url: Option<String>,
+}
+
+impl SearchResult {
+    /// Podcasts without a public feed cannot be subscribed, they are skipped
+    fn into_entry(self) -> Option<PodcastDirectoryEntry> {
+        Some(PodcastDirectoryEntry {
+            title: super::non_empty(self.collection_name)?,
+            author: super::non_empty(self.artist_name),
+            description: None,
+            feed_url: super::non_empty(self.feed_url)?,
+            image_url: super::non_empty(self.artwork_url_600)
+                .or_else(|| super::non_empty(self.artwork_url_100)),
+            website: super::non_empty(self.collection_view_url),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entertainarr_domain::podcast::prelude::PodcastDirectory;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const RESPONSE: &str = include_str!("../assets/itunes-search.json");
+
+    #[tokio::test]
+    async fn should_search_podcasts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(RESPONSE, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = crate::Config::Itunes(super::Config {
+            base_url: format!("{}/", server.uri()).into(),
+            country: Some("fr".into()),
+        })
+        .build()
+        .unwrap();
+
+        let result = client.search("rustacean", 10).await.unwrap();
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].url.query(),
+            Some("media=podcast&entity=podcast&term=rustacean&limit=10&country=fr")
+        );
+        // the second result has no feed url
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].title, "Rustacean Station");
+        assert_eq!(result[0].author.as_deref(), Some("Rustacean Station"));
+        assert_eq!(
+            result[0].feed_url,
+            "https://rustacean-station.org/podcast.rss"
+        );
+        assert_eq!(
+            result[0].image_url.as_deref(),
+            Some("https://is1-ssl.mzstatic.com/image/thumb/600x600bb.jpg")
+        );
+    }
+}
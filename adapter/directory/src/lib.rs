@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use entertainarr_domain::podcast::entity::PodcastDirectoryEntry;
+
+mod itunes;
+mod podcast_index;
+
+const USER_AGENT: &str = concat!("entertainarr/", env!("CARGO_PKG_VERSION"));
+/// The search is done while the user waits for the answer
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Podcast directory configuration, the iTunes Search API is used by default
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "provider", rename_all = "kebab-case")]
+pub enum Config {
+    Itunes(itunes::Config),
+    PodcastIndex(podcast_index::Config),
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::Itunes(itunes::Config::default())
+    }
+}
+
+impl Config {
+    pub fn build(self) -> anyhow::Result<DirectoryClient> {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(TIMEOUT)
+            .build()
+            .context("unable to build http client")?;
+        let provider = match self {
+            Self::Itunes(inner) => Provider::Itunes(inner.build()),
+            Self::PodcastIndex(inner) => Provider::PodcastIndex(inner.build()?),
+        };
+        Ok(DirectoryClient { client, provider })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Provider {
+    Itunes(itunes::Itunes),
+    PodcastIndex(podcast_index::PodcastIndex),
+}
+
+impl Provider {
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Itunes(_) => "itunes",
+            Self::PodcastIndex(_) => "podcast-index",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DirectoryClient {
+    client: reqwest::Client,
+    provider: Provider,
+}
+
+impl entertainarr_domain::podcast::prelude::PodcastDirectory for DirectoryClient {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            directory.provider = self.provider.name(),
+            directory.query = query,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn search(&self, query: &str, limit: u32) -> anyhow::Result<Vec<PodcastDirectoryEntry>> {
+        match self.provider {
+            Provider::Itunes(ref inner) => inner.search(&self.client, query, limit).await,
+            Provider::PodcastIndex(ref inner) => inner.search(&self.client, query, limit).await,
+        }
+    }
+}
+
+/// Ignores blank values, that directories tend to return instead of omitting the field
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn should_default_to_itunes() {
+        assert!(matches!(super::Config::default(), super::Config::Itunes(_)));
+        let config: super::Config = serde_json::from_str(r#"{"provider":"itunes"}"#).unwrap();
+        assert!(matches!(config, super::Config::Itunes(_)));
+        let config: super::Config = serde_json::from_str(
+            r#"{"provider":"podcast-index","api_key":"key","api_secret":"secret"}"#,
+        )
+        .unwrap();
+        assert!(matches!(config, super::Config::PodcastIndex(_)));
+    }
+}
@@ -0,0 +1,183 @@
+//! Client of the Podcast Index API
+//!
+//! See <https://podcastindex-org.github.io/docs-api/>
+
+use std::borrow::Cow;
+
+use anyhow::Context;
+use entertainarr_domain::podcast::entity::PodcastDirectoryEntry;
+use sha1::Digest;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    #[serde(default = "Config::default_base_url")]
+    pub base_url: Cow<'static, str>,
+    pub api_key: String,
+    pub api_secret: String,
+}
+
+impl Config {
+    pub const fn default_base_url() -> Cow<'static, str> {
+        Cow::Borrowed("https://api.podcastindex.org")
+    }
+
+    pub(crate) fn build(self) -> anyhow::Result<PodcastIndex> {
+        if self.api_key.is_empty() || self.api_secret.is_empty() {
+            anyhow::bail!("podcast index requires an api key and secret");
+        }
+        Ok(PodcastIndex {
+            base_url: self.base_url.trim_end_matches('/').to_string(),
+            api_key: self.api_key,
+            api_secret: self.api_secret,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct PodcastIndex {
+    base_url: String,
+    api_key: String,
+    api_secret: String,
+}
+
+impl PodcastIndex {
+    /// Hash expected in the authorization header, computed from the credentials and the request time
+    fn authorization(&self, timestamp: i64) -> String {
+        let mut hasher = sha1::Sha1::new();
+        hasher.update(self.api_key.as_bytes());
+        hasher.update(self.api_secret.as_bytes());
+        hasher.update(timestamp.to_string().as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    pub(crate) async fn search(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<PodcastDirectoryEntry>> {
+        let timestamp = chrono::Utc::now().timestamp();
+        let res = client
+            .get(format!("{}/api/1.0/search/byterm", self.base_url))
+            .query(&[("q", query), ("max", limit.to_string().as_str())])
+            .header("X-Auth-Key", self.api_key.as_str())
+            .header("X-Auth-Date", timestamp.to_string())
+            .header("Authorization", self.authorization(timestamp))
+            .send()
+            .await
+            .context("unable to send request")?;
+        let res = res.error_for_status().context("invalid response status")?;
+        let payload: SearchResponse = res.json().await.context("unable to read response")?;
+        Ok(payload
+            .feeds
+            .into_iter()
+            .filter_map(Feed::into_entry)
+            .collect())
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    feeds: Vec<Feed>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Feed {
+    title: Option<String>,
+    author: Option<String>,
+    description: Option<String>,
+    url: Option<String>,
+    image: Option<String>,
+    artwork: Option<String>,
+    link: Option<String>,
+}
+
+impl Feed {
+    fn into_entry(self) -> Option<PodcastDirectoryEntry> {
+        Some(PodcastDirectoryEntry {
+            title: super::non_empty(self.title)?,
+            author: super::non_empty(self.author),
+            description: super::non_empty(self.description),
+            feed_url: super::non_empty(self.url)?,
+            image_url: super::non_empty(self.artwork).or_else(|| super::non_empty(self.image)),
+            website: super::non_empty(self.link),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entertainarr_domain::podcast::prelude::PodcastDirectory;
+    use wiremock::matchers::{header, header_exists, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const RESPONSE: &str = include_str!("../assets/podcast-index-search.json");
+
+    #[test]
+    fn should_compute_authorization() {
+        let client = super::Config {
+            base_url: super::Config::default_base_url(),
+            api_key: "key".into(),
+            api_secret: "secret".into(),
+        }
+        .build()
+        .unwrap();
+        // sha1("keysecret1700000000")
+        assert_eq!(
+            client.authorization(1700000000),
+            "abaf71c02050c31e4d4e6b08c1625173af0445ba"
+        );
+    }
+
+    #[test]
+    fn should_require_credentials() {
+        let config = super::Config {
+            base_url: super::Config::default_base_url(),
+            api_key: String::new(),
+            api_secret: String::new(),
+        };
+        assert!(config.build().is_err());
+    }
+
+    #[tokio::test]
+    async fn should_search_podcasts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/1.0/search/byterm"))
+            .and(query_param("q", "podcasting"))
+            .and(query_param("max", "5"))
+            .and(header("x-auth-key", "key"))
+            .and(header_exists("x-auth-date"))
+            .and(header_exists("authorization"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(RESPONSE, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = crate::Config::PodcastIndex(super::Config {
+            base_url: server.uri().into(),
+            api_key: "key".into(),
+            api_secret: "secret".into(),
+        })
+        .build()
+        .unwrap();
+
+        let result = client.search("podcasting", 5).await.unwrap();
+        // the second feed has no title
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].title, "Podcasting 2.0");
+        assert_eq!(
+            result[0].feed_url,
+            "https://mp3s.nashownotes.com/pc20rss.xml"
+        );
+        // empty artwork falls back on the image
+        assert_eq!(
+            result[0].image_url.as_deref(),
+            Some("https://noagendaassets.com/enc/pc20.png")
+        );
+        assert_eq!(
+            result[0].website.as_deref(),
+            Some("https://podcastindex.org")
+        );
+    }
+}
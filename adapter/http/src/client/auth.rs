@@ -18,6 +18,7 @@ impl super::Client {
                 attributes: AuthenticationRequestAttributes {
                     email: email.into(),
                     password: password.into(),
+                    device_name: None,
                 },
             }))
             .send()
@@ -1,7 +1,9 @@
 use anyhow::Context;
 
 use crate::entity::ApiResource;
-use crate::entity::podcast::PodcastSubscribeDocument;
+use crate::entity::podcast::{
+    PodcastDirectoryDocument, PodcastImportDocument, PodcastSubscribeDocument,
+};
 
 impl super::Client {
     pub async fn podcast_subscribe(&self, feed_url: &str) -> anyhow::Result<()> {
@@ -20,4 +22,61 @@ impl super::Client {
         res.error_for_status_ref()?;
         Ok(())
     }
+
+    pub async fn podcast_import(
+        &self,
+        opml: String,
+    ) -> anyhow::Result<ApiResource<Vec<PodcastImportDocument>>> {
+        let Some(token) = self.token.as_deref() else {
+            anyhow::bail!("unauthorized")
+        };
+        let url = format!("{}/api/users/me/podcasts/import", self.base_url);
+        let res = self
+            .inner
+            .post(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "text/x-opml")
+            .body(opml)
+            .send()
+            .await
+            .context("unable to send request")?;
+        res.error_for_status_ref()?;
+        res.json().await.context("unable to read response")
+    }
+
+    pub async fn podcast_export(&self) -> anyhow::Result<String> {
+        let Some(token) = self.token.as_deref() else {
+            anyhow::bail!("unauthorized")
+        };
+        let url = format!("{}/api/users/me/podcasts/export", self.base_url);
+        let res = self
+            .inner
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .send()
+            .await
+            .context("unable to send request")?;
+        res.error_for_status_ref()?;
+        res.text().await.context("unable to read response")
+    }
+
+    pub async fn podcast_discover(
+        &self,
+        query: &str,
+    ) -> anyhow::Result<ApiResource<Vec<PodcastDirectoryDocument>>> {
+        let Some(token) = self.token.as_deref() else {
+            anyhow::bail!("unauthorized")
+        };
+        let url = format!("{}/api/podcast-directory", self.base_url);
+        let res = self
+            .inner
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .query(&[("q", query)])
+            .send()
+            .await
+            .context("unable to send request")?;
+        res.error_for_status_ref()?;
+        res.json().await.context("unable to read response")
+    }
 }
@@ -2,7 +2,10 @@ use anyhow::Context;
 
 use crate::entity::{
     ApiResource,
-    podcast_episode::{PodcastEpisodeDocument, PodcastEpisodeRelation},
+    podcast_episode::{
+        PodcastEpisodeDocument, PodcastEpisodeProgressDocument,
+        PodcastEpisodeProgressUpdateDocument, PodcastEpisodeRelation,
+    },
 };
 
 impl super::Client {
@@ -28,4 +31,35 @@ impl super::Client {
             res.json().await.context("unable to deserialize payload")
         }
     }
+
+    pub async fn podcast_episode_progress_update(
+        &self,
+        podcast_episode_id: u64,
+        progress: std::time::Duration,
+        completed: bool,
+    ) -> anyhow::Result<ApiResource<PodcastEpisodeProgressDocument>> {
+        let Some(token) = self.token.as_deref() else {
+            anyhow::bail!("unauthorized")
+        };
+        let url = format!(
+            "{}/api/users/me/podcast-episodes/{podcast_episode_id}/progress",
+            self.base_url
+        );
+        let res = self
+            .inner
+            .put(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .json(&ApiResource::new(
+                PodcastEpisodeProgressUpdateDocument::new(progress, completed),
+            ))
+            .send()
+            .await
+            .context("unable to send request")?;
+        if let Err(err) = res.error_for_status_ref() {
+            let body = res.text().await.context("unable to read payload body")?;
+            Err(anyhow::Error::from(err).context(body))
+        } else {
+            res.json().await.context("unable to deserialize payload")
+        }
+    }
 }
@@ -22,15 +22,20 @@ impl<'a> AuthenticationRequestDocument<'a> {
             attributes: AuthenticationRequestAttributes {
                 email: email.into(),
                 password: password.into(),
+                device_name: None,
             },
         }
     }
 }
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AuthenticationRequestAttributes<'a> {
     pub email: Cow<'a, str>,
     pub password: Cow<'a, str>,
+    /// Name displayed in the list of sessions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<Cow<'a, str>>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
@@ -42,4 +47,78 @@ pub struct AuthenticationTokenDocument {
 }
 
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
-pub struct AuthenticationTokenAttributes {}
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationTokenAttributes {
+    /// Token to exchange against a new access token once it expires
+    pub refresh_token: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct AuthenticationRefreshDocument<'a> {
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("authentication-refreshes"),
+    pub attributes: AuthenticationRefreshAttributes<'a>,
+}
+
+impl<'a> AuthenticationRefreshDocument<'a> {
+    pub fn new(refresh_token: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            kind: Default::default(),
+            attributes: AuthenticationRefreshAttributes {
+                refresh_token: refresh_token.into(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationRefreshAttributes<'a> {
+    pub refresh_token: Cow<'a, str>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDocument {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("sessions"),
+    pub attributes: SessionAttributes,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAttributes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether the session is the one of the access token used for the request
+    pub current: bool,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Token granting access to the personal feed of the user, only provided when it's issued
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedTokenDocument {
+    /// Identifier of the user
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("feed-tokens"),
+    pub attributes: FeedTokenAttributes,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedTokenAttributes {
+    pub token: String,
+    /// Path of the feed, including the token, to subscribe to in a third-party player
+    pub feed_url: String,
+}
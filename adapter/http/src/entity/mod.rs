@@ -3,6 +3,7 @@ use std::borrow::Cow;
 pub mod auth;
 pub mod podcast;
 pub mod podcast_episode;
+pub mod search;
 
 fn default_includes<T>() -> Vec<T> {
     Vec::new()
@@ -14,6 +15,8 @@ pub struct ApiResource<T, I = ()> {
     pub data: T,
     #[serde(default = "default_includes", skip_serializing_if = "Vec::is_empty")]
     pub includes: Vec<I>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ApiMeta>,
 }
 
 impl<T> ApiResource<T> {
@@ -21,10 +24,21 @@ impl<T> ApiResource<T> {
         Self {
             data,
             includes: Vec::new(),
+            meta: None,
         }
     }
 }
 
+/// Pagination details of a list
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMeta {
+    /// Number of items matching the request, all pages included
+    pub total: u64,
+    /// Cursor to request the page following this one, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Relation<T> {
@@ -51,6 +65,14 @@ impl ApiError {
         }
     }
 
+    pub fn bad_gateway(message: impl Into<Cow<'static, str>>) -> ApiError {
+        ApiError {
+            status_code: axum::http::StatusCode::BAD_GATEWAY,
+            message: message.into(),
+            detail: None,
+        }
+    }
+
     pub fn bad_request(message: impl Into<Cow<'static, str>>) -> ApiError {
         ApiError {
             status_code: axum::http::StatusCode::BAD_REQUEST,
@@ -67,6 +89,14 @@ impl ApiError {
         }
     }
 
+    pub fn not_found(message: impl Into<Cow<'static, str>>) -> ApiError {
+        ApiError {
+            status_code: axum::http::StatusCode::NOT_FOUND,
+            message: message.into(),
+            detail: None,
+        }
+    }
+
     pub fn unauthorized(message: impl Into<Cow<'static, str>>) -> ApiError {
         ApiError {
             status_code: axum::http::StatusCode::UNAUTHORIZED,
@@ -1,4 +1,11 @@
-#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub mod errors {
+    pub const CODE_PLAYBACK_SPEED_OUT_OF_RANGE: &str = "playback-speed-out-of-range";
+    pub const CODE_SKIP_TOO_LONG: &str = "skip-too-long";
+    pub const CODE_AUTO_DOWNLOAD_TOO_LARGE: &str = "auto-download-too-large";
+    pub const CODE_IMPORT_TOO_LARGE: &str = "import-too-large";
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PodcastDocument {
     pub id: u64,
@@ -15,7 +22,7 @@ pub struct PodcastEntity {
     pub kind: monostate::MustBe!("podcasts"),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PodcastAttributes {
     pub title: String,
@@ -28,10 +35,106 @@ pub struct PodcastAttributes {
     pub feed_url: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub website: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explicit: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub persons: Vec<PodcastPersonAttributes>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub funding: Vec<PodcastFundingAttributes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_synced_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync_error: Option<String>,
+    /// Subscription of the current user, provided when fetching a single podcast or the
+    /// subscriptions of the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscription: Option<PodcastSubscriptionAttributes>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Person taking part in a show or an episode
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastPersonAttributes {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub href: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+}
+
+/// Link to support a show
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastFundingAttributes {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastSubscriptionAttributes {
+    /// New episodes are appended to the queue of the user
+    #[serde(default)]
+    pub auto_queue: bool,
+    /// Default playback speed, `1.0` being the normal speed
+    pub playback_speed: f32,
+    /// Seconds to skip at the beginning of the episodes
+    pub skip_start: u32,
+    /// Seconds to skip at the end of the episodes
+    pub skip_end: u32,
+    /// Number of latest episodes kept downloaded on the server, `0` when disabled
+    pub auto_download: u32,
+    pub include_in_home: bool,
+    pub notify: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Settings to change on the subscription, the missing ones are kept
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastSubscriptionUpdateDocument {
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-subscriptions"),
+    pub attributes: PodcastSubscriptionUpdateAttributes,
+}
+
+impl PodcastSubscriptionUpdateDocument {
+    pub fn new(attributes: PodcastSubscriptionUpdateAttributes) -> Self {
+        Self {
+            kind: Default::default(),
+            attributes,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastSubscriptionUpdateAttributes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playback_speed: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_start: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_end: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_download: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_in_home: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<bool>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PodcastSubscribeDocument {
@@ -56,3 +159,72 @@ impl PodcastSubscribeDocument {
 pub struct PodcastSubscribeAttributes {
     pub feed_url: String,
 }
+
+/// Number of latest episodes of the podcast kept downloaded on the server
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastDownloadPolicyDocument {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-download-policies"),
+    pub attributes: PodcastDownloadPolicyAttributes,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastDownloadPolicyAttributes {
+    /// `0` when the episodes are not downloaded
+    pub latest: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastImportDocument {
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-imports"),
+    pub attributes: PodcastImportAttributes,
+    /// Subscribed podcast, missing when the feed couldn't be imported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<PodcastImportRelationship>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastImportAttributes {
+    pub feed_url: String,
+    /// Title provided by the OPML outline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct PodcastImportRelationship {
+    pub podcast: super::Relation<PodcastEntity>,
+}
+
+/// Podcast found in an external directory, identified by its feed url
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastDirectoryDocument {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-directory-entries"),
+    pub attributes: PodcastDirectoryAttributes,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastDirectoryAttributes {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub feed_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+}
@@ -1,5 +1,9 @@
 use std::time::Duration;
 
+pub mod errors {
+    pub const CODE_INVALID_CURSOR: &str = "invalid-cursor";
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PodcastEpisodeDocument {
@@ -29,16 +33,165 @@ pub struct PodcastEpisodeAttributes {
     pub file_size: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub file_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub season_number: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub episode_number: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub episode_type: Option<PodcastEpisodeType>,
+    /// Chapters are provided by the `/podcast-episodes/{id}/chapters` resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chapters_url: Option<String>,
+    /// Transcript is provided by the `/podcast-episodes/{id}/transcript` resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub persons: Vec<super::podcast::PodcastPersonAttributes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Streams the copy of the file stored on the server, when the episode was downloaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<PodcastEpisodeProgressAttributes>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PodcastEpisodeType {
+    Full,
+    Trailer,
+    Bonus,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastEpisodeChapterDocument {
+    /// Identifier of the podcast episode followed by the position of the chapter
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-episode-chapters"),
+    pub attributes: PodcastEpisodeChapterAttributes,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastEpisodeChapterAttributes {
+    pub start: Duration,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastEpisodeTranscriptCueDocument {
+    /// Identifier of the podcast episode followed by the position of the cue
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-episode-transcript-cues"),
+    pub attributes: PodcastEpisodeTranscriptCueAttributes,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastEpisodeTranscriptCueAttributes {
+    pub start: Duration,
+    pub end: Duration,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastEpisodeProgressDocument {
+    /// Identifier of the podcast episode
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-episode-progresses"),
+    pub attributes: PodcastEpisodeProgressAttributes,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastEpisodeProgressAttributes {
+    pub progress: Duration,
+    pub completed: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastEpisodeProgressUpdateDocument {
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-episode-progresses"),
+    pub attributes: PodcastEpisodeProgressUpdateAttributes,
+}
+
+impl PodcastEpisodeProgressUpdateDocument {
+    pub fn new(progress: Duration, completed: bool) -> Self {
+        Self {
+            kind: Default::default(),
+            attributes: PodcastEpisodeProgressUpdateAttributes {
+                progress,
+                completed,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastEpisodeProgressUpdateAttributes {
+    pub progress: Duration,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// Moves an episode of the queue to the position, starting from `0`
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastQueueMoveDocument {
+    #[serde(rename = "type")]
+    pub kind: monostate::MustBe!("podcast-queue-entries"),
+    pub attributes: PodcastQueueMoveAttributes,
+}
+
+impl PodcastQueueMoveDocument {
+    pub fn new(position: u32) -> Self {
+        Self {
+            kind: Default::default(),
+            attributes: PodcastQueueMoveAttributes { position },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastQueueMoveAttributes {
+    pub position: u32,
+}
+
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(test, derive(PartialEq, Eq))]
 pub enum PodcastEpisodeField {
     #[default]
     PublishedAt,
+    Duration,
+    Title,
+    ProgressUpdatedAt,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
@@ -0,0 +1,22 @@
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum SearchDocument {
+    Podcast(super::podcast::PodcastDocument),
+    PodcastEpisode(super::podcast_episode::PodcastEpisodeDocument),
+}
+
+impl SearchDocument {
+    pub fn into_podcast(self) -> Option<super::podcast::PodcastDocument> {
+        match self {
+            Self::Podcast(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    pub fn into_podcast_episode(self) -> Option<super::podcast_episode::PodcastEpisodeDocument> {
+        match self {
+            Self::PodcastEpisode(inner) => Some(inner),
+            _ => None,
+        }
+    }
+}
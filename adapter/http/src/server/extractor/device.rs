@@ -0,0 +1,142 @@
+use std::convert::Infallible;
+use std::net::IpAddr;
+
+use axum::extract::FromRequestParts;
+use axum::http::HeaderMap;
+use entertainarr_domain::auth::entity::DeviceInfo;
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Device the request is sent from, the name is left to the request payload
+#[derive(Clone, Debug, Default)]
+pub struct Device(pub DeviceInfo);
+
+/// Each proxy appends the address it received the request from, so the client is the last
+/// address that isn't one of the trusted proxies
+fn forwarded_for(headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> Option<String> {
+    let addresses = headers
+        .get(X_FORWARDED_FOR)
+        .and_then(|value| value.to_str().ok())?
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .collect::<Vec<_>>();
+    addresses
+        .iter()
+        .rev()
+        .find(|value| {
+            value
+                .parse::<IpAddr>()
+                .map_or(true, |address| !trusted_proxies.contains(&address))
+        })
+        .or_else(|| addresses.first())
+        .map(|value| value.to_string())
+}
+
+impl<S> FromRequestParts<S> for Device
+where
+    S: crate::server::prelude::ServerState,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let user_agent = parts
+            .headers
+            .get(axum::http::header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+        let peer = super::peer_address(parts);
+        let ip_address = match peer {
+            Some(peer) if state.trusted_proxies().contains(&peer) => {
+                forwarded_for(&parts.headers, state.trusted_proxies())
+                    .or_else(|| Some(peer.to_string()))
+            }
+            _ => peer.map(|peer| peer.to_string()),
+        };
+        Ok(Device(DeviceInfo {
+            name: None,
+            user_agent,
+            ip_address,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::{ConnectInfo, FromRequestParts};
+
+    use crate::server::prelude::tests::MockServerState;
+
+    fn request(forwarded_for: &str) -> axum::http::request::Parts {
+        let (parts, _) = axum::http::Request::builder()
+            .header("User-Agent", "Mozilla/5.0")
+            .header("X-Forwarded-For", forwarded_for)
+            .extension(ConnectInfo(std::net::SocketAddr::from((
+                [127, 0, 0, 1],
+                80,
+            ))))
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts
+    }
+
+    #[tokio::test]
+    async fn should_prefer_forwarded_address_from_trusted_proxy() {
+        let state = MockServerState::builder()
+            .trusted_proxies(vec![
+                "127.0.0.1".parse().unwrap(),
+                "192.168.1.1".parse().unwrap(),
+            ])
+            .build();
+        let mut parts = request("10.0.0.1, 192.168.1.1");
+        let super::Device(device) = super::Device::from_request_parts(&mut parts, &state)
+            .await
+            .unwrap();
+        assert_eq!(device.user_agent.as_deref(), Some("Mozilla/5.0"));
+        assert_eq!(device.ip_address.as_deref(), Some("10.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn should_ignore_addresses_added_by_the_client() {
+        let state = MockServerState::builder()
+            .trusted_proxies(vec!["127.0.0.1".parse().unwrap()])
+            .build();
+        let mut parts = request("1.2.3.4, 10.0.0.1");
+        let super::Device(device) = super::Device::from_request_parts(&mut parts, &state)
+            .await
+            .unwrap();
+        assert_eq!(device.ip_address.as_deref(), Some("10.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn should_ignore_forwarded_address_from_untrusted_peer() {
+        let state = MockServerState::builder().build();
+        let mut parts = request("10.0.0.1");
+        let super::Device(device) = super::Device::from_request_parts(&mut parts, &state)
+            .await
+            .unwrap();
+        assert_eq!(device.ip_address.as_deref(), Some("127.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn should_fallback_on_peer_address() {
+        let (mut parts, _) = axum::http::Request::builder()
+            .extension(ConnectInfo(std::net::SocketAddr::from((
+                [127, 0, 0, 1],
+                80,
+            ))))
+            .body(())
+            .unwrap()
+            .into_parts();
+        let state = MockServerState::builder().build();
+        let super::Device(device) = super::Device::from_request_parts(&mut parts, &state)
+            .await
+            .unwrap();
+        assert!(device.user_agent.is_none());
+        assert_eq!(device.ip_address.as_deref(), Some("127.0.0.1"));
+    }
+}
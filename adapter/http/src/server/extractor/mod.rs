@@ -1 +1,16 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::extract::ConnectInfo;
+
+pub mod device;
+pub mod origin;
 pub mod user;
+
+/// Address of the peer the request was received from, the forwarded headers being only
+/// honoured when it is one of the trusted proxies
+fn peer_address(parts: &axum::http::request::Parts) -> Option<IpAddr> {
+    parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
@@ -0,0 +1,116 @@
+use std::convert::Infallible;
+
+use axum::extract::FromRequestParts;
+use axum::http::uri::Authority;
+use axum::http::{HeaderMap, header};
+
+const X_FORWARDED_HOST: &str = "x-forwarded-host";
+const X_FORWARDED_PROTO: &str = "x-forwarded-proto";
+
+/// Scheme and host the client reached the server with, like `https://example.com`
+#[derive(Clone, Debug)]
+pub struct Origin(pub String);
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        // a proxy chain may list several values, the first one being the client's
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+impl<S> FromRequestParts<S> for Origin
+where
+    S: crate::server::prelude::ServerState,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let trusted =
+            super::peer_address(parts).is_some_and(|peer| state.trusted_proxies().contains(&peer));
+        let (scheme, host) = if trusted {
+            (
+                header_value(&parts.headers, X_FORWARDED_PROTO),
+                header_value(&parts.headers, X_FORWARDED_HOST)
+                    .or_else(|| header_value(&parts.headers, header::HOST.as_str())),
+            )
+        } else {
+            (None, header_value(&parts.headers, header::HOST.as_str()))
+        };
+        let scheme = match scheme {
+            Some("https") => "https",
+            _ => "http",
+        };
+        let host = host
+            .filter(|value| {
+                value
+                    .parse::<Authority>()
+                    .is_ok_and(|auth| auth.as_str() == *value)
+            })
+            .unwrap_or("localhost");
+        Ok(Origin(format!("{scheme}://{host}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::{ConnectInfo, FromRequestParts};
+
+    use crate::server::prelude::tests::MockServerState;
+
+    fn request(headers: &[(&str, &str)]) -> axum::http::request::Parts {
+        let mut builder = axum::http::Request::builder().extension(ConnectInfo(
+            std::net::SocketAddr::from(([127, 0, 0, 1], 80)),
+        ));
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        let (parts, _) = builder.body(()).unwrap().into_parts();
+        parts
+    }
+
+    #[tokio::test]
+    async fn should_use_forwarded_headers_from_trusted_proxy() {
+        let state = MockServerState::builder()
+            .trusted_proxies(vec!["127.0.0.1".parse().unwrap()])
+            .build();
+        let mut parts = request(&[
+            ("Host", "internal:3000"),
+            ("X-Forwarded-Proto", "https"),
+            ("X-Forwarded-Host", "example.com"),
+        ]);
+        let super::Origin(origin) = super::Origin::from_request_parts(&mut parts, &state)
+            .await
+            .unwrap();
+        assert_eq!(origin, "https://example.com");
+    }
+
+    #[tokio::test]
+    async fn should_ignore_forwarded_headers_from_untrusted_peer() {
+        let state = MockServerState::builder().build();
+        let mut parts = request(&[
+            ("Host", "example.com"),
+            ("X-Forwarded-Proto", "https"),
+            ("X-Forwarded-Host", "attacker.com"),
+        ]);
+        let super::Origin(origin) = super::Origin::from_request_parts(&mut parts, &state)
+            .await
+            .unwrap();
+        assert_eq!(origin, "http://example.com");
+    }
+
+    #[tokio::test]
+    async fn should_fallback_on_localhost_with_invalid_host() {
+        let state = MockServerState::builder().build();
+        let mut parts = request(&[("Host", "example.com/<script>")]);
+        let super::Origin(origin) = super::Origin::from_request_parts(&mut parts, &state)
+            .await
+            .unwrap();
+        assert_eq!(origin, "http://localhost");
+    }
+}
@@ -1,3 +1,5 @@
+use axum::extract::FromRequestParts;
+use entertainarr_domain::auth::entity::Principal;
 use entertainarr_domain::auth::prelude::{AuthenticationService, VerifyError};
 
 use crate::entity::ApiError;
@@ -5,7 +7,27 @@ use crate::entity::ApiError;
 #[derive(Clone, Copy, Debug)]
 pub struct CurrentUser(pub u64);
 
-impl<S> axum::extract::FromRequestParts<S> for CurrentUser
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    S: crate::server::prelude::ServerState,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        CurrentSession::from_request_parts(parts, state)
+            .await
+            .map(|CurrentSession(principal)| CurrentUser(principal.user_id))
+    }
+}
+
+/// User and session of the access token
+#[derive(Clone, Debug)]
+pub struct CurrentSession(pub Principal);
+
+impl<S> FromRequestParts<S> for CurrentSession
 where
     S: crate::server::prelude::ServerState,
 {
@@ -27,7 +49,7 @@ where
                 .authentication_service()
                 .verify(authorization)
                 .await
-                .map(|profile| CurrentUser(profile.id))
+                .map(CurrentSession)
                 .map_err(|err| match err {
                     VerifyError::ExpiredToken => {
                         ApiError::unauthorized("authorization token expired")
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use axum::{Json, extract::State};
 
 use entertainarr_domain::auth::{
@@ -6,18 +8,21 @@ use entertainarr_domain::auth::{
 };
 
 use crate::entity::auth::{
-    AuthenticationRequestDocument, AuthenticationTokenDocument,
+    AuthenticationRequestDocument, AuthenticationTokenAttributes, AuthenticationTokenDocument,
     errors::{CODE_EMAIL_TOO_SHORT, CODE_PASSWORD_TOO_SHORT},
 };
 use crate::entity::{ApiError, ApiErrorDetail, ApiResource};
+use crate::server::extractor::device::Device;
 
 pub async fn handle<S>(
     State(state): State<S>,
+    Device(mut device): Device,
     Json(payload): Json<ApiResource<AuthenticationRequestDocument<'static>>>,
 ) -> Result<Json<ApiResource<AuthenticationTokenDocument>>, ApiError>
 where
     S: crate::server::prelude::ServerState,
 {
+    device.name = payload.data.attributes.device_name.map(Cow::into_owned);
     let email = Email::try_new(payload.data.attributes.email).map_err(|_| {
         ApiError::bad_request("invalid credentials")
             .with_detail(ApiErrorDetail::new("email", CODE_EMAIL_TOO_SHORT))
@@ -28,13 +33,19 @@ where
     })?;
     state
         .authentication_service()
-        .login(LoginRequest { email, password })
+        .login(LoginRequest {
+            email,
+            password,
+            device,
+        })
         .await
         .map(|res| {
             Json(ApiResource::new(AuthenticationTokenDocument {
                 id: res.token,
                 kind: Default::default(),
-                attributes: Default::default(),
+                attributes: AuthenticationTokenAttributes {
+                    refresh_token: res.refresh_token,
+                },
             }))
         })
         .map_err(|err| match err {
@@ -64,6 +75,7 @@ mod tests {
             Box::pin(async move {
                 Ok(LoginSuccess {
                     token: String::from("token"),
+                    refresh_token: String::from("refresh-token"),
                 })
             })
         });
@@ -72,9 +84,13 @@ mod tests {
             .build();
         let payload = AuthenticationRequestDocument::new("user@example.com", "password");
         assert!(
-            super::handle(State(state), Json(crate::entity::ApiResource::new(payload)))
-                .await
-                .is_ok()
+            super::handle(
+                State(state),
+                Default::default(),
+                Json(crate::entity::ApiResource::new(payload))
+            )
+            .await
+            .is_ok()
         );
     }
 
@@ -82,9 +98,13 @@ mod tests {
     async fn should_fail_validation_invalid_username() {
         let state = MockServerState::default();
         let payload = AuthenticationRequestDocument::new("  ", "password");
-        let err = super::handle(State(state), Json(crate::entity::ApiResource::new(payload)))
-            .await
-            .unwrap_err();
+        let err = super::handle(
+            State(state),
+            Default::default(),
+            Json(crate::entity::ApiResource::new(payload)),
+        )
+        .await
+        .unwrap_err();
         assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
         assert_eq!(err.message, "invalid credentials");
         let detail = err.detail.unwrap();
@@ -96,9 +116,13 @@ mod tests {
     async fn should_fail_validation_empty_password() {
         let state = MockServerState::default();
         let payload = AuthenticationRequestDocument::new("user@example.com", "          ");
-        let err = super::handle(State(state), Json(crate::entity::ApiResource::new(payload)))
-            .await
-            .unwrap_err();
+        let err = super::handle(
+            State(state),
+            Default::default(),
+            Json(crate::entity::ApiResource::new(payload)),
+        )
+        .await
+        .unwrap_err();
         assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
         assert_eq!(err.message, "invalid credentials");
         let detail = err.detail.unwrap();
@@ -110,9 +134,13 @@ mod tests {
     async fn should_fail_validation_invalid_password() {
         let state = MockServerState::default();
         let payload = AuthenticationRequestDocument::new("user@example.com", "foo");
-        let err = super::handle(State(state), Json(crate::entity::ApiResource::new(payload)))
-            .await
-            .unwrap_err();
+        let err = super::handle(
+            State(state),
+            Default::default(),
+            Json(crate::entity::ApiResource::new(payload)),
+        )
+        .await
+        .unwrap_err();
         assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
         assert_eq!(err.message, "invalid credentials");
         let detail = err.detail.unwrap();
@@ -135,10 +163,13 @@ mod integration {
         auth_service.expect_login().returning(|req| {
             assert_eq!(req.email.into_inner(), "user@example.com");
             assert_eq!(req.password.into_inner(), "password");
+            assert_eq!(req.device.name.as_deref(), Some("Laptop"));
+            assert_eq!(req.device.user_agent.as_deref(), Some("Mozilla/5.0"));
 
             Box::pin(async move {
                 Ok(LoginSuccess {
                     token: String::from("token"),
+                    refresh_token: String::from("refresh-token"),
                 })
             })
         });
@@ -152,8 +183,9 @@ mod integration {
                     .uri("/api/auth/login")
                     .method(axum::http::Method::POST)
                     .header("Content-Type", "application/json")
+                    .header("User-Agent", "Mozilla/5.0")
                     .body(axum::body::Body::from(
-                        r#"{"data":{"attributes":{"email":"user@example.com","password":"password"},"type":"authentication-requests"}}"#,
+                        r#"{"data":{"attributes":{"email":"user@example.com","password":"password","deviceName":"Laptop"},"type":"authentication-requests"}}"#,
                     ))
                     .unwrap(),
             )
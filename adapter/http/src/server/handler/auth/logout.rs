@@ -0,0 +1,67 @@
+use axum::extract::State;
+
+use entertainarr_domain::auth::prelude::AuthenticationService;
+
+use crate::entity::ApiError;
+use crate::server::extractor::user::CurrentSession;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentSession(principal): CurrentSession,
+) -> Result<axum::http::StatusCode, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    state
+        .authentication_service()
+        .logout(&principal)
+        .await
+        .map(|_| axum::http::StatusCode::NO_CONTENT)
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to logout");
+            ApiError::internal()
+        })
+}
+
+#[cfg(test)]
+mod integration {
+    use crate::server::prelude::tests::MockServerState;
+
+    use entertainarr_domain::auth::{entity::Principal, prelude::MockAuthenticationService};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn should_revoke_session() {
+        let router = crate::server::handler::create();
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service.expect_verify().returning(|_| {
+            Box::pin(async move {
+                Ok(Principal {
+                    user_id: 1,
+                    session_id: 2,
+                })
+            })
+        });
+        auth_service.expect_logout().returning(|principal| {
+            assert_eq!(principal.user_id, 1);
+            assert_eq!(principal.session_id, 2);
+            Box::pin(async move { Ok(()) })
+        });
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let res = router
+            .with_state(state)
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/auth/logout")
+                    .method(axum::http::Method::POST)
+                    .header("Authorization", "Bearer token")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::NO_CONTENT);
+    }
+}
@@ -1,6 +1,8 @@
 use axum::routing::post;
 
 mod login;
+mod logout;
+mod refresh;
 mod signup;
 
 pub fn create<S>() -> axum::Router<S>
@@ -9,5 +11,7 @@ where
 {
     axum::Router::new()
         .route("/auth/login", post(login::handle::<S>))
+        .route("/auth/logout", post(logout::handle::<S>))
+        .route("/auth/refresh", post(refresh::handle::<S>))
         .route("/auth/signup", post(signup::handle::<S>))
 }
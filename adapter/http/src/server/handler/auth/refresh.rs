@@ -0,0 +1,90 @@
+use axum::{Json, extract::State};
+
+use entertainarr_domain::auth::prelude::{AuthenticationService, VerifyError};
+
+use crate::entity::auth::{
+    AuthenticationRefreshDocument, AuthenticationTokenAttributes, AuthenticationTokenDocument,
+};
+use crate::entity::{ApiError, ApiResource};
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    Json(payload): Json<ApiResource<AuthenticationRefreshDocument<'static>>>,
+) -> Result<Json<ApiResource<AuthenticationTokenDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    state
+        .authentication_service()
+        .refresh(payload.data.attributes.refresh_token.as_ref())
+        .await
+        .map(|res| {
+            Json(ApiResource::new(AuthenticationTokenDocument {
+                id: res.token,
+                kind: Default::default(),
+                attributes: AuthenticationTokenAttributes {
+                    refresh_token: res.refresh_token,
+                },
+            }))
+        })
+        .map_err(|err| match err {
+            VerifyError::ExpiredToken => ApiError::unauthorized("refresh token expired"),
+            VerifyError::InvalidToken => ApiError::unauthorized("refresh token invalid"),
+            VerifyError::Internal(err) => {
+                tracing::error!(error = %err, error.stacktrace = ?err, "unable to refresh token");
+                ApiError::internal()
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::ApiResource;
+    use crate::entity::auth::AuthenticationRefreshDocument;
+    use crate::server::prelude::tests::MockServerState;
+
+    use axum::{Json, extract::State, http::StatusCode};
+    use entertainarr_domain::auth::prelude::{
+        LoginSuccess, MockAuthenticationService, VerifyError,
+    };
+
+    #[tokio::test]
+    async fn should_succeed() {
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service.expect_refresh().returning(|refresh_token| {
+            assert_eq!(refresh_token, "previous");
+
+            Box::pin(async move {
+                Ok(LoginSuccess {
+                    token: String::from("token"),
+                    refresh_token: String::from("next"),
+                })
+            })
+        });
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let payload = AuthenticationRefreshDocument::new("previous");
+        let Json(res) = super::handle(State(state), Json(ApiResource::new(payload)))
+            .await
+            .unwrap();
+        assert_eq!(res.data.id, "token");
+        assert_eq!(res.data.attributes.refresh_token, "next");
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_token_invalid() {
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service
+            .expect_refresh()
+            .returning(|_| Box::pin(async move { Err(VerifyError::InvalidToken) }));
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let payload = AuthenticationRefreshDocument::new("previous");
+        let err = super::handle(State(state), Json(ApiResource::new(payload)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::UNAUTHORIZED);
+    }
+}
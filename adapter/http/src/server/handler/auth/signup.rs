@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use axum::{Json, extract::State};
 use entertainarr_domain::auth::{
     entity::{Email, Password},
@@ -5,18 +7,21 @@ use entertainarr_domain::auth::{
 };
 
 use crate::entity::auth::{
-    AuthenticationRequestDocument, AuthenticationTokenDocument,
+    AuthenticationRequestDocument, AuthenticationTokenAttributes, AuthenticationTokenDocument,
     errors::{CODE_EMAIL_CONFLICT, CODE_EMAIL_TOO_SHORT, CODE_PASSWORD_TOO_SHORT},
 };
 use crate::entity::{ApiError, ApiErrorDetail, ApiResource};
+use crate::server::extractor::device::Device;
 
 pub async fn handle<S>(
     State(state): State<S>,
+    Device(mut device): Device,
     Json(payload): Json<ApiResource<AuthenticationRequestDocument<'static>>>,
 ) -> Result<Json<ApiResource<AuthenticationTokenDocument>>, ApiError>
 where
     S: crate::server::prelude::ServerState,
 {
+    device.name = payload.data.attributes.device_name.map(Cow::into_owned);
     let email = Email::try_new(payload.data.attributes.email).map_err(|_| {
         ApiError::bad_request("invalid credentials")
             .with_detail(ApiErrorDetail::new("email", CODE_EMAIL_TOO_SHORT))
@@ -27,13 +32,19 @@ where
     })?;
     state
         .authentication_service()
-        .signup(SignupRequest { email, password })
+        .signup(SignupRequest {
+            email,
+            password,
+            device,
+        })
         .await
         .map(|res| {
             Json(ApiResource::new(AuthenticationTokenDocument {
                 id: res.token,
                 kind: Default::default(),
-                attributes: Default::default(),
+                attributes: AuthenticationTokenAttributes {
+                    refresh_token: res.refresh_token,
+                },
             }))
         })
         .map_err(|err| match err {
@@ -65,6 +76,7 @@ mod tests {
             Box::pin(async move {
                 Ok(LoginSuccess {
                     token: String::from("token"),
+                    refresh_token: String::from("refresh-token"),
                 })
             })
         });
@@ -73,9 +85,13 @@ mod tests {
             .build();
         let payload = AuthenticationRequestDocument::new("user@example.com", "password");
         assert!(
-            super::handle(State(state), Json(ApiResource::new(payload)))
-                .await
-                .is_ok()
+            super::handle(
+                State(state),
+                Default::default(),
+                Json(ApiResource::new(payload))
+            )
+            .await
+            .is_ok()
         );
     }
 
@@ -83,9 +99,13 @@ mod tests {
     async fn should_fail_validation_invalid_username() {
         let state = MockServerState::default();
         let payload = AuthenticationRequestDocument::new("   ", "password");
-        let err = super::handle(State(state), Json(ApiResource::new(payload)))
-            .await
-            .unwrap_err();
+        let err = super::handle(
+            State(state),
+            Default::default(),
+            Json(ApiResource::new(payload)),
+        )
+        .await
+        .unwrap_err();
         assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
         assert_eq!(err.message, "invalid credentials");
         let detail = err.detail.unwrap();
@@ -97,9 +117,13 @@ mod tests {
     async fn should_fail_validation_empty_password() {
         let state = MockServerState::default();
         let payload = AuthenticationRequestDocument::new("user@example.com", "          ");
-        let err = super::handle(State(state), Json(ApiResource::new(payload)))
-            .await
-            .unwrap_err();
+        let err = super::handle(
+            State(state),
+            Default::default(),
+            Json(ApiResource::new(payload)),
+        )
+        .await
+        .unwrap_err();
         assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
         assert_eq!(err.message, "invalid credentials");
         let detail = err.detail.unwrap();
@@ -111,9 +135,13 @@ mod tests {
     async fn should_fail_validation_invalid_password() {
         let state = MockServerState::default();
         let payload = AuthenticationRequestDocument::new("user@example.com", "foo");
-        let err = super::handle(State(state), Json(ApiResource::new(payload)))
-            .await
-            .unwrap_err();
+        let err = super::handle(
+            State(state),
+            Default::default(),
+            Json(ApiResource::new(payload)),
+        )
+        .await
+        .unwrap_err();
         assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
         assert_eq!(err.message, "invalid credentials");
         let detail = err.detail.unwrap();
@@ -136,9 +164,13 @@ mod tests {
             .authentication(auth_service)
             .build();
         let payload = AuthenticationRequestDocument::new("user@example.com", "password");
-        let err = super::handle(State(state), Json(ApiResource::new(payload)))
-            .await
-            .unwrap_err();
+        let err = super::handle(
+            State(state),
+            Default::default(),
+            Json(ApiResource::new(payload)),
+        )
+        .await
+        .unwrap_err();
         assert_eq!(err.status_code, StatusCode::CONFLICT);
         assert_eq!(err.message, "user conflict");
         let detail = err.detail.unwrap();
@@ -165,6 +197,7 @@ mod integration {
             Box::pin(async move {
                 Ok(LoginSuccess {
                     token: String::from("token"),
+                    refresh_token: String::from("refresh-token"),
                 })
             })
         });
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use entertainarr_domain::auth::prelude::AuthenticationService;
+use entertainarr_domain::podcast::prelude::{
+    ListPodcastEpisodeFilter, ListPodcastEpisodeParams, PodcastEpisodeField, PodcastEpisodeService,
+    PodcastService,
+};
+use entertainarr_domain::prelude::{Page, Sort, SortOrder};
+use serde_qs::axum::QsQuery;
+
+use crate::entity::ApiError;
+use crate::server::extractor::origin::Origin;
+
+/// Number of episodes listed in the feed, starting from the latest
+const LIMIT: u32 = 100;
+
+#[derive(serde::Deserialize)]
+pub struct QueryParams {
+    token: String,
+}
+
+/// Personal feed of the unplayed episodes of the subscribed podcasts, authenticated by the
+/// feed token so that it can be used by third-party players
+pub async fn handle<S>(
+    State(state): State<S>,
+    Path(user_id): Path<u64>,
+    Origin(origin): Origin,
+    QsQuery(params): QsQuery<QueryParams>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let valid = state
+        .authentication_service()
+        .verify_feed_token(user_id, &params.token)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to verify feed token");
+            ApiError::internal()
+        })?;
+    if !valid {
+        return Err(ApiError::not_found("feed not found"));
+    }
+
+    let episodes = state
+        .podcast_episode_service()
+        .list(ListPodcastEpisodeParams {
+            user_id,
+            filter: ListPodcastEpisodeFilter {
+                subscribed: Some(true),
+                watched: Some(false),
+                ..Default::default()
+            },
+            sort: Sort {
+                field: PodcastEpisodeField::PublishedAt,
+                order: SortOrder::Desc,
+            },
+            page: Page {
+                limit: LIMIT,
+                offset: 0,
+            },
+            cursor: None,
+        })
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to list podcast episodes");
+            ApiError::internal()
+        })?
+        .items;
+
+    let podcast_ids = episodes.iter().map(|e| e.podcast_id).collect::<Vec<_>>();
+    let podcasts = state
+        .podcast_service()
+        .list_by_ids(&podcast_ids)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to list podcasts");
+            ApiError::internal()
+        })?
+        .into_iter()
+        .map(|podcast| (podcast.id, podcast))
+        .collect::<HashMap<_, _>>();
+
+    Ok((
+        [(header::CONTENT_TYPE, super::rss::CONTENT_TYPE)],
+        super::rss::render(&format!("{origin}/"), &episodes, &podcasts),
+    ))
+}
+
+#[cfg(test)]
+mod integration {
+    use tower::ServiceExt;
+
+    use crate::server::prelude::tests::MockServerState;
+    use entertainarr_domain::auth::prelude::MockAuthenticationService;
+    use entertainarr_domain::podcast::prelude::{MockPodcastEpisodeService, MockPodcastService};
+
+    #[tokio::test]
+    async fn should_render_unplayed_episodes() {
+        let router = crate::server::handler::create();
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service
+            .expect_verify_feed_token()
+            .returning(|user_id, token| {
+                assert_eq!(user_id, 1);
+                assert_eq!(token, "secret");
+                Box::pin(async move { Ok(true) })
+            });
+        let mut podcast_episode_service = MockPodcastEpisodeService::new();
+        podcast_episode_service.expect_list().returning(|params| {
+            assert_eq!(params.user_id, 1);
+            assert_eq!(params.filter.subscribed, Some(true));
+            assert_eq!(params.filter.watched, Some(false));
+            Box::pin(async move { Ok(Default::default()) })
+        });
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_list_by_ids()
+            .returning(|_| Box::pin(async move { Ok(Vec::new()) }));
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .podcast(podcast_service)
+            .podcast_episode(podcast_episode_service)
+            .build();
+        let res = router
+            .with_state(state)
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/users/1/feed.xml?token=secret")
+                    .method(axum::http::Method::GET)
+                    .header("Host", "example.com")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::OK);
+        assert_eq!(
+            res.headers().get(axum::http::header::CONTENT_TYPE).unwrap(),
+            super::super::rss::CONTENT_TYPE
+        );
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("<link>http://example.com/</link>"));
+    }
+
+    #[tokio::test]
+    async fn should_fail_with_invalid_token() {
+        let router = crate::server::handler::create();
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service
+            .expect_verify_feed_token()
+            .returning(|_, _| Box::pin(async move { Ok(false) }));
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let res = router
+            .with_state(state)
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/users/1/feed.xml?token=wrong")
+                    .method(axum::http::Method::GET)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::NOT_FOUND);
+    }
+}
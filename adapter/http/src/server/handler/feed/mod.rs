@@ -0,0 +1,32 @@
+use axum::routing::{get, post};
+
+use crate::entity::auth::{FeedTokenAttributes, FeedTokenDocument};
+
+pub mod detail;
+mod rss;
+pub mod token;
+
+pub fn create<S>() -> axum::Router<S>
+where
+    S: crate::server::prelude::ServerState + Clone,
+{
+    axum::Router::new()
+        .route("/users/{user_id}/feed.xml", get(detail::handle::<S>))
+        .route(
+            "/users/me/feed-token",
+            post(token::handle_rotate::<S>).delete(token::handle_revoke::<S>),
+        )
+}
+
+impl FeedTokenDocument {
+    fn new(user_id: u64, token: String) -> Self {
+        Self {
+            id: user_id,
+            kind: Default::default(),
+            attributes: FeedTokenAttributes {
+                feed_url: format!("/api/users/{user_id}/feed.xml?token={token}"),
+                token,
+            },
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! Writing of the personal feed as RSS 2.0 with the iTunes extension
+
+use std::collections::HashMap;
+
+use entertainarr_domain::podcast::entity::{Podcast, PodcastEpisode};
+
+use crate::server::handler::prelude::escape_xml;
+
+pub const CONTENT_TYPE: &str = "application/rss+xml; charset=utf-8";
+
+const TITLE: &str = "Entertainarr unplayed episodes";
+const DESCRIPTION: &str = "Unplayed episodes of the subscribed podcasts";
+
+fn push_element(output: &mut String, indent: &str, name: &str, value: &str) {
+    output.push_str(indent);
+    output.push('<');
+    output.push_str(name);
+    output.push('>');
+    output.push_str(&escape_xml(value));
+    output.push_str("</");
+    output.push_str(name);
+    output.push_str(">\n");
+}
+
+fn push_item(output: &mut String, episode: &PodcastEpisode, podcast: Option<&Podcast>) {
+    const INDENT: &str = "      ";
+
+    output.push_str("    <item>\n");
+    let title = match podcast {
+        Some(podcast) => format!("{} - {}", podcast.title, episode.title),
+        None => episode.title.clone(),
+    };
+    push_element(output, INDENT, "title", &title);
+    push_element(output, INDENT, "itunes:title", &episode.title);
+    if let Some(podcast) = podcast {
+        let author = podcast.author.as_deref().unwrap_or(podcast.title.as_str());
+        push_element(output, INDENT, "itunes:author", author);
+    }
+    // the guid of the feed is only unique in the scope of its podcast
+    output.push_str(INDENT);
+    output.push_str("<guid isPermaLink=\"false\">entertainarr-podcast-episode-");
+    output.push_str(&episode.id.to_string());
+    output.push_str("</guid>\n");
+    if let Some(published_at) = episode.published_at {
+        push_element(output, INDENT, "pubDate", &published_at.to_rfc2822());
+    }
+    if let Some(ref description) = episode.description {
+        push_element(output, INDENT, "description", description);
+    }
+    if let Some(ref link) = episode.link {
+        push_element(output, INDENT, "link", link);
+    }
+    output.push_str(INDENT);
+    output.push_str("<enclosure url=\"");
+    output.push_str(&escape_xml(&episode.file_url));
+    output.push_str("\" length=\"");
+    output.push_str(&episode.file_size.unwrap_or(0).to_string());
+    output.push_str("\" type=\"");
+    output.push_str(&escape_xml(
+        episode.file_type.as_deref().unwrap_or("audio/mpeg"),
+    ));
+    output.push_str("\"/>\n");
+    if let Some(duration) = episode.duration {
+        push_element(
+            output,
+            INDENT,
+            "itunes:duration",
+            &duration.as_secs().to_string(),
+        );
+    }
+    let image_url = episode
+        .image_url
+        .as_deref()
+        .or_else(|| podcast.and_then(|podcast| podcast.image_url.as_deref()));
+    if let Some(image_url) = image_url {
+        output.push_str(INDENT);
+        output.push_str("<itunes:image href=\"");
+        output.push_str(&escape_xml(image_url));
+        output.push_str("\"/>\n");
+    }
+    if let Some(explicit) = podcast.and_then(|podcast| podcast.explicit) {
+        push_element(
+            output,
+            INDENT,
+            "itunes:explicit",
+            if explicit { "true" } else { "false" },
+        );
+    }
+    if let Some(season_number) = episode.season_number {
+        push_element(output, INDENT, "itunes:season", &season_number.to_string());
+    }
+    if let Some(episode_number) = episode.episode_number {
+        push_element(
+            output,
+            INDENT,
+            "itunes:episode",
+            &episode_number.to_string(),
+        );
+    }
+    if let Some(episode_type) = episode.episode_type {
+        push_element(output, INDENT, "itunes:episodeType", episode_type.as_str());
+    }
+    output.push_str("    </item>\n");
+}
+
+/// Renders the episodes, in the given order, with the podcast they belong to
+pub fn render(link: &str, episodes: &[PodcastEpisode], podcasts: &HashMap<u64, Podcast>) -> String {
+    let mut output = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n  <channel>\n",
+    );
+    push_element(&mut output, "    ", "title", TITLE);
+    push_element(&mut output, "    ", "link", link);
+    push_element(&mut output, "    ", "description", DESCRIPTION);
+    push_element(&mut output, "    ", "itunes:author", "Entertainarr");
+    push_element(&mut output, "    ", "itunes:explicit", "false");
+    // the feed is personal and shouldn't be listed by directories
+    push_element(&mut output, "    ", "itunes:block", "Yes");
+    for episode in episodes {
+        push_item(&mut output, episode, podcasts.get(&episode.podcast_id));
+    }
+    output.push_str("  </channel>\n</rss>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+    use entertainarr_domain::podcast::entity::{Podcast, PodcastEpisode};
+
+    fn podcast() -> Podcast {
+        Podcast {
+            id: 1,
+            feed_url: "https://example.com/feed.rss".into(),
+            title: "Tom & Jerry".into(),
+            description: None,
+            image_url: Some("https://example.com/cover.png".into()),
+            language: None,
+            website: None,
+            author: None,
+            categories: Vec::new(),
+            explicit: Some(false),
+            persons: Vec::new(),
+            funding: Vec::new(),
+            last_synced_at: None,
+            last_sync_error: None,
+            feed_cache: Default::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn episode() -> PodcastEpisode {
+        PodcastEpisode {
+            id: 12,
+            podcast_id: 1,
+            guid: Some("abc".into()),
+            published_at: Some(Utc.with_ymd_and_hms(2025, 11, 3, 8, 0, 0).unwrap()),
+            title: "Cats <3 mice".into(),
+            description: Some("<p>Hello</p>".into()),
+            link: None,
+            duration: Some(Duration::from_secs(3600)),
+            file_url: "https://example.com/episode.mp3?a=1&b=2".into(),
+            file_size: Some(1024),
+            file_type: Some("audio/mpeg".into()),
+            image_url: None,
+            season_number: None,
+            episode_number: Some(3),
+            episode_type: None,
+            chapters_url: None,
+            transcript: None,
+            persons: Vec::new(),
+            removed_at: None,
+            downloaded: false,
+            progress: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn should_render_valid_feed() {
+        let podcasts = HashMap::from([(1, podcast())]);
+        let output = super::render("http://localhost:3000", &[episode()], &podcasts);
+
+        let doc = roxmltree::Document::parse(&output).unwrap();
+        let channel = doc
+            .root_element()
+            .children()
+            .find(|node| node.has_tag_name("channel"))
+            .unwrap();
+        let item = channel
+            .children()
+            .find(|node| node.has_tag_name("item"))
+            .unwrap();
+        let text = |name: &str| {
+            item.children()
+                .find(|node| node.tag_name().name() == name)
+                .and_then(|node| node.text())
+        };
+        assert_eq!(text("title"), Some("Tom & Jerry - Cats <3 mice"));
+        assert_eq!(text("author"), Some("Tom & Jerry"));
+        assert_eq!(text("pubDate"), Some("Mon, 3 Nov 2025 08:00:00 +0000"));
+        assert_eq!(text("duration"), Some("3600"));
+        assert_eq!(text("episode"), Some("3"));
+        let enclosure = item
+            .children()
+            .find(|node| node.has_tag_name("enclosure"))
+            .unwrap();
+        assert_eq!(
+            enclosure.attribute("url"),
+            Some("https://example.com/episode.mp3?a=1&b=2")
+        );
+        assert_eq!(enclosure.attribute("length"), Some("1024"));
+        let image = item
+            .children()
+            .find(|node| node.tag_name().name() == "image")
+            .unwrap();
+        assert_eq!(
+            image.attribute("href"),
+            Some("https://example.com/cover.png")
+        );
+    }
+}
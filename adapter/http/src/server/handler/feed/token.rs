@@ -0,0 +1,96 @@
+use axum::Json;
+use axum::extract::State;
+use entertainarr_domain::auth::prelude::AuthenticationService;
+
+use crate::entity::auth::FeedTokenDocument;
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+
+/// Issues a new token for the personal feed, the previous feed url stops working
+pub async fn handle_rotate<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+) -> Result<Json<ApiResource<FeedTokenDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let token = state
+        .authentication_service()
+        .rotate_feed_token(user_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to rotate feed token");
+            ApiError::internal()
+        })?;
+    Ok(Json(ApiResource::new(FeedTokenDocument::new(
+        user_id, token,
+    ))))
+}
+
+pub async fn handle_revoke<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+) -> Result<axum::http::StatusCode, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    match state
+        .authentication_service()
+        .revoke_feed_token(user_id)
+        .await
+    {
+        Ok(true) => Ok(axum::http::StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::not_found("feed token not found")),
+        Err(err) => {
+            tracing::error!(error = ?err, "unable to revoke feed token");
+            Err(ApiError::internal())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::State;
+    use axum::http::StatusCode;
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+    use entertainarr_domain::auth::prelude::MockAuthenticationService;
+
+    #[tokio::test]
+    async fn should_rotate_token() {
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service
+            .expect_rotate_feed_token()
+            .return_once(|user_id| {
+                assert_eq!(user_id, 1);
+                Box::pin(async move { Ok(String::from("secret")) })
+            });
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let res = super::handle_rotate(State(state), CurrentUser(1))
+            .await
+            .unwrap();
+        assert_eq!(res.data.id, 1);
+        assert_eq!(res.data.attributes.token, "secret");
+        assert_eq!(
+            res.data.attributes.feed_url,
+            "/api/users/1/feed.xml?token=secret"
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_revoking_missing_token() {
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service
+            .expect_revoke_feed_token()
+            .return_once(|_| Box::pin(async move { Ok(false) }));
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let err = super::handle_revoke(State(state), CurrentUser(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+}
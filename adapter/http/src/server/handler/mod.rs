@@ -4,9 +4,13 @@ use axum::routing::{get, head};
 
 mod auth;
 pub mod client;
+mod feed;
 mod podcast;
 mod podcast_episode;
+mod podcast_queue;
 pub(crate) mod prelude;
+mod search;
+mod session;
 mod status;
 
 pub fn create<S>() -> axum::Router<S>
@@ -15,8 +19,12 @@ where
 {
     let api = axum::Router::new()
         .merge(auth::create::<S>())
+        .merge(feed::create::<S>())
         .merge(podcast::create::<S>())
-        .merge(podcast_episode::create::<S>());
+        .merge(podcast_episode::create::<S>())
+        .merge(podcast_queue::create::<S>())
+        .merge(session::create::<S>())
+        .route("/search", get(search::handle::<S>));
 
     axum::Router::new()
         .route("/", get(client::handle_index::<S>))
@@ -0,0 +1,95 @@
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::PodcastQueueService;
+
+use crate::entity::ApiError;
+use crate::server::extractor::user::CurrentUser;
+
+async fn handle<S>(
+    state: S,
+    user_id: u64,
+    podcast_id: u64,
+    enabled: bool,
+) -> Result<axum::http::StatusCode, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    match state
+        .podcast_queue_service()
+        .set_auto_queue(user_id, podcast_id, enabled)
+        .await
+    {
+        Ok(true) => Ok(axum::http::StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::not_found("podcast subscription not found")),
+        Err(err) => {
+            tracing::error!(error = ?err, "unable to update podcast auto queue");
+            Err(ApiError::internal())
+        }
+    }
+}
+
+/// Appends the episodes published from now on to the queue of the user
+pub async fn enable<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(podcast_id): Path<u64>,
+) -> Result<axum::http::StatusCode, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    handle(state, user_id, podcast_id, true).await
+}
+
+pub async fn disable<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(podcast_id): Path<u64>,
+) -> Result<axum::http::StatusCode, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    handle(state, user_id, podcast_id, false).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+    use entertainarr_domain::podcast::prelude::MockPodcastQueueService;
+
+    #[tokio::test]
+    async fn should_enable_auto_queue() {
+        let mut podcast_queue_service = MockPodcastQueueService::new();
+        podcast_queue_service.expect_set_auto_queue().return_once(
+            |user_id, podcast_id, enabled| {
+                assert_eq!(user_id, 1);
+                assert_eq!(podcast_id, 2);
+                assert!(enabled);
+                Box::pin(async move { Ok(true) })
+            },
+        );
+        let state = MockServerState::builder()
+            .podcast_queue(podcast_queue_service)
+            .build();
+        let res = super::enable(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap();
+        assert_eq!(res, StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_not_subscribed() {
+        let mut podcast_queue_service = MockPodcastQueueService::new();
+        podcast_queue_service
+            .expect_set_auto_queue()
+            .return_once(|_, _, _| Box::pin(async move { Ok(false) }));
+        let state = MockServerState::builder()
+            .podcast_queue(podcast_queue_service)
+            .build();
+        let err = super::disable(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+}
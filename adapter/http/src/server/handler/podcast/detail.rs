@@ -0,0 +1,118 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::PodcastService;
+
+use crate::entity::podcast::PodcastDocument;
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(podcast_id): Path<u64>,
+) -> Result<Json<ApiResource<PodcastDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let podcast = state
+        .podcast_service()
+        .find_by_id(podcast_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to find podcast");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast not found"))?;
+    let subscription = state
+        .podcast_service()
+        .subscription(user_id, podcast_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to find podcast subscription");
+            ApiError::internal()
+        })?;
+
+    let mut document = PodcastDocument::from(podcast);
+    document.attributes.subscription = subscription.map(Into::into);
+    Ok(Json(ApiResource::new(document)))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+    };
+    use chrono::Utc;
+    use entertainarr_domain::podcast::{
+        entity::{Podcast, PodcastSubscription},
+        prelude::MockPodcastService,
+    };
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    #[tokio::test]
+    async fn should_return_podcast_with_subscription() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_find_by_id()
+            .return_once(|podcast_id| {
+                assert_eq!(podcast_id, 2);
+                Box::pin(async move {
+                    Ok(Some(Podcast {
+                        id: 2,
+                        feed_url: "https://example.com/feed.rss".into(),
+                        title: "Example".into(),
+                        description: None,
+                        image_url: None,
+                        language: None,
+                        website: None,
+                        author: None,
+                        categories: Vec::new(),
+                        explicit: None,
+                        persons: Vec::new(),
+                        funding: Vec::new(),
+                        last_synced_at: None,
+                        last_sync_error: None,
+                        feed_cache: Default::default(),
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    }))
+                })
+            });
+        podcast_service
+            .expect_subscription()
+            .return_once(|user_id, podcast_id| {
+                assert_eq!(user_id, 1);
+                assert_eq!(podcast_id, 2);
+                Box::pin(async move {
+                    Ok(Some(PodcastSubscription {
+                        user_id,
+                        podcast_id,
+                        auto_queue: false,
+                        settings: Default::default(),
+                        created_at: Utc::now(),
+                    }))
+                })
+            });
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let res = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap();
+        assert_eq!(res.data.id, 2);
+        assert!(res.data.attributes.subscription.is_some());
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_not_found() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_find_by_id()
+            .return_once(|_| Box::pin(async move { Ok(None) }));
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let err = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,133 @@
+use axum::Json;
+use axum::extract::State;
+use entertainarr_domain::podcast::prelude::PodcastService;
+use serde_qs::axum::QsQuery;
+
+use crate::entity::podcast::{PodcastDirectoryAttributes, PodcastDirectoryDocument};
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+
+const DEFAULT_LIMIT: u32 = 20;
+const MAX_LIMIT: u32 = 100;
+
+const fn default_limit() -> u32 {
+    DEFAULT_LIMIT
+}
+
+#[derive(serde::Deserialize)]
+pub struct QueryParams {
+    q: String,
+    #[serde(default = "default_limit")]
+    limit: u32,
+}
+
+/// Searches the configured podcast directory, results can be subscribed using their feed url
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(_user_id): CurrentUser,
+    QsQuery(params): QsQuery<QueryParams>,
+) -> Result<Json<ApiResource<Vec<PodcastDirectoryDocument>>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let entries = state
+        .podcast_service()
+        .discover(&params.q, params.limit.clamp(1, MAX_LIMIT))
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to search podcast directory");
+            ApiError::internal()
+        })?;
+
+    Ok(Json(ApiResource::new(
+        entries
+            .into_iter()
+            .map(PodcastDirectoryDocument::from)
+            .collect(),
+    )))
+}
+
+impl From<entertainarr_domain::podcast::entity::PodcastDirectoryEntry>
+    for PodcastDirectoryDocument
+{
+    fn from(value: entertainarr_domain::podcast::entity::PodcastDirectoryEntry) -> Self {
+        Self {
+            id: value.feed_url.clone(),
+            kind: Default::default(),
+            attributes: PodcastDirectoryAttributes {
+                title: value.title,
+                author: value.author,
+                description: value.description,
+                feed_url: value.feed_url,
+                image_url: value.image_url,
+                website: value.website,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{extract::State, http::StatusCode};
+    use entertainarr_domain::podcast::{
+        entity::PodcastDirectoryEntry, prelude::MockPodcastService,
+    };
+    use serde_qs::axum::QsQuery;
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    #[tokio::test]
+    async fn should_return_directory_entries() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_discover()
+            .return_once(|query, limit| {
+                assert_eq!(query, "rust");
+                assert_eq!(limit, 100);
+                Box::pin(async move {
+                    Ok(vec![PodcastDirectoryEntry {
+                        title: "Rustacean Station".into(),
+                        author: Some("Rustacean Station".into()),
+                        description: None,
+                        feed_url: "https://rustacean-station.org/podcast.rss".into(),
+                        image_url: None,
+                        website: None,
+                    }])
+                })
+            });
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let res = super::handle(
+            State(state),
+            CurrentUser(1),
+            QsQuery(super::QueryParams {
+                q: "rust".into(),
+                limit: 500,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.data.len(), 1);
+        assert_eq!(res.data[0].id, "https://rustacean-station.org/podcast.rss");
+        assert_eq!(res.data[0].attributes.title, "Rustacean Station");
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_directory_fails() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_discover()
+            .return_once(|_, _| Box::pin(async move { Err(anyhow::anyhow!("unreachable")) }));
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let err = super::handle(
+            State(state),
+            CurrentUser(1),
+            QsQuery(super::QueryParams {
+                q: "rust".into(),
+                limit: 10,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,235 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::{PodcastDownloadService, PodcastService};
+
+use crate::entity::podcast::{
+    PodcastDownloadPolicyAttributes, PodcastDownloadPolicyDocument,
+    errors::CODE_AUTO_DOWNLOAD_TOO_LARGE,
+};
+use crate::entity::{ApiError, ApiErrorDetail, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+
+async fn ensure_podcast_exists<S>(state: &S, podcast_id: u64) -> Result<(), ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    state
+        .podcast_service()
+        .find_by_id(podcast_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to find podcast");
+            ApiError::internal()
+        })?
+        .map(|_| ())
+        .ok_or_else(|| ApiError::not_found("podcast not found"))
+}
+
+fn document(podcast_id: u64, latest: u32) -> PodcastDownloadPolicyDocument {
+    PodcastDownloadPolicyDocument {
+        id: podcast_id,
+        kind: Default::default(),
+        attributes: PodcastDownloadPolicyAttributes { latest },
+    }
+}
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(_): CurrentUser,
+    Path(podcast_id): Path<u64>,
+) -> Result<Json<ApiResource<PodcastDownloadPolicyDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    ensure_podcast_exists(&state, podcast_id).await?;
+    let policy = state
+        .podcast_download_service()
+        .download_policy(podcast_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to find podcast download policy");
+            ApiError::internal()
+        })?;
+    let latest = policy.map(|policy| policy.latest).unwrap_or_default();
+    Ok(Json(ApiResource::new(document(podcast_id, latest))))
+}
+
+/// Only the subscribers can change what gets downloaded for a podcast
+pub async fn handle_update<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(podcast_id): Path<u64>,
+    Json(payload): Json<ApiResource<PodcastDownloadPolicyDocument>>,
+) -> Result<Json<ApiResource<PodcastDownloadPolicyDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let latest = payload.data.attributes.latest;
+    if latest > super::MAX_AUTO_DOWNLOAD {
+        return Err(ApiError::bad_request("invalid download policy")
+            .with_detail(ApiErrorDetail::new("latest", CODE_AUTO_DOWNLOAD_TOO_LARGE)));
+    }
+    state
+        .podcast_service()
+        .subscription(user_id, podcast_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to find podcast subscription");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast subscription not found"))?;
+    let policy = state
+        .podcast_download_service()
+        .set_download_policy(podcast_id, latest)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to update podcast download policy");
+            ApiError::internal()
+        })?;
+    let latest = policy.map(|policy| policy.latest).unwrap_or_default();
+    Ok(Json(ApiResource::new(document(podcast_id, latest))))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Json;
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use chrono::Utc;
+    use entertainarr_domain::podcast::entity::{
+        Podcast, PodcastDownloadPolicy, PodcastSubscription,
+    };
+    use entertainarr_domain::podcast::prelude::{MockPodcastDownloadService, MockPodcastService};
+
+    use crate::entity::ApiResource;
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    fn podcast_service() -> MockPodcastService {
+        let mut service = MockPodcastService::new();
+        service.expect_find_by_id().return_once(|podcast_id| {
+            Box::pin(async move {
+                Ok(Some(Podcast {
+                    id: podcast_id,
+                    feed_url: "http://example.org/feed.rss".into(),
+                    title: "Example".into(),
+                    description: None,
+                    image_url: None,
+                    language: None,
+                    website: None,
+                    author: None,
+                    categories: Vec::new(),
+                    explicit: None,
+                    persons: Vec::new(),
+                    funding: Vec::new(),
+                    last_synced_at: None,
+                    last_sync_error: None,
+                    feed_cache: Default::default(),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                }))
+            })
+        });
+        service
+    }
+
+    #[tokio::test]
+    async fn should_return_disabled_policy() {
+        let mut download_service = MockPodcastDownloadService::new();
+        download_service
+            .expect_download_policy()
+            .return_once(|_| Box::pin(async { Ok(None) }));
+        let state = MockServerState::builder()
+            .podcast(podcast_service())
+            .podcast_download(download_service)
+            .build();
+        let res = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap();
+        assert_eq!(res.data.id, 2);
+        assert_eq!(res.data.attributes.latest, 0);
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_podcast_not_found() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_find_by_id()
+            .return_once(|_| Box::pin(async { Ok(None) }));
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let err = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn should_update_policy() {
+        let mut download_service = MockPodcastDownloadService::new();
+        download_service
+            .expect_set_download_policy()
+            .return_once(|podcast_id, latest| {
+                assert_eq!(podcast_id, 2);
+                assert_eq!(latest, 3);
+                Box::pin(async move { Ok(Some(PodcastDownloadPolicy { podcast_id, latest })) })
+            });
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_subscription()
+            .return_once(|user_id, podcast_id| {
+                Box::pin(async move {
+                    Ok(Some(PodcastSubscription {
+                        user_id,
+                        podcast_id,
+                        auto_queue: false,
+                        settings: Default::default(),
+                        created_at: Utc::now(),
+                    }))
+                })
+            });
+        let state = MockServerState::builder()
+            .podcast(podcast_service)
+            .podcast_download(download_service)
+            .build();
+        let res = super::handle_update(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            Json(ApiResource::new(super::document(2, 3))),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.data.attributes.latest, 3);
+    }
+
+    #[tokio::test]
+    async fn should_not_update_policy_without_subscription() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_subscription()
+            .return_once(|_, _| Box::pin(async { Ok(None) }));
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let err = super::handle_update(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            Json(ApiResource::new(super::document(2, 3))),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn should_refuse_too_many_downloads() {
+        let state = MockServerState::builder().build();
+        let err = super::handle_update(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            Json(ApiResource::new(super::document(2, 51))),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,132 @@
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use entertainarr_domain::podcast::prelude::PodcastService;
+
+use crate::entity::ApiError;
+use crate::server::extractor::user::CurrentUser;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+) -> Result<impl IntoResponse, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let list = state
+        .podcast_service()
+        .subscriptions(user_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to list user podcasts");
+            ApiError::internal()
+        })?;
+    let podcasts = list
+        .into_iter()
+        .map(|item| item.podcast)
+        .collect::<Vec<_>>();
+    Ok((
+        [
+            (header::CONTENT_TYPE, super::opml::CONTENT_TYPE),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"subscriptions.opml\"",
+            ),
+        ],
+        super::opml::render(&podcasts),
+    ))
+}
+
+#[cfg(test)]
+mod integration {
+    use chrono::Utc;
+    use tower::ServiceExt;
+
+    use crate::server::prelude::tests::MockServerState;
+    use entertainarr_domain::{
+        auth::{entity::Principal, prelude::MockAuthenticationService},
+        podcast::{
+            entity::{
+                Podcast, PodcastSubscription, PodcastSubscriptionSettings, SubscribedPodcast,
+            },
+            prelude::MockPodcastService,
+        },
+    };
+
+    #[tokio::test]
+    async fn should_export_subscriptions() {
+        let router = crate::server::handler::create();
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service.expect_verify().returning(|_| {
+            Box::pin(async move {
+                Ok(Principal {
+                    user_id: 1,
+                    session_id: 1,
+                })
+            })
+        });
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service.expect_subscriptions().returning(|user_id| {
+            assert_eq!(user_id, 1);
+            Box::pin(async move {
+                Ok(vec![SubscribedPodcast {
+                    podcast: Podcast {
+                        id: 1,
+                        feed_url: "https://example.com/feed.rss".into(),
+                        title: "Example".into(),
+                        description: None,
+                        image_url: None,
+                        language: None,
+                        website: None,
+                        author: None,
+                        categories: Vec::new(),
+                        explicit: None,
+                        persons: Vec::new(),
+                        funding: Vec::new(),
+                        last_synced_at: None,
+                        last_sync_error: None,
+                        feed_cache: Default::default(),
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    },
+                    subscription: PodcastSubscription {
+                        user_id: 1,
+                        podcast_id: 1,
+                        auto_queue: false,
+                        settings: PodcastSubscriptionSettings {
+                            playback_speed: 1.5,
+                            ..Default::default()
+                        },
+                        created_at: Utc::now(),
+                    },
+                }])
+            })
+        });
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .podcast(podcast_service)
+            .build();
+        let res = router
+            .with_state(state)
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/users/me/podcasts/export")
+                    .method(axum::http::Method::GET)
+                    .header("Authorization", "Bearer token")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::OK);
+        assert_eq!(
+            res.headers().get(axum::http::header::CONTENT_TYPE).unwrap(),
+            "text/x-opml"
+        );
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains(r#"xmlUrl="https://example.com/feed.rss""#));
+    }
+}
@@ -0,0 +1,172 @@
+use axum::Json;
+use axum::extract::State;
+use futures::StreamExt;
+
+use crate::entity::podcast::{
+    PodcastEntity, PodcastImportAttributes, PodcastImportDocument, PodcastImportRelationship,
+    errors::CODE_IMPORT_TOO_LARGE,
+};
+use crate::entity::{ApiError, ApiErrorDetail, ApiResource, Relation};
+use crate::server::extractor::user::CurrentUser;
+use entertainarr_domain::podcast::prelude::PodcastService;
+
+/// Number of feeds loaded at the same time
+const CONCURRENCY: usize = 4;
+/// Each feed is loaded while the request is pending, so the document has to stay reasonable
+const MAX_OUTLINES: usize = 500;
+
+async fn import<S>(state: &S, user_id: u64, outline: super::opml::Outline) -> PodcastImportDocument
+where
+    S: crate::server::prelude::ServerState,
+{
+    let (relationship, error) = match state
+        .podcast_service()
+        .subscribe(user_id, &outline.feed_url)
+        .await
+    {
+        Ok(podcast) => (
+            Some(PodcastImportRelationship {
+                podcast: Relation {
+                    data: PodcastEntity {
+                        id: podcast.id,
+                        kind: Default::default(),
+                    },
+                },
+            }),
+            None,
+        ),
+        Err(err) => {
+            tracing::warn!(feed_url = outline.feed_url, error = ?err, "unable to import subscription");
+            (None, Some(String::from("unable to load podcast feed")))
+        }
+    };
+    PodcastImportDocument {
+        kind: Default::default(),
+        attributes: PodcastImportAttributes {
+            feed_url: outline.feed_url,
+            title: outline.title,
+            error,
+        },
+        relationship,
+    }
+}
+
+/// Subscribes to every feed of the OPML document, a failing feed doesn't prevent the others
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    body: String,
+) -> Result<Json<ApiResource<Vec<PodcastImportDocument>>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let outlines = super::opml::parse(&body).map_err(|err| {
+        tracing::debug!(error = ?err, "unable to parse opml document");
+        ApiError::bad_request("invalid opml document")
+    })?;
+    if outlines.len() > MAX_OUTLINES {
+        return Err(ApiError::bad_request("too many podcasts to import")
+            .with_detail(ApiErrorDetail::new("outline", CODE_IMPORT_TOO_LARGE)));
+    }
+
+    let data = futures::stream::iter(outlines)
+        .map(|outline| import(&state, user_id, outline))
+        .buffered(CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+    Ok(Json(ApiResource::new(data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{extract::State, http::StatusCode};
+    use chrono::Utc;
+    use entertainarr_domain::podcast::{entity::Podcast, prelude::MockPodcastService};
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    const OPML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <body>
+    <outline type="rss" text="Working" xmlUrl="https://example.com/working.rss"/>
+    <outline type="rss" text="Broken" xmlUrl="https://example.com/broken.rss"/>
+  </body>
+</opml>"#;
+
+    #[tokio::test]
+    async fn should_report_each_feed() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_subscribe()
+            .times(2)
+            .returning(|user_id, feed_url| {
+                assert_eq!(user_id, 1);
+                let feed_url = feed_url.to_string();
+                Box::pin(async move {
+                    if feed_url.ends_with("broken.rss") {
+                        return Err(anyhow::anyhow!("connection refused to 10.0.0.1:443"));
+                    }
+                    Ok(Podcast {
+                        id: 42,
+                        feed_url,
+                        title: "Working".into(),
+                        description: None,
+                        image_url: None,
+                        language: None,
+                        website: None,
+                        author: None,
+                        categories: Vec::new(),
+                        explicit: None,
+                        persons: Vec::new(),
+                        funding: Vec::new(),
+                        last_synced_at: None,
+                        last_sync_error: None,
+                        feed_cache: Default::default(),
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    })
+                })
+            });
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let res = super::handle(State(state), CurrentUser(1), OPML.to_string())
+            .await
+            .unwrap();
+        assert_eq!(res.data.len(), 2);
+        assert_eq!(
+            res.data[0].relationship.as_ref().unwrap().podcast.data.id,
+            42
+        );
+        assert!(res.data[0].attributes.error.is_none());
+        assert!(res.data[1].relationship.is_none());
+        assert_eq!(
+            res.data[1].attributes.error.as_deref(),
+            Some("unable to load podcast feed")
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_document() {
+        let state = MockServerState::default();
+        let err = super::handle(State(state), CurrentUser(1), "<html></html>".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn should_reject_too_many_feeds() {
+        let outlines = (0..=super::MAX_OUTLINES)
+            .map(|index| {
+                format!(r#"<outline type="rss" text="{index}" xmlUrl="https://example.com/{index}.rss"/>"#)
+            })
+            .collect::<String>();
+        let document = format!(r#"<opml version="2.0"><body>{outlines}</body></opml>"#);
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service.expect_subscribe().never();
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let err = super::handle(State(state), CurrentUser(1), document)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
+    }
+}
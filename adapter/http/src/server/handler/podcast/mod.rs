@@ -1,22 +1,48 @@
-use crate::entity::podcast::{PodcastAttributes, PodcastDocument};
-use axum::routing::{delete, post};
+use crate::entity::podcast::{
+    PodcastAttributes, PodcastDocument, PodcastFundingAttributes, PodcastPersonAttributes,
+    PodcastSubscriptionAttributes,
+};
+use axum::routing::{delete, get, post, put};
 
+pub mod auto_queue;
+pub mod detail;
+pub mod discover;
+pub mod download_policy;
+pub mod export;
+pub mod import;
+mod opml;
+pub mod settings;
 pub mod subscribe;
 pub mod unsubscribe;
 pub mod user_list;
 
+/// Any subscriber can ask for downloads, which all end up in the media directory of the server
+const MAX_AUTO_DOWNLOAD: u32 = 50;
+
 pub fn create<S>() -> axum::Router<S>
 where
     S: crate::server::prelude::ServerState + Clone,
 {
     axum::Router::new()
+        .route("/podcast-directory", get(discover::handle::<S>))
+        .route("/podcasts/{podcast_id}", get(detail::handle::<S>))
+        .route(
+            "/podcasts/{podcast_id}/download-policy",
+            get(download_policy::handle::<S>).put(download_policy::handle_update::<S>),
+        )
         .route(
             "/users/me/podcasts",
             post(subscribe::handle::<S>).get(user_list::handle::<S>),
         )
+        .route("/users/me/podcasts/export", get(export::handle::<S>))
+        .route("/users/me/podcasts/import", post(import::handle::<S>))
         .route(
             "/users/me/podcasts/{podcast_id}",
-            delete(unsubscribe::handle::<S>),
+            delete(unsubscribe::handle::<S>).patch(settings::handle::<S>),
+        )
+        .route(
+            "/users/me/podcasts/{podcast_id}/auto-queue",
+            put(auto_queue::enable::<S>).delete(auto_queue::disable::<S>),
         )
 }
 
@@ -32,9 +58,71 @@ impl From<entertainarr_domain::podcast::entity::Podcast> for PodcastDocument {
                 language: value.language,
                 feed_url: value.feed_url,
                 website: value.website,
+                author: value.author,
+                categories: value.categories,
+                explicit: value.explicit,
+                persons: value
+                    .persons
+                    .into_iter()
+                    .map(PodcastPersonAttributes::from)
+                    .collect(),
+                funding: value
+                    .funding
+                    .into_iter()
+                    .map(PodcastFundingAttributes::from)
+                    .collect(),
+                last_synced_at: value.last_synced_at,
+                last_sync_error: value.last_sync_error,
+                subscription: None,
                 created_at: value.created_at,
                 updated_at: value.updated_at,
             },
         }
     }
 }
+
+impl From<entertainarr_domain::podcast::entity::PodcastPerson> for PodcastPersonAttributes {
+    fn from(value: entertainarr_domain::podcast::entity::PodcastPerson) -> Self {
+        Self {
+            name: value.name,
+            role: value.role,
+            group: value.group,
+            href: value.href,
+            image_url: value.image_url,
+        }
+    }
+}
+
+impl From<entertainarr_domain::podcast::entity::PodcastFunding> for PodcastFundingAttributes {
+    fn from(value: entertainarr_domain::podcast::entity::PodcastFunding) -> Self {
+        Self {
+            url: value.url,
+            title: value.title,
+        }
+    }
+}
+
+impl From<entertainarr_domain::podcast::entity::PodcastSubscription>
+    for PodcastSubscriptionAttributes
+{
+    fn from(value: entertainarr_domain::podcast::entity::PodcastSubscription) -> Self {
+        Self {
+            auto_queue: value.auto_queue,
+            playback_speed: value.settings.playback_speed,
+            skip_start: value.settings.skip_start,
+            skip_end: value.settings.skip_end,
+            auto_download: value.settings.auto_download,
+            include_in_home: value.settings.include_in_home,
+            notify: value.settings.notify,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl From<entertainarr_domain::podcast::entity::SubscribedPodcast> for PodcastDocument {
+    fn from(value: entertainarr_domain::podcast::entity::SubscribedPodcast) -> Self {
+        let mut document = PodcastDocument::from(value.podcast);
+        document.attributes.subscription = Some(value.subscription.into());
+        document
+    }
+}
@@ -0,0 +1,147 @@
+//! Reading and writing of OPML subscription lists
+
+use std::collections::HashSet;
+
+use entertainarr_domain::podcast::entity::Podcast;
+
+use crate::server::handler::prelude::escape_xml;
+
+pub const CONTENT_TYPE: &str = "text/x-opml";
+
+#[derive(Debug, PartialEq)]
+pub struct Outline {
+    pub feed_url: String,
+    pub title: Option<String>,
+}
+
+/// Lists the feeds of the document, including the ones nested in categories
+pub fn parse(input: &str) -> anyhow::Result<Vec<Outline>> {
+    let doc = roxmltree::Document::parse(input)?;
+    let root = doc.root_element();
+    if !root.has_tag_name("opml") {
+        anyhow::bail!("expected opml root element");
+    }
+    let body = root
+        .children()
+        .find(|node| node.has_tag_name("body"))
+        .ok_or_else(|| anyhow::anyhow!("missing body element"))?;
+
+    let mut seen = HashSet::new();
+    Ok(body
+        .descendants()
+        .filter(|node| node.has_tag_name("outline"))
+        .filter_map(|node| {
+            let feed_url = node.attribute("xmlUrl")?.trim();
+            if feed_url.is_empty() || !seen.insert(feed_url) {
+                return None;
+            }
+            let title = node
+                .attribute("title")
+                .or_else(|| node.attribute("text"))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from);
+            Some(Outline {
+                feed_url: feed_url.to_string(),
+                title,
+            })
+        })
+        .collect())
+}
+
+pub fn render(podcasts: &[Podcast]) -> String {
+    let mut output = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n  <head>\n    <title>Entertainarr subscriptions</title>\n  </head>\n  <body>\n",
+    );
+    for podcast in podcasts {
+        let title = escape_xml(&podcast.title);
+        output.push_str("    <outline type=\"rss\" text=\"");
+        output.push_str(&title);
+        output.push_str("\" title=\"");
+        output.push_str(&title);
+        output.push_str("\" xmlUrl=\"");
+        output.push_str(&escape_xml(&podcast.feed_url));
+        output.push('"');
+        if let Some(ref website) = podcast.website {
+            output.push_str(" htmlUrl=\"");
+            output.push_str(&escape_xml(website));
+            output.push('"');
+        }
+        output.push_str("/>\n");
+    }
+    output.push_str("  </body>\n</opml>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn should_parse_nested_outlines() {
+        let outlines = super::parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="1.0">
+  <head><title>Export</title></head>
+  <body>
+    <outline text="Tech">
+      <outline type="rss" text="Rustacean Station" xmlUrl="https://rustacean-station.org/podcast.rss"/>
+      <outline type="rss" text="Duplicate" xmlUrl="https://rustacean-station.org/podcast.rss"/>
+    </outline>
+    <outline type="rss" title="Floss &amp; Weekly" xmlUrl=" https://feeds.twit.tv/floss.xml "/>
+    <outline type="link" text="Website" url="https://example.com"/>
+  </body>
+</opml>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            outlines,
+            vec![
+                super::Outline {
+                    feed_url: "https://rustacean-station.org/podcast.rss".into(),
+                    title: Some("Rustacean Station".into()),
+                },
+                super::Outline {
+                    feed_url: "https://feeds.twit.tv/floss.xml".into(),
+                    title: Some("Floss & Weekly".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_other_documents() {
+        assert!(super::parse("<rss><channel/></rss>").is_err());
+        assert!(super::parse("not xml").is_err());
+    }
+
+    #[test]
+    fn should_render_parsable_document() {
+        let podcast = entertainarr_domain::podcast::entity::Podcast {
+            id: 1,
+            feed_url: "https://example.com/feed?a=1&b=2".into(),
+            title: "Tom & Jerry <show>".into(),
+            description: None,
+            image_url: None,
+            language: None,
+            website: Some("https://example.com".into()),
+            author: None,
+            categories: Vec::new(),
+            explicit: None,
+            persons: Vec::new(),
+            funding: Vec::new(),
+            feed_cache: Default::default(),
+            last_synced_at: None,
+            last_sync_error: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let output = super::render(&[podcast]);
+        let outlines = super::parse(&output).unwrap();
+        assert_eq!(
+            outlines,
+            vec![super::Outline {
+                feed_url: "https://example.com/feed?a=1&b=2".into(),
+                title: Some("Tom & Jerry <show>".into()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::entity::PodcastSubscriptionSettingsUpdate;
+use entertainarr_domain::podcast::prelude::PodcastService;
+
+use crate::entity::podcast::{
+    PodcastDocument, PodcastSubscriptionUpdateAttributes, PodcastSubscriptionUpdateDocument,
+    errors::{CODE_AUTO_DOWNLOAD_TOO_LARGE, CODE_PLAYBACK_SPEED_OUT_OF_RANGE, CODE_SKIP_TOO_LONG},
+};
+use crate::entity::{ApiError, ApiErrorDetail, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+
+const MIN_PLAYBACK_SPEED: f32 = 0.5;
+const MAX_PLAYBACK_SPEED: f32 = 4.0;
+/// Skipping more than 10 minutes is most likely a mistake
+const MAX_SKIP: u32 = 10 * 60;
+
+fn validate(
+    attributes: PodcastSubscriptionUpdateAttributes,
+) -> Result<PodcastSubscriptionSettingsUpdate, ApiError> {
+    if let Some(speed) = attributes.playback_speed
+        && !(MIN_PLAYBACK_SPEED..=MAX_PLAYBACK_SPEED).contains(&speed)
+    {
+        return Err(
+            ApiError::bad_request("invalid subscription settings").with_detail(
+                ApiErrorDetail::new("playbackSpeed", CODE_PLAYBACK_SPEED_OUT_OF_RANGE),
+            ),
+        );
+    }
+    for (name, value) in [
+        ("skipStart", attributes.skip_start),
+        ("skipEnd", attributes.skip_end),
+    ] {
+        if value.is_some_and(|value| value > MAX_SKIP) {
+            return Err(ApiError::bad_request("invalid subscription settings")
+                .with_detail(ApiErrorDetail::new(name, CODE_SKIP_TOO_LONG)));
+        }
+    }
+    if attributes
+        .auto_download
+        .is_some_and(|value| value > super::MAX_AUTO_DOWNLOAD)
+    {
+        return Err(
+            ApiError::bad_request("invalid subscription settings").with_detail(
+                ApiErrorDetail::new("autoDownload", CODE_AUTO_DOWNLOAD_TOO_LARGE),
+            ),
+        );
+    }
+    Ok(PodcastSubscriptionSettingsUpdate {
+        playback_speed: attributes.playback_speed,
+        skip_start: attributes.skip_start,
+        skip_end: attributes.skip_end,
+        auto_download: attributes.auto_download,
+        include_in_home: attributes.include_in_home,
+        notify: attributes.notify,
+    })
+}
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(podcast_id): Path<u64>,
+    Json(payload): Json<ApiResource<PodcastSubscriptionUpdateDocument>>,
+) -> Result<Json<ApiResource<PodcastDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let update = validate(payload.data.attributes)?;
+    let subscription = state
+        .podcast_service()
+        .update_subscription(user_id, podcast_id, update)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to update podcast subscription");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast subscription not found"))?;
+    let podcast = state
+        .podcast_service()
+        .find_by_id(podcast_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to find podcast");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast not found"))?;
+
+    let mut document = PodcastDocument::from(podcast);
+    document.attributes.subscription = Some(subscription.into());
+    Ok(Json(ApiResource::new(document)))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Json;
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use chrono::Utc;
+    use entertainarr_domain::podcast::entity::{
+        Podcast, PodcastSubscription, PodcastSubscriptionSettings,
+    };
+    use entertainarr_domain::podcast::prelude::MockPodcastService;
+
+    use crate::entity::ApiResource;
+    use crate::entity::podcast::{
+        PodcastSubscriptionUpdateAttributes, PodcastSubscriptionUpdateDocument,
+    };
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    fn payload(
+        attributes: PodcastSubscriptionUpdateAttributes,
+    ) -> Json<ApiResource<PodcastSubscriptionUpdateDocument>> {
+        Json(ApiResource::new(PodcastSubscriptionUpdateDocument::new(
+            attributes,
+        )))
+    }
+
+    #[tokio::test]
+    async fn should_update_settings() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_update_subscription()
+            .return_once(|user_id, podcast_id, update| {
+                assert_eq!(user_id, 1);
+                assert_eq!(podcast_id, 2);
+                assert_eq!(update.playback_speed, Some(1.5));
+                assert_eq!(update.notify, None);
+                Box::pin(async move {
+                    Ok(Some(PodcastSubscription {
+                        user_id,
+                        podcast_id,
+                        auto_queue: false,
+                        settings: PodcastSubscriptionSettings {
+                            playback_speed: 1.5,
+                            ..Default::default()
+                        },
+                        created_at: Utc::now(),
+                    }))
+                })
+            });
+        podcast_service
+            .expect_find_by_id()
+            .return_once(|podcast_id| {
+                Box::pin(async move {
+                    Ok(Some(Podcast {
+                        id: podcast_id,
+                        feed_url: "https://example.com/feed.rss".into(),
+                        title: "Example".into(),
+                        description: None,
+                        image_url: None,
+                        language: None,
+                        website: None,
+                        author: None,
+                        categories: Vec::new(),
+                        explicit: None,
+                        persons: Vec::new(),
+                        funding: Vec::new(),
+                        last_synced_at: None,
+                        last_sync_error: None,
+                        feed_cache: Default::default(),
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    }))
+                })
+            });
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let res = super::handle(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            payload(PodcastSubscriptionUpdateAttributes {
+                playback_speed: Some(1.5),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        let subscription = res.0.data.attributes.subscription.unwrap();
+        assert_eq!(subscription.playback_speed, 1.5);
+        assert!(subscription.include_in_home);
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_playback_speed() {
+        let state = MockServerState::builder().build();
+        let err = super::handle(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            payload(PodcastSubscriptionUpdateAttributes {
+                playback_speed: Some(12.0),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.detail.unwrap().attribute, "playbackSpeed");
+    }
+
+    #[tokio::test]
+    async fn should_reject_large_auto_download() {
+        let state = MockServerState::builder().build();
+        let err = super::handle(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            payload(PodcastSubscriptionUpdateAttributes {
+                auto_download: Some(10_000),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
+        let detail = err.detail.unwrap();
+        assert_eq!(detail.attribute, "autoDownload");
+        assert_eq!(detail.code, "auto-download-too-large");
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_not_subscribed() {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_update_subscription()
+            .return_once(|_, _, _| Box::pin(async move { Ok(None) }));
+        let state = MockServerState::builder().podcast(podcast_service).build();
+        let err = super::handle(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            payload(PodcastSubscriptionUpdateAttributes {
+                notify: Some(true),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+}
@@ -74,6 +74,14 @@ mod tests {
                         image_url: None,
                         language: None,
                         website: None,
+                        author: None,
+                        categories: Vec::new(),
+                        explicit: None,
+                        persons: Vec::new(),
+                        funding: Vec::new(),
+                        last_synced_at: None,
+                        last_sync_error: None,
+                        feed_cache: Default::default(),
                         created_at: Utc::now(),
                         updated_at: Utc::now(),
                     })
@@ -27,6 +27,7 @@ where
             .map(PodcastDocument::from)
             .collect::<Vec<_>>(),
         includes: Vec::new(),
+        meta: None,
     }))
 }
 
@@ -38,8 +39,13 @@ mod integration {
 
     use crate::server::prelude::tests::MockServerState;
     use entertainarr_domain::{
-        auth::{entity::Profile, prelude::MockAuthenticationService},
-        podcast::{entity::Podcast, prelude::MockPodcastService},
+        auth::{entity::Principal, prelude::MockAuthenticationService},
+        podcast::{
+            entity::{
+                Podcast, PodcastSubscription, PodcastSubscriptionSettings, SubscribedPodcast,
+            },
+            prelude::MockPodcastService,
+        },
     };
 
     #[tokio::test]
@@ -174,22 +180,47 @@ mod integration {
     async fn should_answer_if_autheticated() {
         let router = crate::server::handler::create();
         let mut auth_service = MockAuthenticationService::new();
-        auth_service
-            .expect_verify()
-            .returning(|_| Box::pin(async { Ok(Profile { id: 1 }) }));
+        auth_service.expect_verify().returning(|_| {
+            Box::pin(async {
+                Ok(Principal {
+                    user_id: 1,
+                    session_id: 1,
+                })
+            })
+        });
         let mut podcast_service = MockPodcastService::new();
         podcast_service.expect_subscriptions().returning(|_| {
             Box::pin(async {
-                Ok(vec![Podcast {
-                    id: 1,
-                    title: "title".into(),
-                    feed_url: "feed".into(),
-                    image_url: None,
-                    language: None,
-                    website: None,
-                    description: None,
-                    created_at: Utc::now(),
-                    updated_at: Utc::now(),
+                Ok(vec![SubscribedPodcast {
+                    podcast: Podcast {
+                        id: 1,
+                        title: "title".into(),
+                        feed_url: "feed".into(),
+                        image_url: None,
+                        language: None,
+                        website: None,
+                        author: None,
+                        categories: Vec::new(),
+                        explicit: None,
+                        persons: Vec::new(),
+                        funding: Vec::new(),
+                        last_synced_at: None,
+                        last_sync_error: None,
+                        feed_cache: Default::default(),
+                        description: None,
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    },
+                    subscription: PodcastSubscription {
+                        user_id: 1,
+                        podcast_id: 1,
+                        auto_queue: false,
+                        settings: PodcastSubscriptionSettings {
+                            playback_speed: 1.5,
+                            ..Default::default()
+                        },
+                        created_at: Utc::now(),
+                    },
                 }])
             })
         });
@@ -211,15 +242,25 @@ mod integration {
             .await
             .unwrap();
         assert_eq!(res.status(), axum::http::StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains(r#""playbackSpeed":1.5"#));
     }
 
     #[tokio::test]
     async fn should_fail_if_service_fails() {
         let router = crate::server::handler::create();
         let mut auth_service = MockAuthenticationService::new();
-        auth_service
-            .expect_verify()
-            .returning(|_| Box::pin(async { Ok(Profile { id: 1 }) }));
+        auth_service.expect_verify().returning(|_| {
+            Box::pin(async {
+                Ok(Principal {
+                    user_id: 1,
+                    session_id: 1,
+                })
+            })
+        });
         let mut podcast_service = MockPodcastService::new();
         podcast_service
             .expect_subscriptions()
@@ -0,0 +1,89 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::PodcastEpisodeService;
+
+use crate::entity::podcast_episode::PodcastEpisodeChapterDocument;
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+
+/// Lists the chapters of the episode, fetched from its chapters url on first access
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(_): CurrentUser,
+    Path(podcast_episode_id): Path<u64>,
+) -> Result<Json<ApiResource<Vec<PodcastEpisodeChapterDocument>>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let chapters = state
+        .podcast_episode_service()
+        .chapters(podcast_episode_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to load podcast episode chapters");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode chapters not found"))?;
+
+    Ok(Json(ApiResource::new(
+        chapters
+            .into_iter()
+            .enumerate()
+            .map(|(position, chapter)| PodcastEpisodeChapterDocument {
+                id: format!("{podcast_episode_id}-{position}"),
+                kind: Default::default(),
+                attributes: chapter.into(),
+            })
+            .collect(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use entertainarr_domain::podcast::entity::PodcastChapter;
+    use entertainarr_domain::podcast::prelude::MockPodcastEpisodeService;
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    #[tokio::test]
+    async fn should_list_chapters() {
+        let mut service = MockPodcastEpisodeService::new();
+        service.expect_chapters().return_once(|podcast_episode_id| {
+            assert_eq!(podcast_episode_id, 2);
+            Box::pin(async {
+                Ok(Some(vec![PodcastChapter {
+                    start: Duration::from_secs(30),
+                    end: None,
+                    title: Some("Intro".into()),
+                    image_url: None,
+                    url: None,
+                }]))
+            })
+        });
+        let state = MockServerState::builder().podcast_episode(service).build();
+        let res = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap();
+        assert_eq!(res.data.len(), 1);
+        assert_eq!(res.data[0].id, "2-0");
+        assert_eq!(res.data[0].attributes.start, Duration::from_secs(30));
+        assert_eq!(res.data[0].attributes.title.as_deref(), Some("Intro"));
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_episode_has_no_chapters() {
+        let mut service = MockPodcastEpisodeService::new();
+        service
+            .expect_chapters()
+            .return_once(|_| Box::pin(async { Ok(None) }));
+        let state = MockServerState::builder().podcast_episode(service).build();
+        let err = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,105 @@
+//! Opaque cursor given to the clients, the hex encoding of `field:id:value` so it can be put in a
+//! url as is. A missing value is written as an empty string.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use entertainarr_domain::podcast::prelude::{PodcastEpisodeCursor, PodcastEpisodeSortValue};
+
+pub fn encode(cursor: &PodcastEpisodeCursor) -> String {
+    let raw = match &cursor.value {
+        PodcastEpisodeSortValue::PublishedAt(value) => format!(
+            "published_at:{}:{}",
+            cursor.id,
+            value.map(|value| value.to_rfc3339()).unwrap_or_default()
+        ),
+        PodcastEpisodeSortValue::Duration(value) => format!(
+            "duration:{}:{}",
+            cursor.id,
+            value
+                .map(|value| value.as_secs().to_string())
+                .unwrap_or_default()
+        ),
+        PodcastEpisodeSortValue::Title(value) => format!("title:{}:{value}", cursor.id),
+        PodcastEpisodeSortValue::ProgressUpdatedAt(value) => format!(
+            "progress_updated_at:{}:{}",
+            cursor.id,
+            value.map(|value| value.to_rfc3339()).unwrap_or_default()
+        ),
+    };
+    raw.bytes()
+        .fold(String::with_capacity(raw.len() * 2), |mut output, byte| {
+            let _ = write!(output, "{byte:02x}");
+            output
+        })
+}
+
+pub fn decode(input: &str) -> Option<PodcastEpisodeCursor> {
+    if !input.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes = (0..input.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(input.get(index..index + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    let raw = String::from_utf8(bytes).ok()?;
+
+    let mut parts = raw.splitn(3, ':');
+    let field = parts.next()?;
+    let id = parts.next()?.parse::<u64>().ok()?;
+    let value = parts.next()?;
+
+    let value = match field {
+        "published_at" => PodcastEpisodeSortValue::PublishedAt(parse_optional(value)?),
+        "duration" => PodcastEpisodeSortValue::Duration(
+            parse_optional::<u64>(value)?.map(Duration::from_secs),
+        ),
+        "title" => PodcastEpisodeSortValue::Title(value.to_string()),
+        "progress_updated_at" => PodcastEpisodeSortValue::ProgressUpdatedAt(parse_optional(value)?),
+        _ => return None,
+    };
+
+    Some(PodcastEpisodeCursor { value, id })
+}
+
+fn parse_optional<T: std::str::FromStr>(value: &str) -> Option<Option<T>> {
+    if value.is_empty() {
+        Some(None)
+    } else {
+        value.parse().ok().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use entertainarr_domain::podcast::prelude::{PodcastEpisodeCursor, PodcastEpisodeSortValue};
+
+    #[test]
+    fn should_decode_encoded_cursors() {
+        for value in [
+            PodcastEpisodeSortValue::PublishedAt(Some("2025-01-02T03:04:05Z".parse().unwrap())),
+            PodcastEpisodeSortValue::PublishedAt(None),
+            PodcastEpisodeSortValue::Duration(Some(Duration::from_secs(1800))),
+            PodcastEpisodeSortValue::Duration(None),
+            PodcastEpisodeSortValue::Title("Episode 12: the return".into()),
+            PodcastEpisodeSortValue::ProgressUpdatedAt(None),
+        ] {
+            let cursor = PodcastEpisodeCursor { value, id: 42 };
+            let encoded = super::encode(&cursor);
+            assert!(encoded.chars().all(|c| c.is_ascii_hexdigit()));
+            assert_eq!(super::decode(&encoded), Some(cursor));
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_cursors() {
+        assert_eq!(super::decode("not a cursor"), None);
+        assert_eq!(super::decode("abc"), None);
+        // "unknown:1:"
+        assert_eq!(super::decode("756e6b6e6f776e3a313a"), None);
+        // "duration:1:long"
+        assert_eq!(super::decode("6475726174696f6e3a313a6c6f6e67"), None);
+    }
+}
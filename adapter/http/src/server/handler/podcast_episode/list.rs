@@ -1,34 +1,68 @@
 use std::collections::HashSet;
+use std::time::Duration;
 
 use crate::entity::podcast::PodcastDocument;
 use crate::entity::podcast_episode::{
     PodcastEpisodeDocument, PodcastEpisodeInclude, PodcastEpisodeRelation,
+    errors::CODE_INVALID_CURSOR,
 };
-use crate::entity::{ApiError, ApiResource};
+use crate::entity::{ApiError, ApiErrorDetail, ApiMeta, ApiResource};
 use axum::Json;
 use axum::extract::State;
 use serde_qs::axum::QsQuery;
 
-use crate::server::extractor::user::CurrentUser;
+use crate::server::extractor::user::CurrentSession;
 use crate::server::handler::podcast_episode::PodcastEpisodeField;
 use crate::server::handler::prelude::{Page, Sort, from_comma_separated};
 use entertainarr_domain::podcast::prelude::{
-    ListPodcastEpisodeFilter, ListPodcastEpisodeParams, PodcastEpisodeService, PodcastService,
+    ListPodcastEpisodeFilter, ListPodcastEpisodeParams, PodcastEpisodePage, PodcastEpisodeService,
+    PodcastService,
 };
 
 #[derive(Default, serde::Deserialize)]
 pub struct QueryFilter {
+    #[serde(default)]
+    podcast_id: Option<u64>,
+    /// Comma separated list of podcast ids
+    #[serde(default, deserialize_with = "from_comma_separated")]
+    podcast_ids: Vec<u64>,
     #[serde(default)]
     subscribed: Option<bool>,
     #[serde(default)]
     watched: Option<bool>,
+    #[serde(default)]
+    home: Option<bool>,
+    #[serde(default)]
+    in_progress: Option<bool>,
+    #[serde(default)]
+    published_after: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    published_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Minimum duration, in seconds
+    #[serde(default)]
+    min_duration: Option<u64>,
+    /// Maximum duration, in seconds
+    #[serde(default)]
+    max_duration: Option<u64>,
+    #[serde(default)]
+    file_type: Option<String>,
 }
 
 impl From<QueryFilter> for ListPodcastEpisodeFilter {
     fn from(value: QueryFilter) -> Self {
+        let mut podcast_ids = value.podcast_ids;
+        podcast_ids.extend(value.podcast_id);
         Self {
+            podcast_ids,
             subscribed: value.subscribed,
             watched: value.watched,
+            home: value.home,
+            in_progress: value.in_progress,
+            published_after: value.published_after,
+            published_before: value.published_before,
+            min_duration: value.min_duration.map(Duration::from_secs),
+            max_duration: value.max_duration.map(Duration::from_secs),
+            file_type: value.file_type,
         }
     }
 }
@@ -47,19 +81,41 @@ pub struct QueryParams {
 
 pub async fn handle<S>(
     State(state): State<S>,
-    CurrentUser(user_id): CurrentUser,
+    CurrentSession(principal): CurrentSession,
     QsQuery(params): QsQuery<QueryParams>,
 ) -> Result<Json<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>>, ApiError>
 where
     S: crate::server::prelude::ServerState,
 {
-    let list = state
+    let user_id = principal.user_id;
+    let sort: entertainarr_domain::prelude::Sort<
+        entertainarr_domain::podcast::prelude::PodcastEpisodeField,
+    > = params.sort.into();
+    // the cursor only makes sense for the sort it was created with
+    let cursor = match params.page.cursor.as_deref() {
+        Some(input) => Some(
+            super::cursor::decode(input)
+                .filter(|cursor| cursor.value.field() == sort.field)
+                .ok_or_else(|| {
+                    ApiError::bad_request("invalid cursor")
+                        .with_detail(ApiErrorDetail::new("page[cursor]", CODE_INVALID_CURSOR))
+                })?,
+        ),
+        None => None,
+    };
+
+    let PodcastEpisodePage {
+        items: list,
+        total,
+        next_cursor,
+    } = state
         .podcast_episode_service()
         .list(ListPodcastEpisodeParams {
             user_id,
             filter: params.filter.into(),
-            sort: params.sort.into(),
+            sort,
             page: params.page.into(),
+            cursor,
         })
         .await
         .map_err(|err| {
@@ -84,22 +140,42 @@ where
             .map(PodcastDocument::from)
             .map(PodcastEpisodeRelation::Podcast)
             .collect::<Vec<_>>();
-        includes.extend(podcasts.into_iter());
+        includes.extend(podcasts);
     }
 
-    let data = list
+    let mut data = list
         .into_iter()
         .map(super::PodcastEpisodeDocument::from)
         .collect::<Vec<_>>();
+    super::sign_media_urls(&state, &principal, &mut data).await?;
 
-    Ok(Json(ApiResource { data, includes }))
+    Ok(Json(ApiResource {
+        data,
+        includes,
+        meta: Some(ApiMeta {
+            total,
+            next_cursor: next_cursor.as_ref().map(super::cursor::encode),
+        }),
+    }))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use axum::extract::State;
+    use entertainarr_domain::auth::prelude::MockAuthenticationService;
+    use entertainarr_domain::podcast::prelude::{
+        ListPodcastEpisodeFilter, MockPodcastEpisodeService, PodcastEpisodeCursor,
+        PodcastEpisodePage, PodcastEpisodeSortValue,
+    };
     use entertainarr_domain::prelude::SortOrder;
+    use serde_qs::axum::QsQuery;
 
     use crate::entity::podcast_episode::PodcastEpisodeField;
+    use crate::server::extractor::user::CurrentSession;
+    use crate::server::handler::podcast_queue::tests::podcast_episode;
+    use crate::server::prelude::tests::{MockServerState, principal};
 
     #[test]
     fn should_deserialize_sort_and_include() {
@@ -109,4 +185,165 @@ mod tests {
         assert_eq!(payload.sort.field, PodcastEpisodeField::PublishedAt);
         assert_eq!(payload.sort.order, SortOrder::Desc);
     }
+
+    #[test]
+    fn should_deserialize_home_filter() {
+        let payload: super::QueryParams = serde_qs::from_str("filter[home]=true").unwrap();
+        assert_eq!(payload.filter.home, Some(true));
+    }
+
+    #[test]
+    fn should_deserialize_podcast_filter() {
+        let payload: super::QueryParams =
+            serde_qs::from_str("filter[podcast_id]=2&filter[watched]=false").unwrap();
+        assert_eq!(payload.filter.podcast_id, Some(2));
+        assert_eq!(payload.filter.watched, Some(false));
+    }
+
+    #[test]
+    fn should_deserialize_all_filters() {
+        let payload: super::QueryParams = serde_qs::from_str(
+            "filter[podcast_id]=3&filter[podcast_ids]=1,2&filter[subscribed]=false&filter[in_progress]=true&filter[published_after]=2025-01-01T00:00:00Z&filter[min_duration]=60&filter[max_duration]=3600&filter[file_type]=audio/mpeg&sort=-progress_updated_at",
+        )
+        .unwrap();
+        assert_eq!(payload.sort.field, PodcastEpisodeField::ProgressUpdatedAt);
+        let filter = ListPodcastEpisodeFilter::from(payload.filter);
+        assert_eq!(filter.podcast_ids, vec![1, 2, 3]);
+        assert_eq!(filter.subscribed, Some(false));
+        assert_eq!(filter.in_progress, Some(true));
+        assert_eq!(
+            filter.published_after.unwrap().to_rfc3339(),
+            "2025-01-01T00:00:00+00:00"
+        );
+        assert!(filter.published_before.is_none());
+        assert_eq!(filter.min_duration, Some(Duration::from_secs(60)));
+        assert_eq!(filter.max_duration, Some(Duration::from_secs(3600)));
+        assert_eq!(filter.file_type.as_deref(), Some("audio/mpeg"));
+    }
+
+    #[test]
+    fn should_deserialize_sort_fields() {
+        for (input, field) in [
+            ("duration", PodcastEpisodeField::Duration),
+            ("-title", PodcastEpisodeField::Title),
+        ] {
+            let payload: super::QueryParams = serde_qs::from_str(&format!("sort={input}")).unwrap();
+            assert_eq!(payload.sort.field, field);
+        }
+    }
+
+    #[test]
+    fn should_deserialize_page() {
+        let payload: super::QueryParams = serde_qs::from_str("page[limit]=10").unwrap();
+        assert_eq!(payload.page.limit, 10);
+        assert_eq!(payload.page.offset, 0);
+        assert!(payload.page.cursor.is_none());
+
+        let payload: super::QueryParams =
+            serde_qs::from_str("page[limit]=10&page[cursor]=abcd").unwrap();
+        assert_eq!(payload.page.cursor.as_deref(), Some("abcd"));
+    }
+
+    #[tokio::test]
+    async fn should_return_total_and_next_cursor() {
+        let mut podcast_episode_service = MockPodcastEpisodeService::new();
+        podcast_episode_service.expect_list().return_once(|params| {
+            assert_eq!(params.page.limit, 2);
+            assert_eq!(
+                params.cursor,
+                Some(PodcastEpisodeCursor {
+                    value: PodcastEpisodeSortValue::Title("Episode 3".into()),
+                    id: 3,
+                })
+            );
+            Box::pin(async move {
+                Ok(PodcastEpisodePage {
+                    items: vec![podcast_episode(4, 1), podcast_episode(5, 1)],
+                    total: 12,
+                    next_cursor: Some(PodcastEpisodeCursor {
+                        value: PodcastEpisodeSortValue::Title("Episode 5".into()),
+                        id: 5,
+                    }),
+                })
+            })
+        });
+        let state = MockServerState::builder()
+            .podcast_episode(podcast_episode_service)
+            .build();
+
+        let cursor = super::super::cursor::encode(&PodcastEpisodeCursor {
+            value: PodcastEpisodeSortValue::Title("Episode 3".into()),
+            id: 3,
+        });
+        let params =
+            serde_qs::from_str(&format!("sort=title&page[limit]=2&page[cursor]={cursor}")).unwrap();
+        let res = super::handle(State(state), CurrentSession(principal()), QsQuery(params))
+            .await
+            .unwrap();
+        assert_eq!(res.data.len(), 2);
+        let meta = res.0.meta.unwrap();
+        assert_eq!(meta.total, 12);
+        let next_cursor = super::super::cursor::decode(&meta.next_cursor.unwrap()).unwrap();
+        assert_eq!(next_cursor.id, 5);
+    }
+
+    #[tokio::test]
+    async fn should_reject_cursor_of_another_sort() {
+        let state = MockServerState::builder()
+            .podcast_episode(MockPodcastEpisodeService::new())
+            .build();
+
+        let cursor = super::super::cursor::encode(&PodcastEpisodeCursor {
+            value: PodcastEpisodeSortValue::Duration(None),
+            id: 3,
+        });
+        let params = serde_qs::from_str(&format!("sort=title&page[cursor]={cursor}")).unwrap();
+        let err = super::handle(State(state), CurrentSession(principal()), QsQuery(params))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            err.detail.unwrap().code,
+            crate::entity::podcast_episode::errors::CODE_INVALID_CURSOR
+        );
+    }
+
+    #[tokio::test]
+    async fn should_sign_media_urls() {
+        let mut podcast_episode_service = MockPodcastEpisodeService::new();
+        podcast_episode_service.expect_list().return_once(|_| {
+            let mut downloaded = podcast_episode(4, 1);
+            downloaded.downloaded = true;
+            Box::pin(async move {
+                Ok(PodcastEpisodePage {
+                    items: vec![downloaded, podcast_episode(5, 1)],
+                    total: 2,
+                    next_cursor: None,
+                })
+            })
+        });
+        let mut authentication_service = MockAuthenticationService::new();
+        authentication_service
+            .expect_create_media_token()
+            .once()
+            .return_once(|principal, podcast_episode_id| {
+                assert_eq!(principal.session_id, 2);
+                assert_eq!(podcast_episode_id, 4);
+                Box::pin(async { Ok(String::from("token")) })
+            });
+        let state = MockServerState::builder()
+            .authentication(authentication_service)
+            .podcast_episode(podcast_episode_service)
+            .build();
+
+        let params = serde_qs::from_str("").unwrap();
+        let res = super::handle(State(state), CurrentSession(principal()), QsQuery(params))
+            .await
+            .unwrap();
+        assert_eq!(
+            res.data[0].attributes.media_url.as_deref(),
+            Some("/api/podcast-episodes/4/media?token=token")
+        );
+        assert!(res.data[1].attributes.media_url.is_none());
+    }
 }
@@ -0,0 +1,382 @@
+use std::path::PathBuf;
+
+use axum::body::Body;
+use axum::extract::{FromRequestParts, Path, Query, Request, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use entertainarr_domain::auth::prelude::{AuthenticationService, VerifyError};
+use entertainarr_domain::podcast::entity::PodcastEpisodeMediaSource;
+use entertainarr_domain::podcast::prelude::PodcastDownloadService;
+
+use crate::entity::ApiError;
+use crate::server::extractor::user::CurrentUser;
+use crate::server::media_proxy::MediaProxy;
+
+/// Episode files don't change once published, but they are only served to authenticated users
+const CACHE_CONTROL: HeaderValue = HeaderValue::from_static("private, max-age=86400");
+
+/// Headers of the request forwarded to the host, for it to answer with a part of the file
+const FORWARDED_REQUEST_HEADERS: [HeaderName; 4] = [
+    header::RANGE,
+    header::IF_RANGE,
+    header::IF_NONE_MATCH,
+    header::IF_MODIFIED_SINCE,
+];
+
+/// Headers of the host response forwarded to the client
+const FORWARDED_RESPONSE_HEADERS: [HeaderName; 6] = [
+    header::ACCEPT_RANGES,
+    header::CONTENT_LENGTH,
+    header::CONTENT_RANGE,
+    header::CONTENT_TYPE,
+    header::ETAG,
+    header::LAST_MODIFIED,
+];
+
+async fn serve_local(path: PathBuf, request: Request) -> Result<Response, ApiError> {
+    tower_http::services::ServeFile::new(path)
+        .try_call(request)
+        .await
+        .map(IntoResponse::into_response)
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to read podcast episode file");
+            ApiError::internal()
+        })
+}
+
+async fn serve_remote(
+    proxy: &MediaProxy,
+    url: &str,
+    headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    let request = proxy.get(url).map_err(|err| {
+        tracing::warn!(error = %err, "refusing to fetch podcast episode file");
+        ApiError::bad_gateway("podcast episode host not allowed")
+    })?;
+    let request = FORWARDED_REQUEST_HEADERS
+        .iter()
+        .filter_map(|name| Some((name, headers.get(name)?)))
+        .fold(request, |request, (name, value)| {
+            request.header(name, value)
+        });
+    let upstream = request.send().await.map_err(|err| {
+        tracing::warn!(error = ?err, "unable to fetch podcast episode file");
+        ApiError::bad_gateway("unable to fetch podcast episode file")
+    })?;
+    if upstream.status().is_server_error() {
+        tracing::warn!(status = %upstream.status(), "unable to fetch podcast episode file");
+        return Err(ApiError::bad_gateway(
+            "unable to fetch podcast episode file",
+        ));
+    }
+
+    let mut response = Response::builder().status(upstream.status());
+    for name in FORWARDED_RESPONSE_HEADERS.iter() {
+        if let Some(value) = upstream.headers().get(name) {
+            response = response.header(name, value);
+        }
+    }
+    response
+        .body(Body::from_stream(upstream.bytes_stream()))
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to build podcast episode response");
+            ApiError::internal()
+        })
+}
+
+#[derive(Default, serde::Deserialize)]
+pub struct QueryParams {
+    /// Media token of the episode, replacing the authorization header
+    #[serde(default)]
+    token: Option<String>,
+}
+
+/// Streams the episode file, from the copy stored on the server when there's one or from
+/// the host of the podcast otherwise, supporting range requests in both cases
+pub async fn handle<S>(
+    State(state): State<S>,
+    Path(podcast_episode_id): Path<u64>,
+    Query(params): Query<QueryParams>,
+    request: Request,
+) -> Result<Response, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let request = match params.token {
+        Some(token) => {
+            state
+                .authentication_service()
+                .verify_media_token(podcast_episode_id, &token)
+                .await
+                .map_err(|err| match err {
+                    VerifyError::ExpiredToken => ApiError::unauthorized("media token expired"),
+                    VerifyError::InvalidToken => ApiError::unauthorized("media token invalid"),
+                    VerifyError::Internal(inner) => {
+                        tracing::error!(error = ?inner, "unable to verify media token");
+                        ApiError::internal()
+                    }
+                })?;
+            request
+        }
+        None => {
+            let (mut parts, body) = request.into_parts();
+            CurrentUser::from_request_parts(&mut parts, &state).await?;
+            Request::from_parts(parts, body)
+        }
+    };
+
+    let media = state
+        .podcast_download_service()
+        .media(podcast_episode_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to find podcast episode media");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode not found"))?;
+
+    let mut response = match media.source {
+        PodcastEpisodeMediaSource::Local(path)
+            if tokio::fs::try_exists(&path).await.unwrap_or(false) =>
+        {
+            serve_local(path, request).await?
+        }
+        PodcastEpisodeMediaSource::Local(path) => {
+            tracing::warn!(
+                podcast_episode.id = podcast_episode_id,
+                path = %path.display(),
+                "podcast episode file missing, falling back to the host"
+            );
+            serve_remote(state.media_proxy(), &media.file_url, request.headers()).await?
+        }
+        PodcastEpisodeMediaSource::Remote(url) => {
+            serve_remote(state.media_proxy(), &url, request.headers()).await?
+        }
+    };
+    if response.status().is_success() {
+        let headers = response.headers_mut();
+        if let Some(value) = media
+            .file_type
+            .and_then(|value| HeaderValue::try_from(value).ok())
+        {
+            headers.insert(header::CONTENT_TYPE, value);
+        }
+        headers.insert(header::CACHE_CONTROL, CACHE_CONTROL);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::extract::{Path, Query, Request, State};
+    use axum::http::{StatusCode, header};
+    use entertainarr_domain::auth::prelude::{MockAuthenticationService, VerifyError};
+    use entertainarr_domain::podcast::entity::{PodcastEpisodeMedia, PodcastEpisodeMediaSource};
+    use entertainarr_domain::podcast::prelude::MockPodcastDownloadService;
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use crate::server::prelude::tests::MockServerState;
+
+    fn query() -> Query<super::QueryParams> {
+        Query(super::QueryParams {
+            token: Some("token".into()),
+        })
+    }
+
+    fn authentication() -> MockAuthenticationService {
+        let mut service = MockAuthenticationService::new();
+        service
+            .expect_verify_media_token()
+            .return_once(|podcast_episode_id, token| {
+                assert_eq!(podcast_episode_id, 2);
+                assert_eq!(token, "token");
+                Box::pin(async { Ok(1) })
+            });
+        service
+    }
+
+    fn state(source: PodcastEpisodeMediaSource, file_url: String) -> MockServerState {
+        let mut service = MockPodcastDownloadService::new();
+        service.expect_media().return_once(|podcast_episode_id| {
+            assert_eq!(podcast_episode_id, 2);
+            Box::pin(async move {
+                Ok(Some(PodcastEpisodeMedia {
+                    source,
+                    file_url,
+                    file_type: Some("audio/mpeg".into()),
+                }))
+            })
+        });
+        MockServerState::builder()
+            .authentication(authentication())
+            .podcast_download(service)
+            .build()
+    }
+
+    fn range_request(range: &str) -> Request {
+        Request::builder()
+            .header(header::RANGE, range)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_serve_local_file() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = tmpdir.path().join("2.bin");
+        std::fs::write(&path, b"some audio").unwrap();
+
+        let state = state(
+            PodcastEpisodeMediaSource::Local(path),
+            "https://example.com/2.mp3".into(),
+        );
+        let res = super::handle(State(state), Path(2), query(), Request::new(Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "audio/mpeg");
+        assert_eq!(
+            res.headers()[header::CACHE_CONTROL],
+            "private, max-age=86400"
+        );
+        assert_eq!(res.headers()[header::ACCEPT_RANGES], "bytes");
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), b"some audio");
+    }
+
+    #[tokio::test]
+    async fn should_serve_range_of_local_file() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = tmpdir.path().join("2.mp3");
+        std::fs::write(&path, b"some audio").unwrap();
+
+        let state = state(
+            PodcastEpisodeMediaSource::Local(path),
+            "https://example.com/2.mp3".into(),
+        );
+        let res = super::handle(State(state), Path(2), query(), range_request("bytes=5-"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 5-9/10");
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), b"audio");
+    }
+
+    #[tokio::test]
+    async fn should_proxy_range_of_remote_file() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/episode.mp3"))
+            .and(matchers::header("range", "bytes=5-"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("content-range", "bytes 5-9/10")
+                    .insert_header("set-cookie", "tracker=1")
+                    .set_body_raw("audio", "application/octet-stream"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let url = format!("{}/episode.mp3", server.uri());
+
+        let state = state(PodcastEpisodeMediaSource::Remote(url.clone()), url);
+        let res = super::handle(State(state), Path(2), query(), range_request("bytes=5-"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "audio/mpeg");
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 5-9/10");
+        assert!(res.headers().get(header::SET_COOKIE).is_none());
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), b"audio");
+    }
+
+    #[tokio::test]
+    async fn should_fallback_to_remote_file_when_local_missing() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = tmpdir.path().join("2.mp3");
+
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/episode.mp3"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("some audio"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let url = format!("{}/episode.mp3", server.uri());
+
+        let state = state(PodcastEpisodeMediaSource::Local(path), url);
+        let res = super::handle(State(state), Path(2), query(), Request::new(Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.as_ref(), b"some audio");
+    }
+
+    #[tokio::test]
+    async fn should_refuse_proxying_private_hosts() {
+        let mut state = state(
+            PodcastEpisodeMediaSource::Remote("http://169.254.169.254/latest".into()),
+            "http://169.254.169.254/latest".into(),
+        );
+        state.media_proxy = crate::server::media_proxy::MediaProxy::new(false).unwrap();
+        let err = super::handle(State(state), Path(2), query(), Request::new(Body::empty()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_episode_not_found() {
+        let mut service = MockPodcastDownloadService::new();
+        service
+            .expect_media()
+            .return_once(|_| Box::pin(async { Ok(None) }));
+        let state = MockServerState::builder()
+            .authentication(authentication())
+            .podcast_download(service)
+            .build();
+        let err = super::handle(State(state), Path(2), query(), Request::new(Body::empty()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn should_require_token_or_authorization_header() {
+        let state = MockServerState::builder().build();
+        let err = super::handle(
+            State(state),
+            Path(2),
+            Query(Default::default()),
+            Request::new(Body::empty()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_media_token() {
+        let mut service = MockAuthenticationService::new();
+        service
+            .expect_verify_media_token()
+            .return_once(|_, _| Box::pin(async { Err(VerifyError::InvalidToken) }));
+        let state = MockServerState::builder().authentication(service).build();
+        let err = super::handle(State(state), Path(2), query(), Request::new(Body::empty()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -2,23 +2,128 @@ use std::str::FromStr;
 
 use crate::entity::{
     Relation,
-    podcast::PodcastEntity,
+    podcast::{PodcastEntity, PodcastPersonAttributes},
     podcast_episode::{
-        PodcastEpisodeAttributes, PodcastEpisodeDocument, PodcastEpisodeField,
-        PodcastEpisodeInclude, PodcastEpisodeRelationship,
+        PodcastEpisodeAttributes, PodcastEpisodeChapterAttributes, PodcastEpisodeDocument,
+        PodcastEpisodeField, PodcastEpisodeInclude, PodcastEpisodeProgressAttributes,
+        PodcastEpisodeProgressDocument, PodcastEpisodeRelationship,
+        PodcastEpisodeTranscriptCueAttributes, PodcastEpisodeType,
     },
 };
-use axum::routing::get;
+use axum::routing::{get, put};
 
-use entertainarr_domain::podcast::entity::PodcastEpisode;
+use entertainarr_domain::podcast::entity::{
+    PodcastChapter, PodcastEpisode, PodcastEpisodeProgress, PodcastTranscriptCue,
+};
 
+pub mod chapters;
+pub mod cursor;
 pub mod list;
+pub mod media;
+pub mod played;
+pub mod progress;
+pub mod transcript;
 
 pub fn create<S>() -> axum::Router<S>
 where
     S: crate::server::prelude::ServerState + Clone,
 {
-    axum::Router::new().route("/podcast-episodes", get(list::handle::<S>))
+    axum::Router::new()
+        .route("/podcast-episodes", get(list::handle::<S>))
+        .route(
+            "/podcast-episodes/{podcast_episode_id}/chapters",
+            get(chapters::handle::<S>),
+        )
+        .route(
+            "/podcast-episodes/{podcast_episode_id}/media",
+            get(media::handle::<S>),
+        )
+        .route(
+            "/podcast-episodes/{podcast_episode_id}/transcript",
+            get(transcript::handle::<S>),
+        )
+        .route(
+            "/users/me/podcast-episodes/{podcast_episode_id}/progress",
+            put(progress::handle::<S>),
+        )
+        .route(
+            "/users/me/podcast-episodes/{podcast_episode_id}/played",
+            put(played::mark::<S>).delete(played::unmark::<S>),
+        )
+}
+
+/// Adds a token to the url of the episode files, for the players that can't send the
+/// authorization header, like an `<audio>` element
+pub(crate) async fn sign_media_urls<S>(
+    state: &S,
+    principal: &entertainarr_domain::auth::entity::Principal,
+    documents: &mut [PodcastEpisodeDocument],
+) -> Result<(), crate::entity::ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    use entertainarr_domain::auth::prelude::AuthenticationService;
+
+    for document in documents.iter_mut() {
+        let Some(media_url) = document.attributes.media_url.as_mut() else {
+            continue;
+        };
+        let token = state
+            .authentication_service()
+            .create_media_token(principal, document.id)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = ?err, "unable to create media token");
+                crate::entity::ApiError::internal()
+            })?;
+        media_url.push_str("?token=");
+        media_url.push_str(&token);
+    }
+    Ok(())
+}
+
+impl From<PodcastEpisodeProgress> for PodcastEpisodeProgressAttributes {
+    fn from(value: PodcastEpisodeProgress) -> Self {
+        Self {
+            progress: value.progress,
+            completed: value.completed,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+impl From<PodcastChapter> for PodcastEpisodeChapterAttributes {
+    fn from(value: PodcastChapter) -> Self {
+        Self {
+            start: value.start,
+            end: value.end,
+            title: value.title,
+            image_url: value.image_url,
+            url: value.url,
+        }
+    }
+}
+
+impl From<PodcastTranscriptCue> for PodcastEpisodeTranscriptCueAttributes {
+    fn from(value: PodcastTranscriptCue) -> Self {
+        Self {
+            start: value.start,
+            end: value.end,
+            speaker: value.speaker,
+            text: value.text,
+        }
+    }
+}
+
+impl From<PodcastEpisodeProgress> for PodcastEpisodeProgressDocument {
+    fn from(value: PodcastEpisodeProgress) -> Self {
+        Self {
+            id: value.podcast_episode_id,
+            kind: Default::default(),
+            attributes: value.into(),
+        }
+    }
 }
 
 impl From<PodcastEpisode> for PodcastEpisodeDocument {
@@ -36,6 +141,22 @@ impl From<PodcastEpisode> for PodcastEpisodeDocument {
                 file_url: value.file_url,
                 file_size: value.file_size,
                 file_type: value.file_type,
+                image_url: value.image_url,
+                season_number: value.season_number,
+                episode_number: value.episode_number,
+                episode_type: value.episode_type.map(PodcastEpisodeType::from),
+                chapters_url: value.chapters_url,
+                transcript_url: value.transcript.map(|transcript| transcript.url),
+                persons: value
+                    .persons
+                    .into_iter()
+                    .map(PodcastPersonAttributes::from)
+                    .collect(),
+                removed_at: value.removed_at,
+                media_url: value
+                    .downloaded
+                    .then(|| format!("/api/podcast-episodes/{}/media", value.id)),
+                progress: value.progress.map(PodcastEpisodeProgressAttributes::from),
                 created_at: value.created_at,
                 updated_at: value.updated_at,
             },
@@ -51,12 +172,33 @@ impl From<PodcastEpisode> for PodcastEpisodeDocument {
     }
 }
 
+impl From<entertainarr_domain::podcast::entity::PodcastEpisodeType> for PodcastEpisodeType {
+    fn from(value: entertainarr_domain::podcast::entity::PodcastEpisodeType) -> Self {
+        use entertainarr_domain::podcast::entity::PodcastEpisodeType as Domain;
+
+        match value {
+            Domain::Full => Self::Full,
+            Domain::Trailer => Self::Trailer,
+            Domain::Bonus => Self::Bonus,
+        }
+    }
+}
+
 impl From<PodcastEpisodeField> for entertainarr_domain::podcast::prelude::PodcastEpisodeField {
     fn from(value: PodcastEpisodeField) -> Self {
         match value {
             PodcastEpisodeField::PublishedAt => {
                 entertainarr_domain::podcast::prelude::PodcastEpisodeField::PublishedAt
             }
+            PodcastEpisodeField::Duration => {
+                entertainarr_domain::podcast::prelude::PodcastEpisodeField::Duration
+            }
+            PodcastEpisodeField::Title => {
+                entertainarr_domain::podcast::prelude::PodcastEpisodeField::Title
+            }
+            PodcastEpisodeField::ProgressUpdatedAt => {
+                entertainarr_domain::podcast::prelude::PodcastEpisodeField::ProgressUpdatedAt
+            }
         }
     }
 }
@@ -76,6 +218,9 @@ impl FromStr for PodcastEpisodeField {
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         match input {
             "published_at" => Ok(Self::PublishedAt),
+            "duration" => Ok(Self::Duration),
+            "title" => Ok(Self::Title),
+            "progress_updated_at" => Ok(Self::ProgressUpdatedAt),
             _ => Err(ParsePodcastEpisodeFieldError),
         }
     }
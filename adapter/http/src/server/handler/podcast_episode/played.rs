@@ -0,0 +1,106 @@
+use axum::Json;
+use axum::extract::{Path, State};
+
+use crate::entity::podcast_episode::PodcastEpisodeProgressDocument;
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+use entertainarr_domain::podcast::prelude::PodcastEpisodeService;
+
+async fn handle<S>(
+    state: S,
+    user_id: u64,
+    podcast_episode_id: u64,
+    played: bool,
+) -> Result<Json<ApiResource<PodcastEpisodeProgressDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let progress = state
+        .podcast_episode_service()
+        .mark_played(user_id, podcast_episode_id, played)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to mark podcast episode");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode not found"))?;
+
+    Ok(Json(ApiResource::new(progress.into())))
+}
+
+/// Marks the episode as played, keeping the playback position
+pub async fn mark<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(podcast_episode_id): Path<u64>,
+) -> Result<Json<ApiResource<PodcastEpisodeProgressDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    handle(state, user_id, podcast_episode_id, true).await
+}
+
+/// Marks the episode as unplayed, resetting the playback position
+pub async fn unmark<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(podcast_episode_id): Path<u64>,
+) -> Result<Json<ApiResource<PodcastEpisodeProgressDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    handle(state, user_id, podcast_episode_id, false).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use chrono::Utc;
+    use entertainarr_domain::podcast::entity::PodcastEpisodeProgress;
+    use entertainarr_domain::podcast::prelude::MockPodcastEpisodeService;
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    #[tokio::test]
+    async fn should_fail_if_service_fails() {
+        let mut service = MockPodcastEpisodeService::new();
+        service
+            .expect_mark_played()
+            .return_once(|_, _, _| Box::pin(async { Err(anyhow::anyhow!("oops")) }));
+        let state = MockServerState::builder().podcast_episode(service).build();
+        let err = super::mark(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn should_unmark() {
+        let mut service = MockPodcastEpisodeService::new();
+        service
+            .expect_mark_played()
+            .return_once(|user_id, podcast_episode_id, played| {
+                assert_eq!(user_id, 1);
+                assert_eq!(podcast_episode_id, 2);
+                assert!(!played);
+                Box::pin(async move {
+                    Ok(Some(PodcastEpisodeProgress {
+                        user_id,
+                        podcast_episode_id,
+                        progress: Duration::ZERO,
+                        completed: false,
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    }))
+                })
+            });
+        let state = MockServerState::builder().podcast_episode(service).build();
+        let res = super::unmark(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap();
+        assert!(!res.data.attributes.completed);
+    }
+}
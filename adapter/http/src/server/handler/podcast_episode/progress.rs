@@ -0,0 +1,174 @@
+use axum::Json;
+use axum::extract::{Path, State};
+
+use crate::entity::podcast_episode::{
+    PodcastEpisodeProgressDocument, PodcastEpisodeProgressUpdateDocument,
+};
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+use entertainarr_domain::podcast::prelude::PodcastEpisodeService;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(podcast_episode_id): Path<u64>,
+    Json(payload): Json<ApiResource<PodcastEpisodeProgressUpdateDocument>>,
+) -> Result<Json<ApiResource<PodcastEpisodeProgressDocument>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let attributes = payload.data.attributes;
+    let progress = state
+        .podcast_episode_service()
+        .update_progress(
+            user_id,
+            podcast_episode_id,
+            attributes.progress,
+            attributes.completed,
+        )
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to update podcast episode progress");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode not found"))?;
+
+    Ok(Json(ApiResource::new(progress.into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::Json;
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use chrono::Utc;
+    use entertainarr_domain::podcast::entity::PodcastEpisodeProgress;
+    use entertainarr_domain::podcast::prelude::MockPodcastEpisodeService;
+
+    use crate::entity::ApiResource;
+    use crate::entity::podcast_episode::PodcastEpisodeProgressUpdateDocument;
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    #[tokio::test]
+    async fn should_fail_if_episode_not_found() {
+        let mut service = MockPodcastEpisodeService::new();
+        service
+            .expect_update_progress()
+            .return_once(|_, _, _, _| Box::pin(async { Ok(None) }));
+        let state = MockServerState::builder().podcast_episode(service).build();
+        let err = super::handle(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            Json(ApiResource::new(PodcastEpisodeProgressUpdateDocument::new(
+                Duration::from_secs(42),
+                false,
+            ))),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn should_succeed() {
+        let mut service = MockPodcastEpisodeService::new();
+        service.expect_update_progress().return_once(
+            |user_id, podcast_episode_id, progress, completed| {
+                assert_eq!(user_id, 1);
+                assert_eq!(podcast_episode_id, 2);
+                assert_eq!(progress, Duration::from_secs(42));
+                assert!(!completed);
+                Box::pin(async move {
+                    Ok(Some(PodcastEpisodeProgress {
+                        user_id,
+                        podcast_episode_id,
+                        progress,
+                        completed,
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    }))
+                })
+            },
+        );
+        let state = MockServerState::builder().podcast_episode(service).build();
+        let Json(res) = super::handle(
+            State(state),
+            CurrentUser(1),
+            Path(2),
+            Json(ApiResource::new(PodcastEpisodeProgressUpdateDocument::new(
+                Duration::from_secs(42),
+                false,
+            ))),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.data.id, 2);
+        assert_eq!(res.data.attributes.progress, Duration::from_secs(42));
+    }
+}
+
+#[cfg(test)]
+mod integration {
+    use chrono::Utc;
+    use tower::ServiceExt;
+
+    use crate::server::prelude::tests::MockServerState;
+    use entertainarr_domain::{
+        auth::{entity::Principal, prelude::MockAuthenticationService},
+        podcast::{entity::PodcastEpisodeProgress, prelude::MockPodcastEpisodeService},
+    };
+
+    #[tokio::test]
+    async fn should_answer() {
+        let router = crate::server::handler::create();
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service.expect_verify().returning(|_| {
+            Box::pin(async move {
+                Ok(Principal {
+                    user_id: 1,
+                    session_id: 1,
+                })
+            })
+        });
+        let mut service = MockPodcastEpisodeService::new();
+        service.expect_update_progress().return_once(
+            |user_id, podcast_episode_id, progress, completed| {
+                assert_eq!(progress.as_secs(), 42);
+                assert!(completed);
+                Box::pin(async move {
+                    Ok(Some(PodcastEpisodeProgress {
+                        user_id,
+                        podcast_episode_id,
+                        progress,
+                        completed,
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    }))
+                })
+            },
+        );
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .podcast_episode(service)
+            .build();
+        let res = router
+            .with_state(state)
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/users/me/podcast-episodes/2/progress")
+                    .method(axum::http::Method::PUT)
+                    .header("Content-Type", "application/json")
+                    .header("Authorization", "Bearer token")
+                    .body(axum::body::Body::from(
+                        r#"{"data":{"type":"podcast-episode-progresses","attributes":{"progress":{"secs":42,"nanos":0},"completed":true}}}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::OK);
+    }
+}
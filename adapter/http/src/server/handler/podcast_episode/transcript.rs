@@ -0,0 +1,88 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::PodcastEpisodeService;
+
+use crate::entity::podcast_episode::PodcastEpisodeTranscriptCueDocument;
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentUser;
+
+/// Lists the cues of the episode transcript, fetched from its transcript url on first access
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(_): CurrentUser,
+    Path(podcast_episode_id): Path<u64>,
+) -> Result<Json<ApiResource<Vec<PodcastEpisodeTranscriptCueDocument>>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let cues = state
+        .podcast_episode_service()
+        .transcript(podcast_episode_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to load podcast episode transcript");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode transcript not found"))?;
+
+    Ok(Json(ApiResource::new(
+        cues.into_iter()
+            .enumerate()
+            .map(|(position, cue)| PodcastEpisodeTranscriptCueDocument {
+                id: format!("{podcast_episode_id}-{position}"),
+                kind: Default::default(),
+                attributes: cue.into(),
+            })
+            .collect(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use entertainarr_domain::podcast::entity::PodcastTranscriptCue;
+    use entertainarr_domain::podcast::prelude::MockPodcastEpisodeService;
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+
+    #[tokio::test]
+    async fn should_list_transcript_cues() {
+        let mut service = MockPodcastEpisodeService::new();
+        service
+            .expect_transcript()
+            .return_once(|podcast_episode_id| {
+                assert_eq!(podcast_episode_id, 2);
+                Box::pin(async {
+                    Ok(Some(vec![PodcastTranscriptCue {
+                        start: Duration::ZERO,
+                        end: Duration::from_secs(2),
+                        speaker: Some("Jane Doe".into()),
+                        text: "Hello".into(),
+                    }]))
+                })
+            });
+        let state = MockServerState::builder().podcast_episode(service).build();
+        let res = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap();
+        assert_eq!(res.data.len(), 1);
+        assert_eq!(res.data[0].attributes.speaker.as_deref(), Some("Jane Doe"));
+        assert_eq!(res.data[0].attributes.text, "Hello");
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_service_fails() {
+        let mut service = MockPodcastEpisodeService::new();
+        service
+            .expect_transcript()
+            .return_once(|_| Box::pin(async { Err(anyhow::anyhow!("oops")) }));
+        let state = MockServerState::builder().podcast_episode(service).build();
+        let err = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,53 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::PodcastQueueService;
+
+use crate::entity::podcast_episode::{PodcastEpisodeDocument, PodcastEpisodeRelation};
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentSession;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentSession(principal): CurrentSession,
+    Path(podcast_episode_id): Path<u64>,
+) -> Result<Json<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let user_id = principal.user_id;
+    let queue = state
+        .podcast_queue_service()
+        .dequeue(user_id, podcast_episode_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to dequeue podcast episode");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode not queued"))?;
+    super::render(&state, &principal, queue).await.map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::{Path, State};
+    use axum::http::StatusCode;
+    use entertainarr_domain::podcast::prelude::MockPodcastQueueService;
+
+    use crate::server::extractor::user::CurrentSession;
+    use crate::server::prelude::tests::{MockServerState, principal};
+
+    #[tokio::test]
+    async fn should_fail_when_not_queued() {
+        let mut podcast_queue_service = MockPodcastQueueService::new();
+        podcast_queue_service
+            .expect_dequeue()
+            .return_once(|_, _| Box::pin(async move { Ok(None) }));
+        let state = MockServerState::builder()
+            .podcast_queue(podcast_queue_service)
+            .build();
+        let err = super::handle(State(state), CurrentSession(principal()), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+}
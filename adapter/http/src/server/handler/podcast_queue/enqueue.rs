@@ -0,0 +1,28 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::PodcastQueueService;
+
+use crate::entity::podcast_episode::{PodcastEpisodeDocument, PodcastEpisodeRelation};
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentSession;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentSession(principal): CurrentSession,
+    Path(podcast_episode_id): Path<u64>,
+) -> Result<Json<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let user_id = principal.user_id;
+    let queue = state
+        .podcast_queue_service()
+        .enqueue(user_id, podcast_episode_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to enqueue podcast episode");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode not found"))?;
+    super::render(&state, &principal, queue).await.map(Json)
+}
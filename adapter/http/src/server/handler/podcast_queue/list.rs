@@ -0,0 +1,58 @@
+use axum::Json;
+use axum::extract::State;
+use entertainarr_domain::podcast::prelude::PodcastQueueService;
+
+use crate::entity::podcast_episode::{PodcastEpisodeDocument, PodcastEpisodeRelation};
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentSession;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentSession(principal): CurrentSession,
+) -> Result<Json<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let user_id = principal.user_id;
+    let queue = state
+        .podcast_queue_service()
+        .queue(user_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to list podcast queue");
+            ApiError::internal()
+        })?;
+    super::render(&state, &principal, queue).await.map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::State;
+    use entertainarr_domain::podcast::prelude::MockPodcastQueueService;
+
+    use crate::server::extractor::user::CurrentSession;
+    use crate::server::prelude::tests::{MockServerState, principal};
+
+    #[tokio::test]
+    async fn should_list_queue_in_order() {
+        let mut podcast_queue_service = MockPodcastQueueService::new();
+        podcast_queue_service.expect_queue().return_once(|user_id| {
+            assert_eq!(user_id, 1);
+            Box::pin(async move {
+                Ok(vec![
+                    super::super::tests::podcast_episode(3, 1),
+                    super::super::tests::podcast_episode(2, 1),
+                ])
+            })
+        });
+        let state = MockServerState::builder()
+            .podcast(super::super::tests::podcast_service())
+            .podcast_queue(podcast_queue_service)
+            .build();
+        let res = super::handle(State(state), CurrentSession(principal()))
+            .await
+            .unwrap();
+        let ids = res.0.data.iter().map(|e| e.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![3, 2]);
+    }
+}
@@ -0,0 +1,111 @@
+use axum::routing::{get, post, put};
+use entertainarr_domain::auth::entity::Principal;
+use entertainarr_domain::podcast::entity::PodcastEpisode;
+use entertainarr_domain::podcast::prelude::PodcastService;
+
+use crate::entity::podcast::PodcastDocument;
+use crate::entity::podcast_episode::{PodcastEpisodeDocument, PodcastEpisodeRelation};
+use crate::entity::{ApiError, ApiResource};
+
+pub mod dequeue;
+pub mod enqueue;
+pub mod list;
+pub mod play_next;
+pub mod reorder;
+
+pub fn create<S>() -> axum::Router<S>
+where
+    S: crate::server::prelude::ServerState + Clone,
+{
+    axum::Router::new()
+        .route("/users/me/queue", get(list::handle::<S>))
+        .route(
+            "/users/me/queue/{podcast_episode_id}",
+            put(enqueue::handle::<S>)
+                .patch(reorder::handle::<S>)
+                .delete(dequeue::handle::<S>),
+        )
+        .route(
+            "/users/me/queue/{podcast_episode_id}/play-next",
+            post(play_next::handle::<S>),
+        )
+}
+
+/// Renders the queue, always including the podcasts so the client can display it at once
+async fn render<S>(
+    state: &S,
+    principal: &Principal,
+    queue: Vec<PodcastEpisode>,
+) -> Result<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let mut podcast_ids = queue.iter().map(|e| e.podcast_id).collect::<Vec<_>>();
+    podcast_ids.sort_unstable();
+    podcast_ids.dedup();
+    let includes = state
+        .podcast_service()
+        .list_by_ids(&podcast_ids)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to list podcasts");
+            ApiError::internal()
+        })?
+        .into_iter()
+        .map(PodcastDocument::from)
+        .map(PodcastEpisodeRelation::Podcast)
+        .collect();
+    let mut data = queue
+        .into_iter()
+        .map(PodcastEpisodeDocument::from)
+        .collect::<Vec<_>>();
+    super::podcast_episode::sign_media_urls(state, principal, &mut data).await?;
+    Ok(ApiResource {
+        data,
+        includes,
+        meta: None,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use chrono::Utc;
+    use entertainarr_domain::podcast::entity::PodcastEpisode;
+    use entertainarr_domain::podcast::prelude::MockPodcastService;
+
+    pub fn podcast_episode(id: u64, podcast_id: u64) -> PodcastEpisode {
+        PodcastEpisode {
+            id,
+            podcast_id,
+            guid: None,
+            published_at: None,
+            title: format!("Episode {id}"),
+            description: None,
+            link: None,
+            duration: None,
+            file_url: format!("https://example.com/{id}.mp3"),
+            file_size: None,
+            file_type: None,
+            image_url: None,
+            season_number: None,
+            episode_number: None,
+            episode_type: None,
+            chapters_url: None,
+            transcript: None,
+            persons: Vec::new(),
+            removed_at: None,
+            downloaded: false,
+            progress: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn podcast_service() -> MockPodcastService {
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service
+            .expect_list_by_ids()
+            .return_once(|_| Box::pin(async move { Ok(Vec::new()) }));
+        podcast_service
+    }
+}
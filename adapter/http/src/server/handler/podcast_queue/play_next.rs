@@ -0,0 +1,62 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::PodcastQueueService;
+
+use crate::entity::podcast_episode::{PodcastEpisodeDocument, PodcastEpisodeRelation};
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentSession;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentSession(principal): CurrentSession,
+    Path(podcast_episode_id): Path<u64>,
+) -> Result<Json<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let user_id = principal.user_id;
+    let queue = state
+        .podcast_queue_service()
+        .play_next(user_id, podcast_episode_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to play podcast episode next");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode not found"))?;
+    super::render(&state, &principal, queue).await.map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::{Path, State};
+    use entertainarr_domain::podcast::prelude::MockPodcastQueueService;
+
+    use crate::server::extractor::user::CurrentSession;
+    use crate::server::prelude::tests::{MockServerState, principal};
+
+    #[tokio::test]
+    async fn should_return_updated_queue() {
+        let mut podcast_queue_service = MockPodcastQueueService::new();
+        podcast_queue_service
+            .expect_play_next()
+            .return_once(|user_id, podcast_episode_id| {
+                assert_eq!(user_id, 1);
+                assert_eq!(podcast_episode_id, 2);
+                Box::pin(async move {
+                    Ok(Some(vec![
+                        super::super::tests::podcast_episode(2, 1),
+                        super::super::tests::podcast_episode(1, 1),
+                    ]))
+                })
+            });
+        let state = MockServerState::builder()
+            .podcast(super::super::tests::podcast_service())
+            .podcast_queue(podcast_queue_service)
+            .build();
+        let res = super::handle(State(state), CurrentSession(principal()), Path(2))
+            .await
+            .unwrap();
+        assert_eq!(res.0.data[0].id, 2);
+    }
+}
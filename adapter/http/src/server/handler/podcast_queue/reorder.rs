@@ -0,0 +1,73 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use entertainarr_domain::podcast::prelude::PodcastQueueService;
+
+use crate::entity::podcast_episode::{
+    PodcastEpisodeDocument, PodcastEpisodeRelation, PodcastQueueMoveDocument,
+};
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentSession;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentSession(principal): CurrentSession,
+    Path(podcast_episode_id): Path<u64>,
+    Json(payload): Json<ApiResource<PodcastQueueMoveDocument>>,
+) -> Result<Json<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let user_id = principal.user_id;
+    let queue = state
+        .podcast_queue_service()
+        .reorder(
+            user_id,
+            podcast_episode_id,
+            payload.data.attributes.position,
+        )
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to reorder podcast queue");
+            ApiError::internal()
+        })?
+        .ok_or_else(|| ApiError::not_found("podcast episode not queued"))?;
+    super::render(&state, &principal, queue).await.map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Json;
+    use axum::extract::{Path, State};
+    use entertainarr_domain::podcast::prelude::MockPodcastQueueService;
+
+    use crate::entity::ApiResource;
+    use crate::entity::podcast_episode::PodcastQueueMoveDocument;
+    use crate::server::extractor::user::CurrentSession;
+    use crate::server::prelude::tests::{MockServerState, principal};
+
+    #[tokio::test]
+    async fn should_move_episode() {
+        let mut podcast_queue_service = MockPodcastQueueService::new();
+        podcast_queue_service.expect_reorder().return_once(
+            |user_id, podcast_episode_id, position| {
+                assert_eq!(user_id, 1);
+                assert_eq!(podcast_episode_id, 2);
+                assert_eq!(position, 0);
+                Box::pin(async move { Ok(Some(vec![super::super::tests::podcast_episode(2, 1)])) })
+            },
+        );
+        let state = MockServerState::builder()
+            .podcast(super::super::tests::podcast_service())
+            .podcast_queue(podcast_queue_service)
+            .build();
+        let res = super::handle(
+            State(state),
+            CurrentSession(principal()),
+            Path(2),
+            Json(ApiResource::new(PodcastQueueMoveDocument::new(0))),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.0.data.len(), 1);
+    }
+}
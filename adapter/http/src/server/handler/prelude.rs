@@ -1,12 +1,15 @@
 use entertainarr_domain::prelude::SortOrder;
 use serde::Deserialize;
 
-#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Deserialize)]
 pub struct Page {
     #[serde(default = "Page::default_limit")]
     pub limit: u32,
     #[serde(default = "Page::default_offset")]
     pub offset: u32,
+    /// Starts the page after the one that returned it, replacing the offset where supported
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 impl Default for Page {
@@ -14,11 +17,15 @@ impl Default for Page {
         Self {
             limit: Self::default_limit(),
             offset: Self::default_offset(),
+            cursor: None,
         }
     }
 }
 
 impl Page {
+    /// Largest page a client can request, bigger limits are reduced to it
+    pub const MAX_LIMIT: u32 = 500;
+
     pub const fn default_limit() -> u32 {
         50
     }
@@ -31,7 +38,7 @@ impl Page {
 impl From<Page> for entertainarr_domain::prelude::Page {
     fn from(value: Page) -> Self {
         Self {
-            limit: value.limit,
+            limit: value.limit.min(Page::MAX_LIMIT),
             offset: value.offset,
         }
     }
@@ -102,3 +109,30 @@ where
         .map(|cell| cell.parse::<T>().map_err(serde::de::Error::custom))
         .collect()
 }
+
+/// Escapes a value to be written as XML text or attribute
+pub fn escape_xml(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn should_clamp_page_limit() {
+        let page: super::Page = serde_qs::from_str("limit=4294967295&offset=10").unwrap();
+        let page = entertainarr_domain::prelude::Page::from(page);
+        assert_eq!(page.limit, super::Page::MAX_LIMIT);
+        assert_eq!(page.offset, 10);
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashSet;
+
+use axum::Json;
+use axum::extract::State;
+use serde_qs::axum::QsQuery;
+
+use crate::entity::podcast::PodcastDocument;
+use crate::entity::podcast_episode::{
+    PodcastEpisodeDocument, PodcastEpisodeInclude, PodcastEpisodeRelation,
+};
+use crate::entity::search::SearchDocument;
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentSession;
+use crate::server::handler::prelude::from_comma_separated;
+use entertainarr_domain::podcast::prelude::PodcastService;
+use entertainarr_domain::search::prelude::{SearchParams, SearchService};
+
+const DEFAULT_LIMIT: u32 = 20;
+const MAX_LIMIT: u32 = 100;
+
+const fn default_limit() -> u32 {
+    DEFAULT_LIMIT
+}
+
+#[derive(serde::Deserialize)]
+pub struct QueryParams {
+    q: String,
+    #[serde(default, deserialize_with = "from_comma_separated")]
+    include: HashSet<PodcastEpisodeInclude>,
+    /// Maximum number of podcasts and of episodes
+    #[serde(default = "default_limit")]
+    limit: u32,
+}
+
+/// Returns the matching podcasts followed by the matching episodes, both ranked by relevance
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentSession(principal): CurrentSession,
+    QsQuery(params): QsQuery<QueryParams>,
+) -> Result<Json<ApiResource<Vec<SearchDocument>, PodcastEpisodeRelation>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let user_id = principal.user_id;
+    let result = state
+        .search_service()
+        .search(SearchParams {
+            user_id,
+            query: params.q,
+            limit: params.limit.clamp(1, MAX_LIMIT),
+        })
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to search");
+            ApiError::internal()
+        })?;
+
+    let mut includes: Vec<PodcastEpisodeRelation> = Vec::new();
+
+    if params.include.contains(&PodcastEpisodeInclude::Podcast) {
+        let podcast_ids = result
+            .podcast_episodes
+            .iter()
+            .map(|p| p.podcast_id)
+            .collect::<Vec<_>>();
+        let podcasts = state
+            .podcast_service()
+            .list_by_ids(&podcast_ids)
+            .await
+            .map_err(|err| {
+                tracing::error!(error = ?err, "unable to list podcasts");
+                ApiError::internal()
+            })?;
+        includes.extend(
+            podcasts
+                .into_iter()
+                .map(PodcastDocument::from)
+                .map(PodcastEpisodeRelation::Podcast),
+        );
+    }
+
+    let mut podcast_episodes = result
+        .podcast_episodes
+        .into_iter()
+        .map(PodcastEpisodeDocument::from)
+        .collect::<Vec<_>>();
+    super::podcast_episode::sign_media_urls(&state, &principal, &mut podcast_episodes).await?;
+
+    let data = result
+        .podcasts
+        .into_iter()
+        .map(PodcastDocument::from)
+        .map(SearchDocument::Podcast)
+        .chain(
+            podcast_episodes
+                .into_iter()
+                .map(SearchDocument::PodcastEpisode),
+        )
+        .collect::<Vec<_>>();
+
+    Ok(Json(ApiResource {
+        data,
+        includes,
+        meta: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::State;
+    use chrono::Utc;
+    use entertainarr_domain::podcast::entity::{Podcast, PodcastEpisode};
+    use entertainarr_domain::podcast::prelude::MockPodcastService;
+    use entertainarr_domain::search::{entity::SearchResult, prelude::MockSearchService};
+    use serde_qs::axum::QsQuery;
+
+    use crate::server::extractor::user::CurrentSession;
+    use crate::server::prelude::tests::{MockServerState, principal};
+
+    fn podcast(id: u64) -> Podcast {
+        Podcast {
+            id,
+            feed_url: format!("https://example.com/{id}.rss"),
+            title: "Rustacean Station".into(),
+            description: None,
+            image_url: None,
+            language: None,
+            website: None,
+            author: None,
+            categories: Vec::new(),
+            explicit: None,
+            persons: Vec::new(),
+            funding: Vec::new(),
+            last_synced_at: None,
+            last_sync_error: None,
+            feed_cache: Default::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn podcast_episode(id: u64, podcast_id: u64) -> PodcastEpisode {
+        PodcastEpisode {
+            id,
+            podcast_id,
+            guid: None,
+            published_at: None,
+            title: "Async Rust".into(),
+            description: None,
+            link: None,
+            duration: None,
+            file_url: format!("https://example.com/{id}.mp3"),
+            file_size: None,
+            file_type: None,
+            image_url: None,
+            season_number: None,
+            episode_number: None,
+            episode_type: None,
+            chapters_url: None,
+            transcript: None,
+            persons: Vec::new(),
+            removed_at: None,
+            downloaded: false,
+            progress: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn should_deserialize_params() {
+        let params: super::QueryParams = serde_qs::from_str("q=rust&include=podcast").unwrap();
+        assert_eq!(params.q, "rust");
+        assert_eq!(params.include.len(), 1);
+        assert_eq!(params.limit, super::DEFAULT_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn should_return_podcasts_then_episodes() {
+        let mut search_service = MockSearchService::new();
+        search_service.expect_search().return_once(|params| {
+            assert_eq!(params.user_id, 1);
+            assert_eq!(params.query, "rust");
+            assert_eq!(params.limit, super::MAX_LIMIT);
+            Box::pin(async move {
+                Ok(SearchResult {
+                    podcasts: vec![podcast(1)],
+                    podcast_episodes: vec![podcast_episode(10, 2), podcast_episode(11, 2)],
+                })
+            })
+        });
+        let mut podcast_service = MockPodcastService::new();
+        podcast_service.expect_list_by_ids().return_once(|ids| {
+            assert_eq!(ids, &[2, 2]);
+            Box::pin(async move { Ok(vec![podcast(2)]) })
+        });
+        let state = MockServerState::builder()
+            .podcast(podcast_service)
+            .search(search_service)
+            .build();
+        let params = serde_qs::from_str("q=rust&include=podcast&limit=1000").unwrap();
+        let res = super::handle(State(state), CurrentSession(principal()), QsQuery(params))
+            .await
+            .unwrap();
+        assert_eq!(res.data.len(), 3);
+        assert_eq!(res.data[0].clone().into_podcast().unwrap().id, 1);
+        assert_eq!(res.data[1].clone().into_podcast_episode().unwrap().id, 10);
+        assert_eq!(res.includes.len(), 1);
+    }
+}
@@ -0,0 +1,112 @@
+use axum::Json;
+use axum::extract::State;
+use entertainarr_domain::auth::prelude::AuthenticationService;
+
+use crate::entity::auth::SessionDocument;
+use crate::entity::{ApiError, ApiResource};
+use crate::server::extractor::user::CurrentSession;
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentSession(principal): CurrentSession,
+) -> Result<Json<ApiResource<Vec<SessionDocument>>>, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    let list = state
+        .authentication_service()
+        .list_sessions(principal.user_id)
+        .await
+        .map_err(|err| {
+            tracing::error!(error = ?err, "unable to list user sessions");
+            ApiError::internal()
+        })?;
+    Ok(Json(ApiResource::new(
+        list.into_iter()
+            .map(|session| {
+                let current = session.id == principal.session_id;
+                SessionDocument::from_session(session, current)
+            })
+            .collect(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::State;
+    use chrono::Utc;
+
+    use crate::server::{extractor::user::CurrentSession, prelude::tests::MockServerState};
+    use entertainarr_domain::auth::{
+        entity::{DeviceInfo, Principal, Session},
+        prelude::MockAuthenticationService,
+    };
+
+    fn session(id: u64, name: &str) -> Session {
+        Session {
+            id,
+            user_id: 1,
+            expires_at: Utc::now(),
+            revoked_at: None,
+            device: DeviceInfo {
+                name: Some(name.into()),
+                user_agent: Some("Mozilla/5.0".into()),
+                ip_address: Some("192.168.1.12".into()),
+            },
+            last_seen_at: Some(Utc::now()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_flag_current_session() {
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service.expect_list_sessions().returning(|user_id| {
+            assert_eq!(user_id, 1);
+            Box::pin(async move { Ok(vec![session(2, "Laptop"), session(3, "TV")]) })
+        });
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let principal = Principal {
+            user_id: 1,
+            session_id: 2,
+        };
+        let res = super::handle(State(state), CurrentSession(principal))
+            .await
+            .unwrap();
+        assert_eq!(res.data.len(), 2);
+        assert!(res.data[0].attributes.current);
+        assert_eq!(
+            res.data[0].attributes.device_name.as_deref(),
+            Some("Laptop")
+        );
+        assert!(!res.data[1].attributes.current);
+    }
+}
+
+#[cfg(test)]
+mod integration {
+    use tower::ServiceExt;
+
+    use crate::server::prelude::tests::MockServerState;
+
+    #[tokio::test]
+    async fn should_fail_if_anonymous() {
+        let router = crate::server::handler::create();
+        let state = MockServerState::builder().build();
+        let res = router
+            .with_state(state)
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/users/me/sessions")
+                    .method(axum::http::Method::GET)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::UNAUTHORIZED);
+    }
+}
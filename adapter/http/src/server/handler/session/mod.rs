@@ -0,0 +1,37 @@
+use axum::routing::{delete, get};
+
+use crate::entity::auth::{SessionAttributes, SessionDocument};
+
+pub mod list;
+pub mod revoke;
+
+pub fn create<S>() -> axum::Router<S>
+where
+    S: crate::server::prelude::ServerState + Clone,
+{
+    axum::Router::new()
+        .route("/users/me/sessions", get(list::handle::<S>))
+        .route(
+            "/users/me/sessions/{session_id}",
+            delete(revoke::handle::<S>),
+        )
+}
+
+impl SessionDocument {
+    fn from_session(value: entertainarr_domain::auth::entity::Session, current: bool) -> Self {
+        Self {
+            id: value.id,
+            kind: Default::default(),
+            attributes: SessionAttributes {
+                device_name: value.device.name,
+                user_agent: value.device.user_agent,
+                ip_address: value.device.ip_address,
+                last_seen_at: value.last_seen_at,
+                current,
+                expires_at: value.expires_at,
+                created_at: value.created_at,
+                updated_at: value.updated_at,
+            },
+        }
+    }
+}
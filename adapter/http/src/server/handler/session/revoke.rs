@@ -0,0 +1,86 @@
+use axum::extract::{Path, State};
+use entertainarr_domain::auth::prelude::AuthenticationService;
+
+use crate::{entity::ApiError, server::extractor::user::CurrentUser};
+
+pub async fn handle<S>(
+    State(state): State<S>,
+    CurrentUser(user_id): CurrentUser,
+    Path(session_id): Path<u64>,
+) -> Result<axum::http::StatusCode, ApiError>
+where
+    S: crate::server::prelude::ServerState,
+{
+    match state
+        .authentication_service()
+        .revoke_session(user_id, session_id)
+        .await
+    {
+        Ok(true) => Ok(axum::http::StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::not_found("session not found")),
+        Err(err) => {
+            tracing::error!(error = ?err, "unable to revoke session");
+            Err(ApiError::internal())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+    };
+
+    use crate::server::{extractor::user::CurrentUser, prelude::tests::MockServerState};
+    use entertainarr_domain::auth::prelude::MockAuthenticationService;
+
+    #[tokio::test]
+    async fn should_succeed() {
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service
+            .expect_revoke_session()
+            .return_once(|user_id, session_id| {
+                assert_eq!(user_id, 1);
+                assert_eq!(session_id, 2);
+                Box::pin(async { Ok(true) })
+            });
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let res = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap();
+        assert_eq!(res, StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_not_found() {
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service
+            .expect_revoke_session()
+            .return_once(|_, _| Box::pin(async { Ok(false) }));
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let err = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_service_fails() {
+        let mut auth_service = MockAuthenticationService::new();
+        auth_service
+            .expect_revoke_session()
+            .return_once(|_, _| Box::pin(async { Err(anyhow::anyhow!("oops")) }));
+        let state = MockServerState::builder()
+            .authentication(auth_service)
+            .build();
+        let err = super::handle(State(state), CurrentUser(1), Path(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
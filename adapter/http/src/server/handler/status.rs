@@ -22,6 +22,8 @@ mod integration {
     #[tokio::test]
     async fn should_answer() {
         let state = ServerState {
+            media_proxy: Default::default(),
+            trusted_proxies: Arc::new([]),
             authentication_service: Arc::new(
                 entertainarr_domain::auth::prelude::MockAuthenticationService::new(),
             ),
@@ -29,9 +31,18 @@ mod integration {
             podcast_service: Arc::new(
                 entertainarr_domain::podcast::prelude::MockPodcastService::new(),
             ),
+            podcast_download_service: Arc::new(
+                entertainarr_domain::podcast::prelude::MockPodcastDownloadService::new(),
+            ),
             podcast_episode_service: Arc::new(
                 entertainarr_domain::podcast::prelude::MockPodcastEpisodeService::new(),
             ),
+            podcast_queue_service: Arc::new(
+                entertainarr_domain::podcast::prelude::MockPodcastQueueService::new(),
+            ),
+            search_service: Arc::new(
+                entertainarr_domain::search::prelude::MockSearchService::new(),
+            ),
         };
         let router = crate::server::handler::create().with_state(state);
         let res = router
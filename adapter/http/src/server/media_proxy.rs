@@ -0,0 +1,96 @@
+//! Client streaming the episode files from their host. The urls come from feeds any user can
+//! subscribe to, so unless allowed, the loopback, link-local and private addresses are refused,
+//! whether the url points at them directly, through a domain name or through a redirection.
+
+use std::time::Duration;
+
+use anyhow::Context;
+use entertainarr_adapter_public_http::ForbiddenHost;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_REDIRECTS: usize = 5;
+
+#[derive(Clone, Debug)]
+pub struct MediaProxy {
+    client: reqwest::Client,
+    allow_private_hosts: bool,
+}
+
+impl Default for MediaProxy {
+    fn default() -> Self {
+        Self::new(false).expect("unable to build media proxy")
+    }
+}
+
+impl MediaProxy {
+    pub fn new(allow_private_hosts: bool) -> anyhow::Result<Self> {
+        let builder = reqwest::Client::builder()
+            .user_agent(super::USER_AGENT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .redirect(entertainarr_adapter_public_http::redirect_policy(
+                MAX_REDIRECTS,
+                allow_private_hosts,
+            ));
+        let client = entertainarr_adapter_public_http::restrict(builder, allow_private_hosts)
+            .build()
+            .context("unable to build http client")?;
+        Ok(Self {
+            client,
+            allow_private_hosts,
+        })
+    }
+
+    /// Prepares a request to the url, the domain names being checked once resolved
+    pub fn get(&self, url: &str) -> Result<reqwest::RequestBuilder, ForbiddenHost> {
+        let url = entertainarr_adapter_public_http::check_url(url, self.allow_private_hosts)?;
+        Ok(self.client.get(url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn should_refuse_private_urls() {
+        let proxy = super::MediaProxy::new(false).unwrap();
+        assert!(proxy.get("https://example.com/episode.mp3").is_ok());
+        assert!(proxy.get("http://169.254.169.254/latest").is_err());
+        assert!(proxy.get("http://[::1]:3000/").is_err());
+        assert!(proxy.get("file:///etc/passwd").is_err());
+
+        let proxy = super::MediaProxy::new(true).unwrap();
+        assert!(proxy.get("http://127.0.0.1:3000/").is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_refuse_names_resolving_to_private_addresses() {
+        let proxy = super::MediaProxy::new(false).unwrap();
+        let err = proxy
+            .get("http://localhost:1/episode.mp3")
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.is_connect(), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn should_refuse_redirects_to_private_addresses() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/episode.mp3"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header("location", "http://169.254.169.254/latest"),
+            )
+            .mount(&server)
+            .await;
+        // the inner client skips the check of the first url, leaving only the redirection
+        let proxy = super::MediaProxy::new(false).unwrap();
+        let url = format!("{}/episode.mp3", server.uri());
+        let err = proxy.client.get(&url).send().await.unwrap_err();
+        assert!(err.is_redirect(), "{err:?}");
+    }
+}
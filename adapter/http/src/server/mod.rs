@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::Context;
 
 // used for publishing frontend;
@@ -5,6 +7,7 @@ pub use handler::client::prelude::ClientService;
 
 mod extractor;
 mod handler;
+mod media_proxy;
 mod middleware;
 mod prelude;
 
@@ -15,8 +18,18 @@ pub struct Config {
     pub address: std::net::IpAddr,
     #[serde(default = "Config::default_port")]
     pub port: u16,
+    /// Lets the episode files be streamed from loopback, link-local and private addresses,
+    /// for podcasts hosted on the local network
+    #[serde(default)]
+    pub allow_private_media_hosts: bool,
+    /// Addresses of the reverse proxies allowed to give the client address with the
+    /// `X-Forwarded-For` header, the header being ignored when coming from any other peer
+    #[serde(default)]
+    pub trusted_proxies: Vec<std::net::IpAddr>,
 }
 
+const USER_AGENT: &str = concat!("entertainarr/", env!("CARGO_PKG_VERSION"));
+
 const DEFAULT_ADDRESS: std::net::IpAddr = std::net::IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED);
 
 impl Default for Config {
@@ -24,6 +37,8 @@ impl Default for Config {
         Self {
             address: Self::default_address(),
             port: Self::default_port(),
+            allow_private_media_hosts: false,
+            trusted_proxies: Vec::new(),
         }
     }
 }
@@ -37,100 +52,210 @@ impl Config {
         3000
     }
 
-    pub fn builder(self) -> anyhow::Result<HttpServerBuilder<(), (), (), ()>> {
+    pub fn builder(self) -> anyhow::Result<HttpServerBuilder<(), (), (), (), (), (), ()>> {
+        let media_proxy = media_proxy::MediaProxy::new(self.allow_private_media_hosts)
+            .context("unable to build media proxy")?;
         Ok(HttpServerBuilder {
             socket_address: std::net::SocketAddr::from((self.address, self.port)),
+            media_proxy,
+            trusted_proxies: self.trusted_proxies.into(),
             authentication_service: (),
             client_service: (),
             podcast_service: (),
+            podcast_download_service: (),
             podcast_episode_service: (),
+            podcast_queue_service: (),
+            search_service: (),
         })
     }
 }
 
-pub struct HttpServerBuilder<AS, CS, PS, PES> {
+pub struct HttpServerBuilder<AS, CS, PS, PDS, PES, PQS, SS> {
     socket_address: std::net::SocketAddr,
+    /// Used to proxy the episode files
+    media_proxy: media_proxy::MediaProxy,
+    trusted_proxies: Arc<[std::net::IpAddr]>,
     authentication_service: AS,
     client_service: CS,
     podcast_service: PS,
+    podcast_download_service: PDS,
     podcast_episode_service: PES,
+    podcast_queue_service: PQS,
+    search_service: SS,
 }
 
-impl<AS, CS, PS, PES> HttpServerBuilder<AS, CS, PS, PES> {
+impl<AS, CS, PS, PDS, PES, PQS, SS> HttpServerBuilder<AS, CS, PS, PDS, PES, PQS, SS> {
     pub fn with_authentication_service<AS2>(
         self,
         service: AS2,
-    ) -> HttpServerBuilder<AS2, CS, PS, PES>
+    ) -> HttpServerBuilder<AS2, CS, PS, PDS, PES, PQS, SS>
     where
         AS2: entertainarr_domain::auth::prelude::AuthenticationService,
     {
         HttpServerBuilder {
             socket_address: self.socket_address,
+            media_proxy: self.media_proxy,
+            trusted_proxies: self.trusted_proxies,
             authentication_service: service,
             client_service: self.client_service,
             podcast_service: self.podcast_service,
+            podcast_download_service: self.podcast_download_service,
             podcast_episode_service: self.podcast_episode_service,
+            podcast_queue_service: self.podcast_queue_service,
+            search_service: self.search_service,
         }
     }
 
-    pub fn with_client_service<CS2>(self, service: CS2) -> HttpServerBuilder<AS, CS2, PS, PES>
+    pub fn with_client_service<CS2>(
+        self,
+        service: CS2,
+    ) -> HttpServerBuilder<AS, CS2, PS, PDS, PES, PQS, SS>
     where
         CS2: crate::server::handler::client::prelude::ClientService,
     {
         HttpServerBuilder {
             socket_address: self.socket_address,
+            media_proxy: self.media_proxy,
+            trusted_proxies: self.trusted_proxies,
             authentication_service: self.authentication_service,
             client_service: service,
             podcast_service: self.podcast_service,
+            podcast_download_service: self.podcast_download_service,
             podcast_episode_service: self.podcast_episode_service,
+            podcast_queue_service: self.podcast_queue_service,
+            search_service: self.search_service,
         }
     }
 
-    pub fn with_podcast_service<PS2>(self, service: PS2) -> HttpServerBuilder<AS, CS, PS2, PES>
+    pub fn with_podcast_service<PS2>(
+        self,
+        service: PS2,
+    ) -> HttpServerBuilder<AS, CS, PS2, PDS, PES, PQS, SS>
     where
         PS2: entertainarr_domain::podcast::prelude::PodcastService,
     {
         HttpServerBuilder {
             socket_address: self.socket_address,
+            media_proxy: self.media_proxy,
+            trusted_proxies: self.trusted_proxies,
             authentication_service: self.authentication_service,
             client_service: self.client_service,
             podcast_service: service,
+            podcast_download_service: self.podcast_download_service,
             podcast_episode_service: self.podcast_episode_service,
+            podcast_queue_service: self.podcast_queue_service,
+            search_service: self.search_service,
+        }
+    }
+
+    pub fn with_podcast_download_service<PDS2>(
+        self,
+        service: PDS2,
+    ) -> HttpServerBuilder<AS, CS, PS, PDS2, PES, PQS, SS>
+    where
+        PDS2: entertainarr_domain::podcast::prelude::PodcastDownloadService,
+    {
+        HttpServerBuilder {
+            socket_address: self.socket_address,
+            media_proxy: self.media_proxy,
+            trusted_proxies: self.trusted_proxies,
+            authentication_service: self.authentication_service,
+            client_service: self.client_service,
+            podcast_service: self.podcast_service,
+            podcast_download_service: service,
+            podcast_episode_service: self.podcast_episode_service,
+            podcast_queue_service: self.podcast_queue_service,
+            search_service: self.search_service,
         }
     }
 
     pub fn with_podcast_episode_service<PES2>(
         self,
         service: PES2,
-    ) -> HttpServerBuilder<AS, CS, PS, PES2>
+    ) -> HttpServerBuilder<AS, CS, PS, PDS, PES2, PQS, SS>
     where
         PES2: entertainarr_domain::podcast::prelude::PodcastEpisodeService,
     {
         HttpServerBuilder {
             socket_address: self.socket_address,
+            media_proxy: self.media_proxy,
+            trusted_proxies: self.trusted_proxies,
             authentication_service: self.authentication_service,
             client_service: self.client_service,
             podcast_service: self.podcast_service,
+            podcast_download_service: self.podcast_download_service,
             podcast_episode_service: service,
+            podcast_queue_service: self.podcast_queue_service,
+            search_service: self.search_service,
+        }
+    }
+
+    pub fn with_podcast_queue_service<PQS2>(
+        self,
+        service: PQS2,
+    ) -> HttpServerBuilder<AS, CS, PS, PDS, PES, PQS2, SS>
+    where
+        PQS2: entertainarr_domain::podcast::prelude::PodcastQueueService,
+    {
+        HttpServerBuilder {
+            socket_address: self.socket_address,
+            media_proxy: self.media_proxy,
+            trusted_proxies: self.trusted_proxies,
+            authentication_service: self.authentication_service,
+            client_service: self.client_service,
+            podcast_service: self.podcast_service,
+            podcast_download_service: self.podcast_download_service,
+            podcast_episode_service: self.podcast_episode_service,
+            podcast_queue_service: service,
+            search_service: self.search_service,
+        }
+    }
+
+    pub fn with_search_service<SS2>(
+        self,
+        service: SS2,
+    ) -> HttpServerBuilder<AS, CS, PS, PDS, PES, PQS, SS2>
+    where
+        SS2: entertainarr_domain::search::prelude::SearchService,
+    {
+        HttpServerBuilder {
+            socket_address: self.socket_address,
+            media_proxy: self.media_proxy,
+            trusted_proxies: self.trusted_proxies,
+            authentication_service: self.authentication_service,
+            client_service: self.client_service,
+            podcast_service: self.podcast_service,
+            podcast_download_service: self.podcast_download_service,
+            podcast_episode_service: self.podcast_episode_service,
+            podcast_queue_service: self.podcast_queue_service,
+            search_service: service,
         }
     }
 }
 
-impl<AS, CS, PS, PES> HttpServerBuilder<AS, CS, PS, PES>
+impl<AS, CS, PS, PDS, PES, PQS, SS> HttpServerBuilder<AS, CS, PS, PDS, PES, PQS, SS>
 where
     AS: entertainarr_domain::auth::prelude::AuthenticationService + Clone,
     CS: crate::server::handler::client::prelude::ClientService + Clone,
     PS: entertainarr_domain::podcast::prelude::PodcastService + Clone,
+    PDS: entertainarr_domain::podcast::prelude::PodcastDownloadService + Clone,
     PES: entertainarr_domain::podcast::prelude::PodcastEpisodeService + Clone,
+    PQS: entertainarr_domain::podcast::prelude::PodcastQueueService + Clone,
+    SS: entertainarr_domain::search::prelude::SearchService + Clone,
 {
     pub fn router(self) -> axum::Router {
         let state = ServerState {
+            media_proxy: self.media_proxy,
+            trusted_proxies: self.trusted_proxies,
             authentication_service: self.authentication_service,
             client_service: self.client_service,
             podcast_service: self.podcast_service,
+            podcast_download_service: self.podcast_download_service,
             podcast_episode_service: self.podcast_episode_service,
+            podcast_queue_service: self.podcast_queue_service,
+            search_service: self.search_service,
         };
-        handler::create::<ServerState<AS, CS, PS, PES>>()
+        handler::create::<ServerState<AS, CS, PS, PDS, PES, PQS, SS>>()
             .layer(middleware::tracing::layer())
             .with_state(state)
     }
@@ -147,20 +272,37 @@ where
 }
 
 #[derive(Clone, Debug)]
-pub struct ServerState<AS, CS, PS, PES> {
+pub struct ServerState<AS, CS, PS, PDS, PES, PQS, SS> {
+    media_proxy: media_proxy::MediaProxy,
+    trusted_proxies: Arc<[std::net::IpAddr]>,
     authentication_service: AS,
     client_service: CS,
     podcast_service: PS,
+    podcast_download_service: PDS,
     podcast_episode_service: PES,
+    podcast_queue_service: PQS,
+    search_service: SS,
 }
 
-impl<AS, CS, PS, PES> prelude::ServerState for ServerState<AS, CS, PS, PES>
+impl<AS, CS, PS, PDS, PES, PQS, SS> prelude::ServerState
+    for ServerState<AS, CS, PS, PDS, PES, PQS, SS>
 where
     AS: entertainarr_domain::auth::prelude::AuthenticationService,
     CS: crate::server::handler::client::prelude::ClientService,
     PS: entertainarr_domain::podcast::prelude::PodcastService,
+    PDS: entertainarr_domain::podcast::prelude::PodcastDownloadService,
     PES: entertainarr_domain::podcast::prelude::PodcastEpisodeService,
+    PQS: entertainarr_domain::podcast::prelude::PodcastQueueService,
+    SS: entertainarr_domain::search::prelude::SearchService,
 {
+    fn media_proxy(&self) -> &media_proxy::MediaProxy {
+        &self.media_proxy
+    }
+
+    fn trusted_proxies(&self) -> &[std::net::IpAddr] {
+        &self.trusted_proxies
+    }
+
     fn authentication_service(
         &self,
     ) -> &impl entertainarr_domain::auth::prelude::AuthenticationService {
@@ -175,11 +317,27 @@ where
         &self.podcast_service
     }
 
+    fn podcast_download_service(
+        &self,
+    ) -> &impl entertainarr_domain::podcast::prelude::PodcastDownloadService {
+        &self.podcast_download_service
+    }
+
     fn podcast_episode_service(
         &self,
     ) -> &impl entertainarr_domain::podcast::prelude::PodcastEpisodeService {
         &self.podcast_episode_service
     }
+
+    fn podcast_queue_service(
+        &self,
+    ) -> &impl entertainarr_domain::podcast::prelude::PodcastQueueService {
+        &self.podcast_queue_service
+    }
+
+    fn search_service(&self) -> &impl entertainarr_domain::search::prelude::SearchService {
+        &self.search_service
+    }
 }
 
 pub struct HttpServer {
@@ -193,8 +351,12 @@ impl HttpServer {
             .await
             .context("unable to bind socket")?;
         tracing::info!(address = ?self.socket_address, "starting server");
-        axum::serve(listener, self.router)
-            .await
-            .context("server shutdown")
+        axum::serve(
+            listener,
+            self.router
+                .into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .await
+        .context("server shutdown")
     }
 }
@@ -1,13 +1,22 @@
 use entertainarr_domain::auth::prelude::AuthenticationService;
-use entertainarr_domain::podcast::prelude::{PodcastEpisodeService, PodcastService};
+use entertainarr_domain::podcast::prelude::{
+    PodcastDownloadService, PodcastEpisodeService, PodcastQueueService, PodcastService,
+};
+use entertainarr_domain::search::prelude::SearchService;
 
 use crate::server::handler::client::prelude::ClientService;
 
 pub trait ServerState: Send + Sync + 'static {
+    fn media_proxy(&self) -> &super::media_proxy::MediaProxy;
+    /// Peers allowed to give the client address with the `X-Forwarded-For` header
+    fn trusted_proxies(&self) -> &[std::net::IpAddr];
     fn authentication_service(&self) -> &impl AuthenticationService;
     fn client_service(&self) -> &impl ClientService;
     fn podcast_service(&self) -> &impl PodcastService;
+    fn podcast_download_service(&self) -> &impl PodcastDownloadService;
     fn podcast_episode_service(&self) -> &impl PodcastEpisodeService;
+    fn podcast_queue_service(&self) -> &impl PodcastQueueService;
+    fn search_service(&self) -> &impl SearchService;
 }
 
 #[cfg(test)]
@@ -15,25 +24,47 @@ pub mod tests {
     use std::sync::Arc;
 
     use entertainarr_domain::auth::prelude::AuthenticationService;
-    use entertainarr_domain::podcast::prelude::{PodcastEpisodeService, PodcastService};
+    use entertainarr_domain::podcast::prelude::{
+        PodcastDownloadService, PodcastEpisodeService, PodcastQueueService, PodcastService,
+    };
+    use entertainarr_domain::search::prelude::SearchService;
 
     use crate::server::handler::client::prelude::{ClientService, MockClientService};
 
+    /// Session of the user calling the handlers
+    pub fn principal() -> entertainarr_domain::auth::entity::Principal {
+        entertainarr_domain::auth::entity::Principal {
+            user_id: 1,
+            session_id: 2,
+        }
+    }
+
     #[derive(Default)]
     pub struct MockServerStateBuilder {
         pub authentication: Option<entertainarr_domain::auth::prelude::MockAuthenticationService>,
         pub podcast: Option<entertainarr_domain::podcast::prelude::MockPodcastService>,
+        pub podcast_download:
+            Option<entertainarr_domain::podcast::prelude::MockPodcastDownloadService>,
         pub podcast_episode:
             Option<entertainarr_domain::podcast::prelude::MockPodcastEpisodeService>,
+        pub podcast_queue: Option<entertainarr_domain::podcast::prelude::MockPodcastQueueService>,
+        pub search: Option<entertainarr_domain::search::prelude::MockSearchService>,
+        pub trusted_proxies: Vec<std::net::IpAddr>,
     }
 
     impl MockServerStateBuilder {
         pub fn build(self) -> MockServerState {
             MockServerState {
+                // the tests serve the episode files from the loopback
+                media_proxy: crate::server::media_proxy::MediaProxy::new(true).unwrap(),
+                trusted_proxies: self.trusted_proxies,
                 authentication: Arc::new(self.authentication.unwrap_or_default()),
                 client: MockClientService,
                 podcast: Arc::new(self.podcast.unwrap_or_default()),
+                podcast_download: Arc::new(self.podcast_download.unwrap_or_default()),
                 podcast_episode: Arc::new(self.podcast_episode.unwrap_or_default()),
+                podcast_queue: Arc::new(self.podcast_queue.unwrap_or_default()),
+                search: Arc::new(self.search.unwrap_or_default()),
             }
         }
 
@@ -52,14 +83,57 @@ pub mod tests {
             self.podcast = Some(item);
             self
         }
+
+        pub fn podcast_download(
+            mut self,
+            item: entertainarr_domain::podcast::prelude::MockPodcastDownloadService,
+        ) -> Self {
+            self.podcast_download = Some(item);
+            self
+        }
+
+        pub fn podcast_episode(
+            mut self,
+            item: entertainarr_domain::podcast::prelude::MockPodcastEpisodeService,
+        ) -> Self {
+            self.podcast_episode = Some(item);
+            self
+        }
+
+        pub fn podcast_queue(
+            mut self,
+            item: entertainarr_domain::podcast::prelude::MockPodcastQueueService,
+        ) -> Self {
+            self.podcast_queue = Some(item);
+            self
+        }
+
+        pub fn search(
+            mut self,
+            item: entertainarr_domain::search::prelude::MockSearchService,
+        ) -> Self {
+            self.search = Some(item);
+            self
+        }
+
+        pub fn trusted_proxies(mut self, items: Vec<std::net::IpAddr>) -> Self {
+            self.trusted_proxies = items;
+            self
+        }
     }
 
     #[derive(Clone, Default)]
     pub struct MockServerState {
+        pub media_proxy: crate::server::media_proxy::MediaProxy,
+        pub trusted_proxies: Vec<std::net::IpAddr>,
         pub authentication: Arc<entertainarr_domain::auth::prelude::MockAuthenticationService>,
         pub client: MockClientService,
         pub podcast: Arc<entertainarr_domain::podcast::prelude::MockPodcastService>,
+        pub podcast_download:
+            Arc<entertainarr_domain::podcast::prelude::MockPodcastDownloadService>,
         pub podcast_episode: Arc<entertainarr_domain::podcast::prelude::MockPodcastEpisodeService>,
+        pub podcast_queue: Arc<entertainarr_domain::podcast::prelude::MockPodcastQueueService>,
+        pub search: Arc<entertainarr_domain::search::prelude::MockSearchService>,
     }
 
     impl MockServerState {
@@ -69,6 +143,14 @@ pub mod tests {
     }
 
     impl super::ServerState for MockServerState {
+        fn media_proxy(&self) -> &crate::server::media_proxy::MediaProxy {
+            &self.media_proxy
+        }
+
+        fn trusted_proxies(&self) -> &[std::net::IpAddr] {
+            &self.trusted_proxies
+        }
+
         fn authentication_service(&self) -> &impl AuthenticationService {
             &self.authentication
         }
@@ -81,8 +163,20 @@ pub mod tests {
             &self.podcast
         }
 
+        fn podcast_download_service(&self) -> &impl PodcastDownloadService {
+            &self.podcast_download
+        }
+
         fn podcast_episode_service(&self) -> &impl PodcastEpisodeService {
             &self.podcast_episode
         }
+
+        fn podcast_queue_service(&self) -> &impl PodcastQueueService {
+            &self.podcast_queue
+        }
+
+        fn search_service(&self) -> &impl SearchService {
+            &self.search
+        }
     }
 }
@@ -1,9 +1,12 @@
 use anyhow::Context;
 use jsonwebtoken::TokenData;
 
-use entertainarr_domain::auth::entity::Profile;
+use entertainarr_domain::auth::entity::{MediaGrant, Principal};
 use entertainarr_domain::auth::prelude::VerifyError;
 
+/// Audience of the tokens put in the url of the episode files, access tokens having none
+pub(crate) const MEDIA_AUDIENCE: &str = "media";
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct Claims {
     // aud: String, // Optional. Audience
@@ -12,34 +15,81 @@ struct Claims {
     // iss: String, // Optional. Issuer
     // nbf: usize, // Optional. Not Before (as UTC timestamp)
     sub: u64, // Optional. Subject (whom token refers to)
+    sid: u64, // Session the token has been issued for
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct MediaClaims {
+    aud: String,
+    exp: u64,
+    iat: u64,
+    sub: u64,
+    sid: u64, // Session the token has been issued for
+    eid: u64, // Episode the file belongs to
+}
+
+fn verify_error(err: jsonwebtoken::errors::Error) -> VerifyError {
+    match err.kind() {
+        jsonwebtoken::errors::ErrorKind::ExpiredSignature => VerifyError::ExpiredToken,
+        // a token of the other kind, with a valid signature, misses some claims
+        jsonwebtoken::errors::ErrorKind::InvalidToken
+        | jsonwebtoken::errors::ErrorKind::Json(_)
+        | jsonwebtoken::errors::ErrorKind::InvalidAudience
+        | jsonwebtoken::errors::ErrorKind::MissingRequiredClaim(_) => VerifyError::InvalidToken,
+        _ => VerifyError::Internal(err.into()),
+    }
 }
 
 impl entertainarr_domain::auth::prelude::TokenRepository for super::JsonWebToken {
-    #[tracing::instrument(skip_all, fields(user_id = profile.id), err(Debug))]
-    async fn create_token(
-        &self,
-        profile: &entertainarr_domain::auth::entity::Profile,
-    ) -> anyhow::Result<String> {
+    #[tracing::instrument(skip_all, fields(user_id = principal.user_id, session_id = principal.session_id), err(Debug))]
+    async fn create_token(&self, principal: &Principal) -> anyhow::Result<String> {
         let now = chrono::Utc::now();
         let exp = now + self.0.duration;
         let claims = Claims {
             exp: exp.timestamp() as u64,
             iat: now.timestamp() as u64,
-            sub: profile.id,
+            sub: principal.user_id,
+            sid: principal.session_id,
         };
         jsonwebtoken::encode(&self.0.header, &claims, &self.0.encoding)
             .context("unable to create token")
     }
 
     #[tracing::instrument(skip_all, err(Debug))]
-    async fn decode_token(&self, token: &str) -> Result<Profile, VerifyError> {
+    async fn decode_token(&self, token: &str) -> Result<Principal, VerifyError> {
         jsonwebtoken::decode(token, &self.0.decoding, &self.0.validation)
-            .map_err(|err| match err.kind() {
-                jsonwebtoken::errors::ErrorKind::ExpiredSignature => VerifyError::ExpiredToken,
-                jsonwebtoken::errors::ErrorKind::InvalidToken => VerifyError::InvalidToken,
-                _ => VerifyError::Internal(err.into()),
+            .map_err(verify_error)
+            .map(|res: TokenData<Claims>| Principal {
+                user_id: res.claims.sub,
+                session_id: res.claims.sid,
+            })
+    }
+
+    #[tracing::instrument(skip_all, fields(user_id = grant.user_id, session_id = grant.session_id, podcast_episode_id = grant.podcast_episode_id), err(Debug))]
+    async fn create_media_token(&self, grant: &MediaGrant) -> anyhow::Result<String> {
+        let now = chrono::Utc::now();
+        let exp = now + self.0.media_duration;
+        let claims = MediaClaims {
+            aud: MEDIA_AUDIENCE.to_string(),
+            exp: exp.timestamp() as u64,
+            iat: now.timestamp() as u64,
+            sub: grant.user_id,
+            sid: grant.session_id,
+            eid: grant.podcast_episode_id,
+        };
+        jsonwebtoken::encode(&self.0.header, &claims, &self.0.encoding)
+            .context("unable to create media token")
+    }
+
+    #[tracing::instrument(skip_all, err(Debug))]
+    async fn decode_media_token(&self, token: &str) -> Result<MediaGrant, VerifyError> {
+        jsonwebtoken::decode(token, &self.0.decoding, &self.0.media_validation)
+            .map_err(verify_error)
+            .map(|res: TokenData<MediaClaims>| MediaGrant {
+                user_id: res.claims.sub,
+                session_id: res.claims.sid,
+                podcast_episode_id: res.claims.eid,
             })
-            .map(|res: TokenData<Claims>| Profile { id: res.claims.sub })
     }
 }
 
@@ -48,7 +98,7 @@ mod tests {
     use std::borrow::Cow;
 
     use entertainarr_domain::auth::{
-        entity::Profile,
+        entity::{MediaGrant, Principal},
         prelude::{TokenRepository, VerifyError},
     };
 
@@ -57,9 +107,16 @@ mod tests {
         let config = crate::Config {
             secret: Cow::Borrowed("secret"),
             duration: 10,
+            media_duration: 10,
         };
         let client = config.build().unwrap();
-        let _token = client.create_token(&Profile { id: 1 }).await.unwrap();
+        let _token = client
+            .create_token(&Principal {
+                user_id: 1,
+                session_id: 2,
+            })
+            .await
+            .unwrap();
     }
 
     #[tokio::test]
@@ -67,11 +124,19 @@ mod tests {
         let config = crate::Config {
             secret: Cow::Borrowed("secret"),
             duration: 10,
+            media_duration: 10,
         };
         let client = config.build().unwrap();
-        let token = client.create_token(&Profile { id: 1 }).await.unwrap();
-        let profile = client.decode_token(&token).await.unwrap();
-        assert_eq!(profile.id, 1);
+        let token = client
+            .create_token(&Principal {
+                user_id: 1,
+                session_id: 2,
+            })
+            .await
+            .unwrap();
+        let principal = client.decode_token(&token).await.unwrap();
+        assert_eq!(principal.user_id, 1);
+        assert_eq!(principal.session_id, 2);
     }
 
     #[tokio::test]
@@ -79,6 +144,7 @@ mod tests {
         let config = crate::Config {
             secret: Cow::Borrowed("secret"),
             duration: 10,
+            media_duration: 10,
         };
         let client = config.build().unwrap();
         let err = client.decode_token("foo").await.unwrap_err();
@@ -90,6 +156,7 @@ mod tests {
         let config = crate::Config {
             secret: Cow::Borrowed("secret"),
             duration: 10,
+            media_duration: 10,
         };
         let client = config.build().unwrap();
 
@@ -98,10 +165,58 @@ mod tests {
             exp: (now.timestamp() - 1000) as u64,
             iat: (now.timestamp() - 2000) as u64,
             sub: 1,
+            sid: 2,
         };
         let token = jsonwebtoken::encode(&client.0.header, &claims, &client.0.encoding).unwrap();
 
         let err = client.decode_token(&token).await.unwrap_err();
         assert!(matches!(err, VerifyError::ExpiredToken));
     }
+
+    #[tokio::test]
+    async fn should_decode_media_token() {
+        let config = crate::Config {
+            secret: Cow::Borrowed("secret"),
+            duration: 10,
+            media_duration: 10,
+        };
+        let client = config.build().unwrap();
+        let grant = MediaGrant {
+            user_id: 1,
+            session_id: 2,
+            podcast_episode_id: 3,
+        };
+        let token = client.create_media_token(&grant).await.unwrap();
+        assert_eq!(client.decode_media_token(&token).await.unwrap(), grant);
+    }
+
+    #[tokio::test]
+    async fn should_not_mix_access_and_media_tokens() {
+        let config = crate::Config {
+            secret: Cow::Borrowed("secret"),
+            duration: 10,
+            media_duration: 10,
+        };
+        let client = config.build().unwrap();
+        let media_token = client
+            .create_media_token(&MediaGrant {
+                user_id: 1,
+                session_id: 2,
+                podcast_episode_id: 3,
+            })
+            .await
+            .unwrap();
+        let err = client.decode_token(&media_token).await.unwrap_err();
+        assert!(matches!(err, VerifyError::InvalidToken));
+
+        let access_token = client
+            .create_token(&Principal {
+                user_id: 1,
+                session_id: 2,
+            })
+            .await
+            .unwrap();
+        let err = client.decode_media_token(&access_token).await.unwrap_err();
+        assert!(matches!(err, VerifyError::InvalidToken));
+    }
 }
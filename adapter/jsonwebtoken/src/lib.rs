@@ -6,8 +6,12 @@ mod auth;
 pub struct Config {
     #[serde(default = "Config::default_secret")]
     pub secret: Cow<'static, str>,
+    /// Lifetime of an access token, in seconds
     #[serde(default = "Config::default_duration")]
     pub duration: u64,
+    /// Lifetime of a token put in the url of an episode file, in seconds
+    #[serde(default = "Config::default_media_duration")]
+    pub media_duration: u64,
 }
 
 impl Default for Config {
@@ -15,6 +19,7 @@ impl Default for Config {
         Self {
             secret: Self::default_secret(),
             duration: Self::default_duration(),
+            media_duration: Self::default_media_duration(),
         }
     }
 }
@@ -25,7 +30,11 @@ impl Config {
     }
 
     pub const fn default_duration() -> u64 {
-        60 * 60 * 12
+        60 * 15
+    }
+
+    pub const fn default_media_duration() -> u64 {
+        60 * 60 * 24
     }
 
     pub fn build(self) -> anyhow::Result<JsonWebToken> {
@@ -34,11 +43,16 @@ impl Config {
         let encoding = jsonwebtoken::EncodingKey::from_secret(self.secret.as_ref().as_bytes());
         let header = jsonwebtoken::Header::new(algorithm);
         let validation = jsonwebtoken::Validation::new(algorithm);
+        let mut media_validation = jsonwebtoken::Validation::new(algorithm);
+        media_validation.set_audience(&[auth::MEDIA_AUDIENCE]);
+        media_validation.set_required_spec_claims(&["exp", "aud"]);
         Ok(JsonWebToken(Arc::new(Inner {
             decoding,
             duration: std::time::Duration::from_secs(self.duration),
             encoding,
             header,
+            media_duration: std::time::Duration::from_secs(self.media_duration),
+            media_validation,
             validation,
         })))
     }
@@ -49,6 +63,9 @@ struct Inner {
     duration: std::time::Duration,
     encoding: jsonwebtoken::EncodingKey,
     header: jsonwebtoken::Header,
+    media_duration: std::time::Duration,
+    /// Only accepts the tokens issued for the episode files, and the other way around
+    media_validation: jsonwebtoken::Validation,
     validation: jsonwebtoken::Validation,
 }
 
@@ -0,0 +1,347 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use entertainarr_domain::podcast::entity::{PendingPodcastEpisodeDownload, StoredMedia};
+use tokio::io::AsyncWriteExt;
+
+const USER_AGENT: &str = concat!("entertainarr/", env!("CARGO_PKG_VERSION"));
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Applied to each read, so a stalled host doesn't hold the download queue while large files
+/// can still take as long as they need
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_REDIRECTS: usize = 5;
+
+/// Extensions of the usual episode file types
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("audio/aac", "aac"),
+    ("audio/flac", "flac"),
+    ("audio/m4a", "m4a"),
+    ("audio/mp3", "mp3"),
+    ("audio/mp4", "m4a"),
+    ("audio/mpeg", "mp3"),
+    ("audio/ogg", "ogg"),
+    ("audio/opus", "opus"),
+    ("audio/wav", "wav"),
+    ("audio/x-m4a", "m4a"),
+    ("audio/x-wav", "wav"),
+    ("video/mp4", "mp4"),
+    ("video/quicktime", "mov"),
+    ("video/x-m4v", "m4v"),
+];
+
+/// Media library configuration
+#[derive(Debug, serde::Deserialize)]
+pub struct Config {
+    /// Directory where the downloaded episodes are stored
+    #[serde(default = "Config::default_path")]
+    pub path: PathBuf,
+    /// Allows downloading the episodes hosted on the local network
+    #[serde(default)]
+    pub allow_private_hosts: bool,
+    /// Size above which an episode file is not downloaded, in bytes
+    #[serde(default = "Config::default_max_file_size")]
+    pub max_file_size: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+            allow_private_hosts: false,
+            max_file_size: Self::default_max_file_size(),
+        }
+    }
+}
+
+impl Config {
+    pub fn default_path() -> PathBuf {
+        PathBuf::from("media")
+    }
+
+    pub const fn default_max_file_size() -> u64 {
+        // 4GiB
+        4 * 1024 * 1024 * 1024
+    }
+
+    pub fn build(self) -> anyhow::Result<MediaStorage> {
+        let builder = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .read_timeout(READ_TIMEOUT)
+            .redirect(entertainarr_adapter_public_http::redirect_policy(
+                MAX_REDIRECTS,
+                self.allow_private_hosts,
+            ));
+        let client = entertainarr_adapter_public_http::restrict(builder, self.allow_private_hosts)
+            .build()
+            .context("unable to build http client")?;
+        Ok(MediaStorage {
+            client,
+            path: self.path,
+            allow_private_hosts: self.allow_private_hosts,
+            max_file_size: self.max_file_size,
+        })
+    }
+}
+
+/// Finds the extension of the file, from its type or from its url
+fn extension(file_type: Option<&str>, file_url: &str) -> &'static str {
+    let from_type = file_type
+        .map(|value| value.split(';').next().unwrap_or_default().trim())
+        .and_then(|value| {
+            EXTENSIONS
+                .iter()
+                .find(|(mime_type, _)| value.eq_ignore_ascii_case(mime_type))
+        });
+    let from_url = || {
+        let path = reqwest::Url::parse(file_url).ok()?;
+        let (_, extension) = path.path().rsplit_once('.')?;
+        EXTENSIONS
+            .iter()
+            .find(|(_, value)| extension.eq_ignore_ascii_case(value))
+    };
+    from_type
+        .or_else(from_url)
+        .map(|(_, extension)| *extension)
+        .unwrap_or("bin")
+}
+
+#[derive(Clone, Debug)]
+pub struct MediaStorage {
+    client: reqwest::Client,
+    path: PathBuf,
+    allow_private_hosts: bool,
+    max_file_size: u64,
+}
+
+impl entertainarr_domain::podcast::prelude::MediaStorage for MediaStorage {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            podcast_episode.id = download.podcast_episode_id,
+            url.full = download.file_url,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn store(&self, download: &PendingPodcastEpisodeDownload) -> anyhow::Result<StoredMedia> {
+        let url = entertainarr_adapter_public_http::check_url(
+            &download.file_url,
+            self.allow_private_hosts,
+        )?;
+        let extension = extension(download.file_type.as_deref(), &download.file_url);
+        let file_path = format!(
+            "{}/{}.{extension}",
+            download.podcast_id, download.podcast_episode_id
+        );
+        let target = self.path.join(&file_path);
+        // written next to the target, so that a partial download never replaces a copy
+        let partial = target.with_extension(format!("{extension}.part"));
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("unable to create directory {parent:?}"))?;
+        }
+
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .context("unable to fetch episode file")?
+            .error_for_status()
+            .context("unable to fetch episode file")?;
+        if response
+            .content_length()
+            .is_some_and(|length| length > self.max_file_size)
+        {
+            anyhow::bail!("episode file is too large");
+        }
+        let mut file = tokio::fs::File::create(&partial)
+            .await
+            .with_context(|| format!("unable to create file {partial:?}"))?;
+        let mut file_size = 0u64;
+        let written: anyhow::Result<()> = async {
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .context("unable to read episode file")?
+            {
+                // the announced length can't be trusted
+                file_size += chunk.len() as u64;
+                if file_size > self.max_file_size {
+                    anyhow::bail!("episode file is too large");
+                }
+                file.write_all(&chunk)
+                    .await
+                    .context("unable to write episode file")?;
+            }
+            file.flush().await.context("unable to write episode file")
+        }
+        .await;
+        drop(file);
+        if let Err(err) = written {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(err);
+        }
+        tokio::fs::rename(&partial, &target)
+            .await
+            .with_context(|| format!("unable to move file to {target:?}"))?;
+
+        Ok(StoredMedia {
+            file_path,
+            file_size,
+        })
+    }
+
+    #[tracing::instrument(skip(self), err(Debug))]
+    async fn remove(&self, file_path: &str) -> anyhow::Result<()> {
+        match tokio::fs::remove_file(self.resolve(file_path)).await {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err).with_context(|| format!("unable to remove file {file_path:?}")),
+        }
+    }
+
+    fn resolve(&self, file_path: &str) -> PathBuf {
+        self.path.join(file_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entertainarr_domain::podcast::entity::PendingPodcastEpisodeDownload;
+    use entertainarr_domain::podcast::prelude::MediaStorage;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Serves the episode file with the provided status
+    async fn serve(status: u16, body: &[u8]) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/episode.mp3"))
+            .respond_with(ResponseTemplate::new(status).set_body_raw(body, "audio/mpeg"))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn pending(file_url: String) -> PendingPodcastEpisodeDownload {
+        PendingPodcastEpisodeDownload {
+            podcast_id: 1,
+            podcast_episode_id: 2,
+            file_url,
+            file_type: None,
+            attempts: 0,
+        }
+    }
+
+    #[test]
+    fn should_find_extension() {
+        assert_eq!(
+            super::extension(Some("audio/mpeg"), "https://example.com/episode"),
+            "mp3"
+        );
+        assert_eq!(
+            super::extension(Some("Audio/X-M4A"), "https://example.com/episode.mp3"),
+            "m4a"
+        );
+        assert_eq!(
+            super::extension(None, "https://example.com/episode.ogg?token=abc"),
+            "ogg"
+        );
+        assert_eq!(
+            super::extension(Some("application/octet-stream"), "not a url"),
+            "bin"
+        );
+    }
+
+    #[tokio::test]
+    async fn should_store_and_remove_episode_file() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = crate::Config {
+            path: tmpdir.path().join("media"),
+            allow_private_hosts: true,
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+
+        let server = serve(200, b"some audio").await;
+        let download = pending(format!("{}/episode.mp3", server.uri()));
+        let stored = storage.store(&download).await;
+        let stored = stored.unwrap();
+        assert_eq!(stored.file_path, "1/2.mp3");
+        assert_eq!(stored.file_size, 10);
+        let path = storage.resolve(&stored.file_path);
+        assert_eq!(path, tmpdir.path().join("media").join("1/2.mp3"));
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), b"some audio");
+
+        storage.remove(&stored.file_path).await.unwrap();
+        assert!(!path.exists());
+        // removing a missing file is not an error
+        storage.remove(&stored.file_path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_fail_on_error_status() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = crate::Config {
+            path: tmpdir.path().to_path_buf(),
+            allow_private_hosts: true,
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+
+        let server = serve(404, b"").await;
+        let download = pending(format!("{}/episode.mp3", server.uri()));
+        let stored = storage.store(&download).await;
+        assert!(stored.is_err());
+        assert!(!tmpdir.path().join("1/2.mp3").exists());
+        assert!(!tmpdir.path().join("1/2.mp3.part").exists());
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_file_is_too_large() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = crate::Config {
+            path: tmpdir.path().to_path_buf(),
+            allow_private_hosts: true,
+            max_file_size: 4,
+        }
+        .build()
+        .unwrap();
+
+        let server = serve(200, b"some audio").await;
+        let download = pending(format!("{}/episode.mp3", server.uri()));
+        let stored = storage.store(&download).await;
+        let err = stored.unwrap_err();
+        assert_eq!(err.to_string(), "episode file is too large");
+        assert!(!tmpdir.path().join("1/2.mp3").exists());
+    }
+
+    #[tokio::test]
+    async fn should_refuse_private_hosts() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let storage = crate::Config {
+            path: tmpdir.path().to_path_buf(),
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+
+        let stored = storage
+            .store(&pending("http://169.254.169.254/latest".into()))
+            .await;
+        assert!(stored.is_err());
+        let stored = storage
+            .store(&pending("http://localhost:1/episode.mp3".into()))
+            .await;
+        assert!(stored.is_err());
+        assert!(!tmpdir.path().join("1/2.mp3").exists());
+    }
+}
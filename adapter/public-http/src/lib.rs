@@ -0,0 +1,160 @@
+//! Helpers for the http clients reaching the urls found in feeds. Any user can subscribe to a
+//! feed, so unless allowed, the loopback, link-local and private addresses are refused, whether
+//! the url points at them directly, through a domain name or through a redirection.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub struct ForbiddenHost(String);
+
+impl std::fmt::Display for ForbiddenHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "host of {} is not allowed", self.0)
+    }
+}
+
+impl std::error::Error for ForbiddenHost {}
+
+/// Refuses the redirections to private hosts, along with the ones past `max_redirects`
+pub fn redirect_policy(
+    max_redirects: usize,
+    allow_private_hosts: bool,
+) -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= max_redirects {
+            attempt.error("too many redirects")
+        } else if !allow_private_hosts && !is_allowed_url(attempt.url()) {
+            let err = ForbiddenHost(attempt.url().to_string());
+            attempt.error(err)
+        } else {
+            attempt.follow()
+        }
+    })
+}
+
+/// Refuses the domain names resolving to private addresses
+pub fn restrict(
+    builder: reqwest::ClientBuilder,
+    allow_private_hosts: bool,
+) -> reqwest::ClientBuilder {
+    if allow_private_hosts {
+        builder
+    } else {
+        builder.dns_resolver(Arc::new(PublicResolver))
+    }
+}
+
+/// Parses the url, refusing the private hosts given as ip addresses, the domain names being
+/// checked once resolved
+pub fn check_url(url: &str, allow_private_hosts: bool) -> Result<reqwest::Url, ForbiddenHost> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| ForbiddenHost(url.to_string()))?;
+    if !allow_private_hosts && !is_allowed_url(&parsed) {
+        return Err(ForbiddenHost(url.to_string()));
+    }
+    Ok(parsed)
+}
+
+pub fn is_allowed_url(url: &reqwest::Url) -> bool {
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+    match url.host_str() {
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .map_or(true, is_public),
+        None => false,
+    }
+}
+
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_unspecified()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || first == 0
+                // shared address space, used for carrier-grade NAT
+                || (first == 100 && (second & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // unique local
+                    || (first & 0xfe00) == 0xfc00
+                    // link-local
+                    || (first & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Resolves the domain names, keeping only the public addresses
+pub struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let addresses = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|address| is_public(address.ip()))
+                .collect::<Vec<SocketAddr>>();
+            if addresses.is_empty() {
+                return Err(Box::new(ForbiddenHost(name.as_str().to_string()))
+                    as Box<dyn std::error::Error + Send + Sync>);
+            }
+            Ok(Box::new(addresses.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    #[test]
+    fn should_only_allow_public_addresses() {
+        for (input, expected) in [
+            ("93.184.215.14", true),
+            ("2606:2800:21f:cb07:6820:80da:af6b:8b2c", true),
+            ("127.0.0.1", false),
+            ("10.1.2.3", false),
+            ("172.16.0.1", false),
+            ("192.168.1.1", false),
+            ("169.254.169.254", false),
+            ("100.64.0.1", false),
+            ("0.0.0.0", false),
+            ("::1", false),
+            ("fd00::1", false),
+            ("fe80::1", false),
+            ("::ffff:127.0.0.1", false),
+        ] {
+            assert_eq!(
+                super::is_public(input.parse::<IpAddr>().unwrap()),
+                expected,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_refuse_private_urls() {
+        assert!(super::check_url("https://example.com/episode.mp3", false).is_ok());
+        assert!(super::check_url("http://169.254.169.254/latest", false).is_err());
+        assert!(super::check_url("http://[::1]:3000/", false).is_err());
+        assert!(super::check_url("file:///etc/passwd", false).is_err());
+        assert!(super::check_url("not a url", true).is_err());
+        assert!(super::check_url("http://127.0.0.1:3000/", true).is_ok());
+    }
+}
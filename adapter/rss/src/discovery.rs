@@ -0,0 +1,180 @@
+//! Feed autodiscovery, for when a website url is provided instead of a feed url
+
+const FEED_TYPES: [&str; 2] = ["application/rss+xml", "application/atom+xml"];
+
+/// Checks if the fetched document is an HTML page rather than a feed
+pub(crate) fn is_html(content_type: Option<&str>, body: &str) -> bool {
+    let head = body
+        .trim_start()
+        .chars()
+        .take(16)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if head.starts_with("<!doctype html") || head.starts_with("<html") {
+        return true;
+    }
+    let xml_like =
+        head.starts_with("<?xml") || head.starts_with("<rss") || head.starts_with("<feed");
+    !xml_like
+        && content_type.is_some_and(|value| {
+            let value = value.trim_start().to_ascii_lowercase();
+            value.starts_with("text/html") || value.starts_with("application/xhtml+xml")
+        })
+}
+
+/// Looks for the first `<link rel="alternate">` pointing to an RSS or Atom feed
+pub(crate) fn find_feed_url(page_url: &reqwest::Url, body: &str) -> Option<reqwest::Url> {
+    // ascii lowercase keeps the byte offsets of the original body
+    let lower = body.to_ascii_lowercase();
+    let mut offset = 0;
+    while let Some(index) = lower[offset..].find("<link") {
+        let start = offset + index + "<link".len();
+        let end = start + lower[start..].find('>')?;
+        offset = end;
+        if !body[start..].starts_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let attributes = parse_attributes(&body[start..end]);
+        let attribute = |name: &str| {
+            attributes
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        };
+        let is_alternate = attribute("rel").is_some_and(|rel| {
+            rel.split_ascii_whitespace()
+                .any(|item| item.eq_ignore_ascii_case("alternate"))
+        });
+        let is_feed = attribute("type").is_some_and(|kind| {
+            FEED_TYPES
+                .iter()
+                .any(|expected| kind.trim().eq_ignore_ascii_case(expected))
+        });
+        if !is_alternate || !is_feed {
+            continue;
+        }
+        if let Some(url) = attribute("href").and_then(|href| page_url.join(href.trim()).ok()) {
+            return Some(url);
+        }
+    }
+    None
+}
+
+/// Parses the attributes of a tag, quoted or not, decoding the common entities
+fn parse_attributes(input: &str) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    loop {
+        while chars
+            .next_if(|(_, c)| c.is_ascii_whitespace() || *c == '/')
+            .is_some()
+        {}
+        let Some(&(name_start, _)) = chars.peek() else {
+            break;
+        };
+        let mut name_end = input.len();
+        while let Some(&(index, c)) = chars.peek() {
+            if c.is_ascii_whitespace() || c == '=' || c == '/' {
+                name_end = index;
+                break;
+            }
+            chars.next();
+        }
+        let name = &input[name_start..name_end];
+        while chars.next_if(|(_, c)| c.is_ascii_whitespace()).is_some() {}
+        if chars.next_if(|(_, c)| *c == '=').is_none() {
+            result.push((name.to_string(), String::new()));
+            continue;
+        }
+        while chars.next_if(|(_, c)| c.is_ascii_whitespace()).is_some() {}
+        let value = match chars.peek().copied() {
+            Some((index, quote @ ('"' | '\''))) => {
+                chars.next();
+                let start = index + 1;
+                let mut end = input.len();
+                for (index, c) in chars.by_ref() {
+                    if c == quote {
+                        end = index;
+                        break;
+                    }
+                }
+                &input[start..end]
+            }
+            Some((start, _)) => {
+                let mut end = input.len();
+                while let Some(&(index, c)) = chars.peek() {
+                    if c.is_ascii_whitespace() {
+                        end = index;
+                        break;
+                    }
+                    chars.next();
+                }
+                &input[start..end]
+            }
+            None => "",
+        };
+        result.push((name.to_string(), decode_entities(value)));
+    }
+    result
+}
+
+pub(crate) fn decode_entities(input: &str) -> String {
+    input
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    fn page_url() -> reqwest::Url {
+        reqwest::Url::parse("https://example.com/show/").unwrap()
+    }
+
+    #[test]
+    fn should_detect_html() {
+        assert!(super::is_html(None, "\n  <!DOCTYPE html><html></html>"));
+        assert!(super::is_html(
+            Some("text/html; charset=utf-8"),
+            "<head></head>"
+        ));
+        assert!(!super::is_html(
+            Some("text/html"),
+            "<?xml version=\"1.0\"?><rss></rss>"
+        ));
+        assert!(!super::is_html(Some("application/rss+xml"), "<rss></rss>"));
+    }
+
+    #[test]
+    fn should_find_relative_feed_url() {
+        let body = r#"<html><head>
+            <link rel="stylesheet" href="/style.css">
+            <link rel="icon" type="image/png" href="/favicon.png">
+            <LINK TYPE='application/rss+xml' title="Feed" REL='alternate' href='feed.rss?format=rss&amp;v=2' />
+            </head></html>"#;
+        let found = super::find_feed_url(&page_url(), body).unwrap();
+        assert_eq!(
+            found.as_str(),
+            "https://example.com/show/feed.rss?format=rss&v=2"
+        );
+    }
+
+    #[test]
+    fn should_find_atom_feed_url() {
+        let body =
+            r#"<link rel=alternate type=application/atom+xml href=https://feeds.example.org/atom>"#;
+        let found = super::find_feed_url(&page_url(), body).unwrap();
+        assert_eq!(found.as_str(), "https://feeds.example.org/atom");
+    }
+
+    #[test]
+    fn should_not_find_feed_url() {
+        let body = r#"<html><head>
+            <link rel="alternate" hreflang="fr" href="/fr/">
+            <linkedin rel="alternate" type="application/rss+xml" href="/nope.rss">
+            </head></html>"#;
+        assert!(super::find_feed_url(&page_url(), body).is_none());
+    }
+}
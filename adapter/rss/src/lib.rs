@@ -1,25 +1,55 @@
 use std::time::Duration;
 
+use anyhow::Context;
+
+mod discovery;
 mod parser;
 mod podcast;
+mod resource;
 mod tracing;
 
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// The documents are small enough to be read entirely within that time
+const TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(Default, serde::Deserialize)]
-pub struct Config;
+pub struct Config {
+    /// Allows loading the feeds and documents hosted on the local network
+    #[serde(default)]
+    pub allow_private_hosts: bool,
+}
 
 impl Config {
     pub fn build(self) -> anyhow::Result<RssClient> {
-        let client = reqwest::Client::new();
+        // redirects are followed by hand to detect the feeds that moved
+        let builder = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none());
+        let client = entertainarr_adapter_public_http::restrict(builder, self.allow_private_hosts)
+            .build()
+            .context("unable to build http client")?;
         let client = reqwest_middleware::ClientBuilder::new(client)
             .with(tracing::TracingMiddleware)
             .build();
-        Ok(RssClient { client })
+        Ok(RssClient {
+            client,
+            allow_private_hosts: self.allow_private_hosts,
+        })
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct RssClient {
     client: reqwest_middleware::ClientWithMiddleware,
+    allow_private_hosts: bool,
+}
+
+/// Document returned by a feed url, either RSS or Atom
+#[derive(Debug)]
+enum Feed {
+    Rss(Rss),
+    Atom(AtomFeed),
 }
 
 #[derive(Debug, Default)]
@@ -44,13 +74,17 @@ struct Channel {
     atom_link_href: Option<String>,
     // itunes namespace
     itunes_author: Option<String>,
-    itunes_category: Option<String>,
+    itunes_categories: Vec<String>,
     itunes_explicit: Option<bool>,
     itunes_image_href: Option<String>,
+    itunes_new_feed_url: Option<String>,
     itunes_owner_email: Option<String>,
     itunes_owner_name: Option<String>,
     itunes_subtitle: Option<String>,
     itunes_summary: Option<String>,
+    // podcast namespace
+    podcast_funding: Vec<Funding>,
+    podcast_persons: Vec<Person>,
     //
     items: Vec<ChannelItem>,
 }
@@ -68,5 +102,66 @@ pub struct ChannelItem {
     enclosure_length: Option<u64>,
     enclosure_type: Option<String>,
     itunes_duration: Option<Duration>,
+    itunes_episode: Option<u32>,
+    itunes_episode_type: Option<String>,
+    itunes_image_href: Option<String>,
+    itunes_season: Option<u32>,
     itunes_summary: Option<String>,
+    // podcast namespace
+    podcast_chapters_url: Option<String>,
+    podcast_persons: Vec<Person>,
+    podcast_transcripts: Vec<Transcript>,
+}
+
+/// `podcast:funding` element
+#[derive(Debug)]
+struct Funding {
+    url: String,
+    title: Option<String>,
+}
+
+/// `podcast:person` element
+#[derive(Debug)]
+struct Person {
+    name: String,
+    role: Option<String>,
+    group: Option<String>,
+    href: Option<String>,
+    img: Option<String>,
+}
+
+/// `podcast:transcript` element
+#[derive(Debug)]
+struct Transcript {
+    url: String,
+    mime_type: String,
+    language: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct AtomFeed {
+    title: Option<String>,
+    subtitle: Option<String>,
+    language: Option<String>,
+    link_self: Option<String>,
+    link_alternate: Option<String>,
+    icon: Option<String>,
+    logo: Option<String>,
+    entries: Vec<AtomEntry>,
+}
+
+#[derive(Debug, Default)]
+struct AtomEntry {
+    id: Option<String>,
+    title: Option<String>,
+    summary: Option<String>,
+    content: Option<String>,
+    link_alternate: Option<String>,
+    published: Option<chrono::DateTime<chrono::Utc>>,
+    updated: Option<chrono::DateTime<chrono::Utc>>,
+    enclosure_url: Option<String>,
+    enclosure_length: Option<u64>,
+    enclosure_type: Option<String>,
+    // itunes namespace
+    itunes_duration: Option<Duration>,
 }
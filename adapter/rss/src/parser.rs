@@ -5,25 +5,121 @@ use anyhow::Context;
 const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
 const CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
 const ITUNES_NAMESPACE: &str = "http://www.itunes.com/dtds/podcast-1.0.dtd";
+const PODCAST_NAMESPACE: &str = "https://podcastindex.org/namespace/1.0";
+/// Namespace used by the early adopters, before the podcastindex.org one was settled
+const PODCAST_LEGACY_NAMESPACE: &str =
+    "https://github.com/Podcastindex-org/podcast-namespace/blob/main/docs/1.0.md";
 
-fn parse_bool<'a, 'input>(node: roxmltree::Node<'a, 'input>) -> anyhow::Result<Option<bool>> {
+/// Parses `itunes:explicit`, which is `true`/`false` but was `yes`/`no`/`clean` in older feeds
+fn parse_explicit<'a, 'input>(node: roxmltree::Node<'a, 'input>) -> anyhow::Result<Option<bool>> {
     let Some(txt) = node.children().find_map(|item| item.text()) else {
         return Ok(None);
     };
-    txt.parse::<bool>()
-        .context("unable to parse boolean")
+    match txt.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "explicit" => Ok(Some(true)),
+        "false" | "no" | "clean" => Ok(Some(false)),
+        _ => Err(anyhow::anyhow!("unable to parse explicit flag {txt:?}")),
+    }
+}
+
+fn parse_number<'a, 'input>(node: roxmltree::Node<'a, 'input>) -> anyhow::Result<Option<u32>> {
+    let Some(txt) = node.children().find_map(|item| item.text()) else {
+        return Ok(None);
+    };
+    txt.trim()
+        .parse::<u32>()
+        .with_context(|| format!("unable to parse number {txt:?}"))
         .map(Some)
 }
 
+/// Formats found in the wild when the date is neither RFC 2822 nor RFC 3339, without timezone
+const NAIVE_DATE_TIME_FORMATS: [&str; 5] = [
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%a, %d %b %Y %H:%M:%S",
+    "%a, %d %b %Y %H:%M",
+    "%d %b %Y %H:%M:%S",
+];
+
+/// Parses a date, expected as RFC 2822 for RSS and RFC 3339 for Atom, but being lenient
+fn parse_date_text(txt: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let txt = txt.trim();
+    if let Ok(date) = chrono::DateTime::parse_from_rfc2822(txt) {
+        return Some(date.to_utc());
+    }
+    if let Ok(date) = chrono::DateTime::parse_from_rfc3339(txt) {
+        return Some(date.to_utc());
+    }
+    // the day of the week is sometimes wrong or not in english
+    if let Some((_, rest)) = txt.split_once(',')
+        && let Ok(date) = chrono::DateTime::parse_from_rfc2822(rest.trim())
+    {
+        return Some(date.to_utc());
+    }
+    if let Some(date) = NAIVE_DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(txt, format).ok())
+    {
+        return Some(date.and_utc());
+    }
+    chrono::NaiveDate::parse_from_str(txt, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
 fn parse_date<'a, 'input>(
     node: roxmltree::Node<'a, 'input>,
 ) -> anyhow::Result<Option<chrono::DateTime<chrono::Utc>>> {
     let Some(txt) = node.children().find_map(|item| item.text()) else {
         return Ok(None);
     };
-    chrono::DateTime::parse_from_rfc2822(txt)
-        .context("unable to parse date")
-        .map(|date| Some(date.to_utc()))
+    parse_date_text(txt)
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("unable to parse date {txt:?}"))
+}
+
+/// Parses a duration, either as `[[hh:]mm:]ss[.sss]` or as values with units like `3600 sec` or `1h 30min`
+fn parse_duration_text(txt: &str) -> Option<Duration> {
+    let txt = txt.trim();
+    if txt.is_empty() {
+        return None;
+    }
+    let parse_value = |value: &str| {
+        value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite() && *value >= 0.0)
+    };
+    if txt.contains(':') {
+        let mut result = 0.0;
+        for item in txt.split(':') {
+            result = result * 60.0 + parse_value(item)?;
+        }
+        return Duration::try_from_secs_f64(result).ok();
+    }
+    let mut result = 0.0;
+    let mut rest = txt;
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let value = parse_value(&rest[..end])?;
+        rest = rest[end..].trim_start();
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let factor = match rest[..end].to_ascii_lowercase().as_str() {
+            "" | "s" | "sec" | "secs" | "second" | "seconds" => 1.0,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60.0,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600.0,
+            _ => return None,
+        };
+        result += value * factor;
+        rest = rest[end..].trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+    }
+    Duration::try_from_secs_f64(result).ok()
 }
 
 fn parse_duration<'a, 'input>(
@@ -32,11 +128,9 @@ fn parse_duration<'a, 'input>(
     let Some(txt) = node.children().find_map(|item| item.text()) else {
         return Ok(None);
     };
-    let mut result: u64 = 0;
-    for item in txt.split(':') {
-        result = result * 60 + item.parse::<u64>()?;
-    }
-    Ok(Some(Duration::from_secs(result)))
+    parse_duration_text(txt)
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("unable to parse duration {txt:?}"))
 }
 
 fn parse_text<'a, 'input>(node: roxmltree::Node<'a, 'input>) -> Option<String> {
@@ -45,6 +139,61 @@ fn parse_text<'a, 'input>(node: roxmltree::Node<'a, 'input>) -> Option<String> {
         .map(|value| String::from(value.trim()))
 }
 
+fn parse_attribute<'a, 'input>(node: roxmltree::Node<'a, 'input>, name: &str) -> Option<String> {
+    node.attribute(name)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+fn parse_funding<'a, 'input>(node: roxmltree::Node<'a, 'input>) -> anyhow::Result<super::Funding> {
+    Ok(super::Funding {
+        url: parse_attribute(node, "url").ok_or_else(|| anyhow::anyhow!("funding without url"))?,
+        title: parse_text(node).filter(|value| !value.is_empty()),
+    })
+}
+
+fn parse_person<'a, 'input>(node: roxmltree::Node<'a, 'input>) -> anyhow::Result<super::Person> {
+    Ok(super::Person {
+        name: parse_text(node)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("person without name"))?,
+        role: parse_attribute(node, "role"),
+        group: parse_attribute(node, "group"),
+        href: parse_attribute(node, "href"),
+        img: parse_attribute(node, "img"),
+    })
+}
+
+fn parse_transcript<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+) -> anyhow::Result<super::Transcript> {
+    Ok(super::Transcript {
+        url: parse_attribute(node, "url")
+            .ok_or_else(|| anyhow::anyhow!("transcript without url"))?,
+        mime_type: parse_attribute(node, "type")
+            .ok_or_else(|| anyhow::anyhow!("transcript without type"))?,
+        language: parse_attribute(node, "language"),
+    })
+}
+
+impl std::str::FromStr for super::Feed {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let doc = roxmltree::Document::parse(input).context("unable to parse document")?;
+
+        let root = doc.root_element();
+        if root.has_tag_name("rss") {
+            super::Rss::try_from(root).map(super::Feed::Rss)
+        } else if root.has_tag_name((ATOM_NAMESPACE, "feed")) {
+            super::AtomFeed::try_from(root).map(super::Feed::Atom)
+        } else {
+            anyhow::bail!("expected rss or atom root element")
+        }
+    }
+}
+
 impl std::str::FromStr for super::Rss {
     type Err = anyhow::Error;
 
@@ -55,8 +204,15 @@ impl std::str::FromStr for super::Rss {
         if !root.has_tag_name("rss") {
             anyhow::bail!("expected rss root element")
         }
+        super::Rss::try_from(root)
+    }
+}
+
+impl<'a, 'b> TryFrom<roxmltree::Node<'a, 'b>> for super::Rss {
+    type Error = anyhow::Error;
 
-        let channels = root
+    fn try_from(node: roxmltree::Node<'a, 'b>) -> Result<Self, Self::Error> {
+        let channels = node
             .children()
             .filter(|item| item.has_tag_name("channel"))
             .map(super::Channel::try_from)
@@ -89,11 +245,35 @@ impl super::Channel {
         node: roxmltree::Node<'a, 'input>,
     ) -> anyhow::Result<()> {
         if node.tag_name().name() == "link" {
-            self.atom_link_href = node.attribute("href").map(String::from);
+            // other relations, like websub hubs, don't point to the feed itself
+            match node.attribute("rel") {
+                Some("self") => {
+                    self.atom_link_href = node.attribute("href").map(String::from);
+                }
+                None if self.atom_link_href.is_none() => {
+                    self.atom_link_href = node.attribute("href").map(String::from);
+                }
+                _ => {}
+            }
         }
         Ok(())
     }
 
+    /// Reads the category and its subcategories, skipping the ones already known
+    fn parse_itunes_category<'a, 'input>(&mut self, node: roxmltree::Node<'a, 'input>) {
+        if let Some(text) = node.attribute("text").map(str::trim)
+            && !text.is_empty()
+            && !self.itunes_categories.iter().any(|item| item == text)
+        {
+            self.itunes_categories.push(text.to_string());
+        }
+        for child in node.children() {
+            if child.has_tag_name((ITUNES_NAMESPACE, "category")) {
+                self.parse_itunes_category(child);
+            }
+        }
+    }
+
     fn parse_itunes_owner<'a, 'input>(
         &mut self,
         node: roxmltree::Node<'a, 'input>,
@@ -121,14 +301,17 @@ impl super::Channel {
                 self.itunes_author = parse_text(node);
             }
             "category" => {
-                self.itunes_category = node.attribute("text").map(String::from);
+                self.parse_itunes_category(node);
             }
             "explicit" => {
-                self.itunes_explicit = parse_bool(node)?;
+                self.itunes_explicit = parse_explicit(node)?;
             }
             "image" => {
                 self.itunes_image_href = node.attribute("href").map(String::from);
             }
+            "new-feed-url" => {
+                self.itunes_new_feed_url = parse_text(node);
+            }
             "subtitle" => {
                 self.itunes_subtitle = parse_text(node);
             }
@@ -143,6 +326,22 @@ impl super::Channel {
         Ok(())
     }
 
+    fn parse_podcast_namespace<'a, 'input>(
+        &mut self,
+        node: roxmltree::Node<'a, 'input>,
+    ) -> anyhow::Result<()> {
+        match node.tag_name().name() {
+            "funding" => {
+                self.podcast_funding.push(parse_funding(node)?);
+            }
+            "person" => {
+                self.podcast_persons.push(parse_person(node)?);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn parse_no_namespace<'a, 'input>(
         &mut self,
         node: roxmltree::Node<'a, 'input>,
@@ -188,6 +387,9 @@ impl super::Channel {
             None => self.parse_no_namespace(node),
             Some(ATOM_NAMESPACE) => self.parse_atom_namespace(node),
             Some(ITUNES_NAMESPACE) => self.parse_itunes_namespace(node),
+            Some(PODCAST_NAMESPACE | PODCAST_LEGACY_NAMESPACE) => {
+                self.parse_podcast_namespace(node)
+            }
             Some(namespace) => {
                 tracing::warn!(namespace, "unknown namespace");
                 Ok(())
@@ -216,6 +418,9 @@ impl super::ChannelItem {
             None => self.parse_no_namespace(node),
             Some(CONTENT_NAMESPACE) => self.parse_content_namespace(node),
             Some(ITUNES_NAMESPACE) => self.parse_itunes_namespace(node),
+            Some(PODCAST_NAMESPACE | PODCAST_LEGACY_NAMESPACE) => {
+                self.parse_podcast_namespace(node)
+            }
             Some(namespace) => {
                 tracing::warn!(namespace, "unknown namespace");
                 Ok(())
@@ -241,6 +446,18 @@ impl super::ChannelItem {
             "duration" => {
                 self.itunes_duration = parse_duration(node)?;
             }
+            "episode" => {
+                self.itunes_episode = parse_number(node)?;
+            }
+            "episodeType" => {
+                self.itunes_episode_type = parse_text(node);
+            }
+            "image" => {
+                self.itunes_image_href = node.attribute("href").map(String::from);
+            }
+            "season" => {
+                self.itunes_season = parse_number(node)?;
+            }
             "summary" => {
                 self.itunes_summary = parse_text(node);
             }
@@ -249,6 +466,25 @@ impl super::ChannelItem {
         Ok(())
     }
 
+    fn parse_podcast_namespace<'a, 'input>(
+        &mut self,
+        node: roxmltree::Node<'a, 'input>,
+    ) -> anyhow::Result<()> {
+        match node.tag_name().name() {
+            "chapters" => {
+                self.podcast_chapters_url = parse_attribute(node, "url");
+            }
+            "person" => {
+                self.podcast_persons.push(parse_person(node)?);
+            }
+            "transcript" => {
+                self.podcast_transcripts.push(parse_transcript(node)?);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn parse_no_namespace<'a, 'input>(
         &mut self,
         node: roxmltree::Node<'a, 'input>,
@@ -310,6 +546,176 @@ impl<'a, 'b> TryFrom<roxmltree::Node<'a, 'b>> for super::ChannelItem {
     }
 }
 
+/// Relation of an atom link, defaulting to `alternate` as defined in RFC 4287
+fn atom_link_rel<'a, 'input>(node: roxmltree::Node<'a, 'input>) -> &'a str {
+    node.attribute("rel").unwrap_or("alternate")
+}
+
+impl super::AtomFeed {
+    fn parse_link<'a, 'input>(&mut self, node: roxmltree::Node<'a, 'input>) -> anyhow::Result<()> {
+        let href = node.attribute("href").map(String::from);
+        match atom_link_rel(node) {
+            "self" => {
+                self.link_self = href;
+            }
+            "alternate" if self.link_alternate.is_none() => {
+                self.link_alternate = href;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn parse_atom_namespace<'a, 'input>(
+        &mut self,
+        node: roxmltree::Node<'a, 'input>,
+    ) -> anyhow::Result<()> {
+        match node.tag_name().name() {
+            "title" => {
+                self.title = parse_text(node);
+            }
+            "subtitle" => {
+                self.subtitle = parse_text(node);
+            }
+            "link" => {
+                self.parse_link(node)?;
+            }
+            "icon" => {
+                self.icon = parse_text(node);
+            }
+            "logo" => {
+                self.logo = parse_text(node);
+            }
+            "entry" => {
+                self.entries.push(super::AtomEntry::try_from(node)?);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn parse_child<'a, 'input>(&mut self, node: roxmltree::Node<'a, 'input>) -> anyhow::Result<()> {
+        match node.tag_name().namespace() {
+            None => Ok(()),
+            Some(ATOM_NAMESPACE) => self.parse_atom_namespace(node),
+            Some(namespace) => {
+                tracing::warn!(namespace, "unknown namespace");
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a, 'b> TryFrom<roxmltree::Node<'a, 'b>> for super::AtomFeed {
+    type Error = anyhow::Error;
+
+    fn try_from(node: roxmltree::Node<'a, 'b>) -> Result<Self, Self::Error> {
+        let mut feed = super::AtomFeed {
+            language: node
+                .attribute((roxmltree::NS_XML_URI, "lang"))
+                .map(String::from),
+            ..Default::default()
+        };
+        for child in node.children() {
+            if let Err(err) = feed.parse_child(child) {
+                tracing::warn!(error = ?err, "unable to parse child node");
+            }
+        }
+        Ok(feed)
+    }
+}
+
+impl super::AtomEntry {
+    fn parse_link<'a, 'input>(&mut self, node: roxmltree::Node<'a, 'input>) -> anyhow::Result<()> {
+        match atom_link_rel(node) {
+            "alternate" if self.link_alternate.is_none() => {
+                self.link_alternate = node.attribute("href").map(String::from);
+            }
+            "enclosure" if self.enclosure_url.is_none() => {
+                self.enclosure_url = node.attribute("href").map(String::from);
+                self.enclosure_type = node.attribute("type").map(String::from);
+                self.enclosure_length = node.attribute("length").and_then(|value| {
+                    value
+                        .parse::<u64>()
+                        .inspect_err(
+                            |err| tracing::warn!(error = ?err, "unable to parse enclosure length"),
+                        )
+                        .ok()
+                });
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn parse_atom_namespace<'a, 'input>(
+        &mut self,
+        node: roxmltree::Node<'a, 'input>,
+    ) -> anyhow::Result<()> {
+        match node.tag_name().name() {
+            "id" => {
+                self.id = parse_text(node);
+            }
+            "title" => {
+                self.title = parse_text(node);
+            }
+            "summary" => {
+                self.summary = parse_text(node);
+            }
+            "content" => {
+                self.content = parse_text(node);
+            }
+            "link" => {
+                self.parse_link(node)?;
+            }
+            "published" => {
+                self.published = parse_date(node)?;
+            }
+            "updated" => {
+                self.updated = parse_date(node)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn parse_itunes_namespace<'a, 'input>(
+        &mut self,
+        node: roxmltree::Node<'a, 'input>,
+    ) -> anyhow::Result<()> {
+        if node.tag_name().name() == "duration" {
+            self.itunes_duration = parse_duration(node)?;
+        }
+        Ok(())
+    }
+
+    fn parse_child<'a, 'input>(&mut self, node: roxmltree::Node<'a, 'input>) -> anyhow::Result<()> {
+        match node.tag_name().namespace() {
+            None => Ok(()),
+            Some(ATOM_NAMESPACE) => self.parse_atom_namespace(node),
+            Some(ITUNES_NAMESPACE) => self.parse_itunes_namespace(node),
+            Some(namespace) => {
+                tracing::warn!(namespace, "unknown namespace");
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a, 'b> TryFrom<roxmltree::Node<'a, 'b>> for super::AtomEntry {
+    type Error = anyhow::Error;
+
+    fn try_from(node: roxmltree::Node<'a, 'b>) -> Result<Self, Self::Error> {
+        let mut entry = super::AtomEntry::default();
+        for child in node.children() {
+            if let Err(err) = entry.parse_child(child) {
+                tracing::warn!(error = ?err, "unable to parse child node");
+            }
+        }
+        Ok(entry)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -349,4 +755,186 @@ mod tests {
             assert!(item.link.is_some() || item.enclosure_url.is_some());
         }
     }
+
+    #[test]
+    fn should_parse_atom_feed() {
+        let input = include_str!("../assets/podcast/atom-example.xml");
+        let crate::Feed::Atom(feed) = crate::Feed::from_str(input).unwrap() else {
+            panic!("expected atom feed");
+        };
+        assert_eq!(feed.title.as_deref(), Some("Example Atom Podcast"));
+        assert_eq!(
+            feed.link_self.as_deref(),
+            Some("https://example.com/podcast.atom")
+        );
+        assert_eq!(feed.link_alternate.as_deref(), Some("https://example.com/"));
+        assert_eq!(feed.language.as_deref(), Some("en"));
+        assert_eq!(feed.entries.len(), 3);
+        let entry = &feed.entries[0];
+        assert_eq!(
+            entry.enclosure_url.as_deref(),
+            Some("https://example.com/episodes/2.mp3")
+        );
+        assert_eq!(entry.enclosure_length, Some(24986239));
+        assert_eq!(
+            entry.itunes_duration,
+            Some(std::time::Duration::from_secs(3723))
+        );
+        assert_eq!(
+            entry.published.unwrap().to_rfc3339(),
+            "2025-10-05T16:30:02+00:00"
+        );
+        let entry = &feed.entries[1];
+        assert_eq!(
+            entry.link_alternate.as_deref(),
+            Some("https://example.com/episodes/1")
+        );
+        assert_eq!(entry.content.as_deref(), Some("<p>First episode</p>"));
+    }
+
+    #[test]
+    fn should_parse_itunes_metadata() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+<channel>
+    <title>Example</title>
+    <itunes:author>Jane Doe</itunes:author>
+    <itunes:explicit>clean</itunes:explicit>
+    <itunes:category text="Technology" />
+    <itunes:category text="Society &amp; Culture">
+        <itunes:category text="Documentary" />
+    </itunes:category>
+    <itunes:category text="Technology" />
+    <item>
+        <title>Trailer</title>
+        <enclosure url="https://example.com/trailer.mp3" length="1234" type="audio/mpeg" />
+        <itunes:image href="https://example.com/trailer.jpg" />
+        <itunes:season>2</itunes:season>
+        <itunes:episode> 12 </itunes:episode>
+        <itunes:episodeType>trailer</itunes:episodeType>
+    </item>
+</channel>
+</rss>"#;
+        let rss = crate::Rss::from_str(input).unwrap();
+        let channel = &rss.channels[0];
+        assert_eq!(channel.itunes_author.as_deref(), Some("Jane Doe"));
+        assert_eq!(channel.itunes_explicit, Some(false));
+        assert_eq!(
+            channel.itunes_categories,
+            vec!["Technology", "Society & Culture", "Documentary"]
+        );
+        let item = &channel.items[0];
+        assert_eq!(
+            item.itunes_image_href.as_deref(),
+            Some("https://example.com/trailer.jpg")
+        );
+        assert_eq!(item.itunes_season, Some(2));
+        assert_eq!(item.itunes_episode, Some(12));
+        assert_eq!(item.itunes_episode_type.as_deref(), Some("trailer"));
+    }
+
+    #[test]
+    fn should_parse_podcast_namespace() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:podcast="https://podcastindex.org/namespace/1.0">
+<channel>
+    <title>Example</title>
+    <podcast:funding url="https://example.com/donate">Support the show</podcast:funding>
+    <podcast:person href="https://example.com/jane" img="https://example.com/jane.jpg">Jane Doe</podcast:person>
+    <item>
+        <title>First</title>
+        <enclosure url="https://example.com/first.mp3" length="1234" type="audio/mpeg" />
+        <podcast:chapters url="https://example.com/first.json" type="application/json+chapters" />
+        <podcast:transcript url="https://example.com/first.html" type="text/html" />
+        <podcast:transcript url="https://example.com/first.srt" type="application/srt" language="en" />
+        <podcast:person role="guest">John Doe</podcast:person>
+        <podcast:person role="guest"></podcast:person>
+    </item>
+</channel>
+</rss>"#;
+        let rss = crate::Rss::from_str(input).unwrap();
+        let channel = &rss.channels[0];
+        assert_eq!(channel.podcast_funding.len(), 1);
+        assert_eq!(channel.podcast_funding[0].url, "https://example.com/donate");
+        assert_eq!(
+            channel.podcast_funding[0].title.as_deref(),
+            Some("Support the show")
+        );
+        assert_eq!(channel.podcast_persons.len(), 1);
+        assert_eq!(channel.podcast_persons[0].name, "Jane Doe");
+        assert_eq!(
+            channel.podcast_persons[0].img.as_deref(),
+            Some("https://example.com/jane.jpg")
+        );
+        let item = &channel.items[0];
+        assert_eq!(
+            item.podcast_chapters_url.as_deref(),
+            Some("https://example.com/first.json")
+        );
+        assert_eq!(item.podcast_transcripts.len(), 2);
+        assert_eq!(item.podcast_transcripts[1].mime_type, "application/srt");
+        assert_eq!(item.podcast_transcripts[1].language.as_deref(), Some("en"));
+        assert_eq!(item.podcast_persons.len(), 1);
+        assert_eq!(item.podcast_persons[0].role.as_deref(), Some("guest"));
+    }
+
+    #[test]
+    fn should_parse_lenient_dates() {
+        let expected = "2025-10-05T16:30:02+00:00";
+        for input in [
+            "Sun, 05 Oct 2025 16:30:02 +0000",
+            "Sun, 5 Oct 2025 16:30:02 GMT",
+            " 2025-10-05T16:30:02Z ",
+            "Mon, 05 Oct 2025 16:30:02 +0000",
+            "Dim, 05 Oct 2025 16:30:02 +0000",
+            "2025-10-05 16:30:02",
+            "Sun, 05 Oct 2025 16:30:02",
+        ] {
+            let date = super::parse_date_text(input).unwrap();
+            assert_eq!(date.to_rfc3339(), expected, "{input:?}");
+        }
+        assert_eq!(
+            super::parse_date_text("2025-10-05").unwrap().to_rfc3339(),
+            "2025-10-05T00:00:00+00:00"
+        );
+        assert!(super::parse_date_text("yesterday").is_none());
+    }
+
+    #[test]
+    fn should_parse_lenient_durations() {
+        use std::time::Duration;
+
+        for (input, expected) in [
+            ("3723", Duration::from_secs(3723)),
+            ("62:03", Duration::from_secs(3723)),
+            ("1:02:03", Duration::from_secs(3723)),
+            ("1:02:03.5", Duration::from_secs_f64(3723.5)),
+            ("3600 sec", Duration::from_secs(3600)),
+            ("3600s", Duration::from_secs(3600)),
+            ("1h 2min 3s", Duration::from_secs(3723)),
+            ("45 minutes", Duration::from_secs(2700)),
+            (" 120.5 ", Duration::from_secs_f64(120.5)),
+        ] {
+            assert_eq!(
+                super::parse_duration_text(input),
+                Some(expected),
+                "{input:?}"
+            );
+        }
+        assert!(super::parse_duration_text("").is_none());
+        assert!(super::parse_duration_text("about an hour").is_none());
+        assert!(super::parse_duration_text("-1:00").is_none());
+        assert!(super::parse_duration_text("99999999999999999999999").is_none());
+        assert!(super::parse_duration_text("99999999999999999999:00:00").is_none());
+    }
+
+    #[test]
+    fn should_detect_rss_feed() {
+        let input = include_str!("../assets/podcast/rustacean-station.rss");
+        assert!(matches!(
+            crate::Feed::from_str(input).unwrap(),
+            crate::Feed::Rss(_)
+        ));
+        assert!(crate::Feed::from_str("<html></html>").is_err());
+    }
 }
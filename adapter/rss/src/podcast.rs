@@ -2,7 +2,58 @@ use std::str::FromStr;
 
 use anyhow::Context;
 
-use entertainarr_domain::podcast::entity::{PodcastEpisodeInput, PodcastInput};
+use entertainarr_domain::podcast::entity::{
+    FeedCache, FeedLoad, PodcastEpisodeInput, PodcastEpisodeType, PodcastFunding, PodcastInput,
+    PodcastPerson, PodcastTranscriptLink,
+};
+
+use crate::resource::TranscriptFormat;
+
+const MAX_REDIRECTS: usize = 10;
+/// Feeds and the documents they link to are read entirely in memory
+const MAX_DOCUMENT_SIZE: usize = 32 * 1024 * 1024;
+
+impl From<super::Person> for PodcastPerson {
+    fn from(value: super::Person) -> Self {
+        Self {
+            name: value.name,
+            role: value.role,
+            group: value.group,
+            href: value.href,
+            image_url: value.img,
+        }
+    }
+}
+
+impl From<super::Funding> for PodcastFunding {
+    fn from(value: super::Funding) -> Self {
+        Self {
+            url: value.url,
+            title: value.title,
+        }
+    }
+}
+
+impl From<super::Transcript> for PodcastTranscriptLink {
+    fn from(value: super::Transcript) -> Self {
+        Self {
+            url: value.url,
+            mime_type: value.mime_type,
+            language: value.language,
+        }
+    }
+}
+
+/// Picks the transcript in the preferred format, among the ones that can be read
+fn select_transcript(transcripts: Vec<super::Transcript>) -> Option<PodcastTranscriptLink> {
+    transcripts
+        .into_iter()
+        .filter_map(|item| {
+            TranscriptFormat::from_mime_type(&item.mime_type).map(|format| (format, item))
+        })
+        .min_by_key(|(format, _)| *format)
+        .map(|(_, item)| item.into())
+}
 
 impl TryFrom<super::ChannelItem> for PodcastEpisodeInput {
     type Error = anyhow::Error;
@@ -22,6 +73,21 @@ impl TryFrom<super::ChannelItem> for PodcastEpisodeInput {
                 .ok_or_else(|| anyhow::anyhow!("enclure_url attribute not specified"))?,
             file_size: value.enclosure_length,
             file_type: value.enclosure_type,
+            image_url: value.itunes_image_href,
+            season_number: value.itunes_season,
+            episode_number: value.itunes_episode,
+            episode_type: value.itunes_episode_type.and_then(|value| {
+                PodcastEpisodeType::from_str(&value)
+                    .inspect_err(|err| tracing::warn!(error = ?err, "unable to parse episode type"))
+                    .ok()
+            }),
+            chapters_url: value.podcast_chapters_url,
+            transcript: select_transcript(value.podcast_transcripts),
+            persons: value
+                .podcast_persons
+                .into_iter()
+                .map(PodcastPerson::from)
+                .collect(),
         })
     }
 }
@@ -50,26 +116,542 @@ impl TryFrom<super::Channel> for PodcastInput {
             image_url: value.image_url.or(value.itunes_image_href),
             language: value.language,
             website: value.link,
+            author: value.itunes_author,
+            categories: value.itunes_categories,
+            explicit: value.itunes_explicit,
+            persons: value
+                .podcast_persons
+                .into_iter()
+                .map(PodcastPerson::from)
+                .collect(),
+            funding: value
+                .podcast_funding
+                .into_iter()
+                .map(PodcastFunding::from)
+                .collect(),
+            new_feed_url: value.itunes_new_feed_url,
+            redirected_to: None,
+            feed_cache: FeedCache::default(),
+            episodes,
+        })
+    }
+}
+
+impl TryFrom<super::AtomEntry> for PodcastEpisodeInput {
+    type Error = anyhow::Error;
+
+    fn try_from(value: super::AtomEntry) -> Result<Self, Self::Error> {
+        Ok(PodcastEpisodeInput {
+            guid: value.id,
+            published_at: value.published.or(value.updated),
+            title: value
+                .title
+                .ok_or_else(|| anyhow::anyhow!("title element not specified"))?,
+            description: value.summary.or(value.content),
+            link: value.link_alternate,
+            duration: value.itunes_duration,
+            file_url: value
+                .enclosure_url
+                .ok_or_else(|| anyhow::anyhow!("enclosure link not specified"))?,
+            file_size: value.enclosure_length,
+            file_type: value.enclosure_type,
+            image_url: None,
+            season_number: None,
+            episode_number: None,
+            episode_type: None,
+            chapters_url: None,
+            transcript: None,
+            persons: Vec::new(),
+        })
+    }
+}
+
+impl TryFrom<super::AtomFeed> for PodcastInput {
+    type Error = anyhow::Error;
+
+    fn try_from(value: super::AtomFeed) -> Result<Self, Self::Error> {
+        let episodes = value
+            .entries
+            .into_iter()
+            .filter_map(|item| {
+                PodcastEpisodeInput::try_from(item)
+                    .inspect_err(|err| tracing::warn!(error = ?err, "unable to parse episode"))
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        Ok(PodcastInput {
+            feed_url: value
+                .link_self
+                .ok_or_else(|| anyhow::anyhow!("self link not specified"))?,
+            title: value
+                .title
+                .ok_or_else(|| anyhow::anyhow!("title element not specified"))?,
+            description: value.subtitle,
+            image_url: value.logo.or(value.icon),
+            language: value.language,
+            website: value.link_alternate,
+            author: None,
+            categories: Vec::new(),
+            explicit: None,
+            persons: Vec::new(),
+            funding: Vec::new(),
+            new_feed_url: None,
+            redirected_to: None,
+            feed_cache: FeedCache::default(),
             episodes,
         })
     }
 }
 
+impl super::Feed {
+    /// Uses the requested url when the feed doesn't advertise its own
+    fn or_feed_url(mut self, feed_url: &str) -> Self {
+        match &mut self {
+            super::Feed::Rss(rss) => {
+                for channel in rss.channels.iter_mut() {
+                    channel
+                        .atom_link_href
+                        .get_or_insert_with(|| feed_url.to_string());
+                }
+            }
+            super::Feed::Atom(feed) => {
+                feed.link_self.get_or_insert_with(|| feed_url.to_string());
+            }
+        }
+        self
+    }
+}
+
+impl TryFrom<super::Feed> for PodcastInput {
+    type Error = anyhow::Error;
+
+    fn try_from(value: super::Feed) -> Result<Self, Self::Error> {
+        match value {
+            super::Feed::Rss(mut rss) => {
+                let channel = rss
+                    .channels
+                    .pop()
+                    .ok_or_else(|| anyhow::anyhow!("empty rss feed"))?;
+                PodcastInput::try_from(channel)
+            }
+            super::Feed::Atom(feed) => PodcastInput::try_from(feed),
+        }
+    }
+}
+
+/// Reads the body, refusing the documents that are too large, whatever length they announce
+async fn read_body(mut res: reqwest::Response) -> anyhow::Result<String> {
+    if res
+        .content_length()
+        .is_some_and(|length| length > MAX_DOCUMENT_SIZE as u64)
+    {
+        anyhow::bail!("document is too large");
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = res.chunk().await.context("unable to query payload")? {
+        if body.len() + chunk.len() > MAX_DOCUMENT_SIZE {
+            anyhow::bail!("document is too large");
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+fn header_value(res: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+    res.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+}
+
+/// Document fetched from a url, along with what's needed to interpret it
+pub(crate) struct Fetched {
+    pub(crate) url: reqwest::Url,
+    /// Last url reached through permanent redirects only
+    pub(crate) moved_to: Option<reqwest::Url>,
+    pub(crate) content_type: Option<String>,
+    pub(crate) feed_cache: FeedCache,
+    pub(crate) body: String,
+}
+
+impl super::RssClient {
+    /// Fetches the url, following the redirects to keep track of the permanent ones
+    pub(crate) async fn fetch(
+        &self,
+        url: &str,
+        cache: &FeedCache,
+    ) -> anyhow::Result<Option<Fetched>> {
+        let mut url = reqwest::Url::parse(url).context("invalid url")?;
+        let mut moved_to = None;
+        let mut permanent = true;
+        let mut redirects = 0;
+        let res = loop {
+            // redirects included, the domain names being checked once resolved
+            entertainarr_adapter_public_http::check_url(url.as_str(), self.allow_private_hosts)?;
+            let mut req = self.client.get(url.clone());
+            // validators belong to the previous url, the moved feed needs to be read entirely
+            if moved_to.is_none() {
+                if let Some(etag) = cache.etag.as_deref() {
+                    req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+                }
+                if let Some(last_modified) = cache.last_modified.as_deref() {
+                    req = req.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+                }
+            }
+            let res = req.send().await.context("unable to query server")?;
+            if !res.status().is_redirection() || res.status() == reqwest::StatusCode::NOT_MODIFIED {
+                break res;
+            }
+            redirects += 1;
+            if redirects > MAX_REDIRECTS {
+                anyhow::bail!("too many redirects");
+            }
+            let location = res
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| anyhow::anyhow!("redirect without location"))?;
+            url = url.join(location).context("invalid redirect location")?;
+            permanent &= matches!(
+                res.status(),
+                reqwest::StatusCode::MOVED_PERMANENTLY | reqwest::StatusCode::PERMANENT_REDIRECT
+            );
+            if permanent {
+                moved_to = Some(url.clone());
+            }
+        };
+        if res.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        let res = res.error_for_status().context("unable to fetch document")?;
+        let url = res.url().clone();
+        let content_type = header_value(&res, reqwest::header::CONTENT_TYPE);
+        let feed_cache = FeedCache {
+            etag: header_value(&res, reqwest::header::ETAG),
+            last_modified: header_value(&res, reqwest::header::LAST_MODIFIED),
+        };
+        let body = read_body(res).await?;
+        Ok(Some(Fetched {
+            url,
+            moved_to,
+            content_type,
+            feed_cache,
+            body,
+        }))
+    }
+}
+
 impl entertainarr_domain::podcast::prelude::RssFeedLoader for super::RssClient {
-    #[tracing::instrument(skip(self), err(Debug))]
-    async fn load(&self, feed_url: &str) -> anyhow::Result<PodcastInput> {
-        let res = self
-            .client
-            .get(feed_url)
-            .send()
+    #[tracing::instrument(skip(self, cache), err(Debug))]
+    async fn load(&self, feed_url: &str, cache: &FeedCache) -> anyhow::Result<FeedLoad> {
+        let Some(mut fetched) = self.fetch(feed_url, cache).await? else {
+            return Ok(FeedLoad::NotModified);
+        };
+        let mut requested = feed_url.to_string();
+        if super::discovery::is_html(fetched.content_type.as_deref(), &fetched.body) {
+            let discovered = super::discovery::find_feed_url(&fetched.url, &fetched.body)
+                .ok_or_else(|| anyhow::anyhow!("no feed advertised by the html page"))?;
+            tracing::debug!(url = %discovered, "following advertised feed");
+            requested = discovered.to_string();
+            fetched = self
+                .fetch(&requested, &FeedCache::default())
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("feed reported as not modified without cache"))?;
+            if super::discovery::is_html(fetched.content_type.as_deref(), &fetched.body) {
+                anyhow::bail!("advertised feed is an html page");
+            }
+        }
+        let feed = super::Feed::from_str(fetched.body.as_str()).context("unable to parse feed")?;
+        let mut podcast = PodcastInput::try_from(feed.or_feed_url(&requested))?;
+        podcast.feed_cache = fetched.feed_cache;
+        podcast.redirected_to = fetched.moved_to.map(String::from);
+        Ok(FeedLoad::Modified(Box::new(podcast)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entertainarr_domain::podcast::entity::{FeedCache, FeedLoad};
+    use entertainarr_domain::podcast::prelude::RssFeedLoader;
+    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const FEED: &str = include_str!("../assets/podcast/rustacean-station.rss");
+
+    /// Serves the document on the given path
+    async fn serve_document(server: &MockServer, at: &str, content_type: &str, body: &str) {
+        Mock::given(method("GET"))
+            .and(path(at))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, content_type))
+            .mount(server)
+            .await;
+    }
+
+    /// Redirects the given path to the location
+    async fn serve_redirect(server: &MockServer, at: &str, status: u16, location: &str) {
+        Mock::given(method("GET"))
+            .and(path(at))
+            .respond_with(ResponseTemplate::new(status).insert_header("location", location))
+            .mount(server)
+            .await;
+    }
+
+    /// Client reaching the mock server, on the loopback
+    fn client() -> crate::RssClient {
+        crate::Config {
+            allow_private_hosts: true,
+        }
+        .build()
+        .unwrap()
+    }
+
+    /// Paths of the received requests, in order
+    async fn requested_paths(server: &MockServer) -> Vec<String> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|request| request.url.path().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_follow_feed_advertised_by_website() {
+        let server = MockServer::start().await;
+        let page = r#"<!DOCTYPE html><html><head>
+            <link rel="alternate" type="application/rss+xml" href="../podcast.rss">
+            </head><body></body></html>"#;
+        serve_document(&server, "/show/", "text/html", page).await;
+        serve_document(&server, "/podcast.rss", "application/rss+xml", FEED).await;
+        let client = client();
+
+        let page_url = format!("{}/show/", server.uri());
+        let loaded = client.load(&page_url, &FeedCache::default()).await;
+        assert_eq!(
+            requested_paths(&server).await,
+            vec!["/show/", "/podcast.rss"]
+        );
+        let FeedLoad::Modified(podcast) = loaded.unwrap() else {
+            panic!("expected modified feed");
+        };
+        assert_eq!(
+            podcast.feed_url,
+            "https://rustacean-station.org/podcast.rss"
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_website_has_no_feed() {
+        let server = MockServer::start().await;
+        let page = "<html><head><title>Nothing</title></head></html>";
+        serve_document(&server, "/", "text/html", page).await;
+        let client = client();
+
+        let page_url = format!("{}/", server.uri());
+        let err = client
+            .load(&page_url, &FeedCache::default())
             .await
-            .context("unable to query server")?;
-        let body = res.text().await.context("unable to query payload")?;
-        let mut rss = super::Rss::from_str(body.as_str()).context("unable to parse rss result")?;
-        let channel = rss
-            .channels
-            .pop()
-            .ok_or_else(|| anyhow::anyhow!("empty rss feed"))?;
-        PodcastInput::try_from(channel)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "no feed advertised by the html page");
+    }
+
+    #[tokio::test]
+    async fn should_record_permanent_redirect() {
+        let server = MockServer::start().await;
+        serve_redirect(&server, "/podcast.rss", 301, "/moved.rss").await;
+        serve_document(&server, "/moved.rss", "application/rss+xml", FEED).await;
+        let client = client();
+        let cache = FeedCache {
+            etag: Some("\"abcdef\"".into()),
+            last_modified: None,
+        };
+
+        let feed_url = format!("{}/podcast.rss", server.uri());
+        let loaded = client.load(&feed_url, &cache).await;
+        let requests = server.received_requests().await.unwrap();
+        // the validators of the previous url are not sent
+        assert!(requests[0].headers.contains_key("if-none-match"));
+        assert!(!requests[1].headers.contains_key("if-none-match"));
+        let FeedLoad::Modified(podcast) = loaded.unwrap() else {
+            panic!("expected modified feed");
+        };
+        assert_eq!(
+            podcast.redirected_to,
+            Some(format!("{}/moved.rss", server.uri()))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_ignore_temporary_redirect() {
+        let server = MockServer::start().await;
+        serve_redirect(&server, "/podcast.rss", 302, "/tracking/podcast.rss").await;
+        serve_redirect(&server, "/tracking/podcast.rss", 301, "/cdn/podcast.rss").await;
+        serve_document(&server, "/cdn/podcast.rss", "application/rss+xml", FEED).await;
+        let client = client();
+
+        let feed_url = format!("{}/podcast.rss", server.uri());
+        let loaded = client.load(&feed_url, &FeedCache::default()).await;
+        assert_eq!(
+            requested_paths(&server).await,
+            vec!["/podcast.rss", "/tracking/podcast.rss", "/cdn/podcast.rss"]
+        );
+        let FeedLoad::Modified(podcast) = loaded.unwrap() else {
+            panic!("expected modified feed");
+        };
+        assert!(podcast.redirected_to.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_refuse_private_hosts() {
+        let client = crate::Config::default().build().unwrap();
+        let err = client
+            .load("http://169.254.169.254/podcast.rss", &FeedCache::default())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "host of http://169.254.169.254/podcast.rss is not allowed"
+        );
+    }
+
+    #[tokio::test]
+    async fn should_refuse_too_large_documents() {
+        let server = MockServer::start().await;
+        let feed = "a".repeat(super::MAX_DOCUMENT_SIZE + 1);
+        serve_document(&server, "/podcast.rss", "application/rss+xml", &feed).await;
+        let client = client();
+
+        let feed_url = format!("{}/podcast.rss", server.uri());
+        let err = client
+            .load(&feed_url, &FeedCache::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "document is too large");
+    }
+
+    #[test]
+    fn should_read_itunes_new_feed_url() {
+        use std::str::FromStr;
+
+        let input = FEED.replacen(
+            "<channel>",
+            "<channel><itunes:new-feed-url>https://example.com/new.rss</itunes:new-feed-url>",
+            1,
+        );
+        let feed = crate::Feed::from_str(&input).unwrap();
+        let podcast = entertainarr_domain::podcast::entity::PodcastInput::try_from(feed).unwrap();
+        assert_eq!(
+            podcast.new_feed_url.as_deref(),
+            Some("https://example.com/new.rss")
+        );
+    }
+
+    #[test]
+    fn should_select_readable_transcript() {
+        let transcript = |url: &str, mime_type: &str| crate::Transcript {
+            url: url.into(),
+            mime_type: mime_type.into(),
+            language: None,
+        };
+        let selected = super::select_transcript(vec![
+            transcript("https://example.com/t.html", "text/html"),
+            transcript("https://example.com/t.json", "application/json"),
+            transcript("https://example.com/t.vtt", "text/vtt"),
+            transcript("https://example.com/t.srt", "application/srt"),
+        ])
+        .unwrap();
+        assert_eq!(selected.url, "https://example.com/t.vtt");
+        assert!(
+            super::select_transcript(vec![transcript("https://example.com/t.txt", "text/plain")])
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fallback_to_requested_url() {
+        let server = MockServer::start().await;
+        let feed = FEED.replace(
+            r#"<atom:link href="https://rustacean-station.org/podcast.rss" rel="self" type="application/rss+xml" />"#,
+            "",
+        );
+        assert_ne!(feed, FEED);
+        serve_document(&server, "/podcast.rss", "application/rss+xml", &feed).await;
+        let client = client();
+
+        let feed_url = format!("{}/podcast.rss", server.uri());
+        let loaded = client.load(&feed_url, &FeedCache::default()).await;
+        let FeedLoad::Modified(podcast) = loaded.unwrap() else {
+            panic!("expected modified feed");
+        };
+        assert_eq!(podcast.feed_url, feed_url);
+    }
+
+    #[tokio::test]
+    async fn should_load_conditionally() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/podcast.rss"))
+            .and(header_exists("if-none-match"))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/podcast.rss"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"abcdef\"")
+                    .insert_header("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")
+                    .set_body_raw(FEED, "application/rss+xml"),
+            )
+            .mount(&server)
+            .await;
+        let client = client();
+        let feed_url = format!("{}/podcast.rss", server.uri());
+
+        let loaded = client.load(&feed_url, &FeedCache::default()).await;
+        let FeedLoad::Modified(podcast) = loaded.unwrap() else {
+            panic!("expected modified feed");
+        };
+        assert_eq!(podcast.feed_cache.etag.as_deref(), Some("\"abcdef\""));
+        assert_eq!(
+            podcast.feed_cache.last_modified.as_deref(),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+
+        let loaded = client.load(&feed_url, &podcast.feed_cache).await;
+        assert!(matches!(loaded.unwrap(), FeedLoad::NotModified));
+        let requests = server.received_requests().await.unwrap();
+        assert!(!requests[0].headers.contains_key("if-none-match"));
+        assert_eq!(
+            requests[1].headers["if-modified-since"],
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+    }
+
+    #[test]
+    fn should_convert_atom_feed() {
+        use std::str::FromStr;
+
+        let input = include_str!("../assets/podcast/atom-example.xml");
+        let feed = crate::Feed::from_str(input).unwrap();
+        let podcast = entertainarr_domain::podcast::entity::PodcastInput::try_from(feed).unwrap();
+        assert_eq!(podcast.feed_url, "https://example.com/podcast.atom");
+        assert_eq!(podcast.title, "Example Atom Podcast");
+        assert_eq!(
+            podcast.image_url.as_deref(),
+            Some("https://example.com/logo.png")
+        );
+        // the announcement has no enclosure
+        assert_eq!(podcast.episodes.len(), 2);
+        assert_eq!(
+            podcast.episodes[1].published_at.unwrap().to_rfc3339(),
+            "2025-09-28T18:30:02+00:00"
+        );
+        assert_eq!(
+            podcast.episodes[1].description.as_deref(),
+            Some("<p>First episode</p>")
+        );
     }
 }
@@ -0,0 +1,312 @@
+//! Documents an episode refers to through the podcast namespace, like its chapters and transcript
+
+use std::time::Duration;
+
+use anyhow::Context;
+use entertainarr_domain::podcast::entity::{
+    FeedCache, PodcastChapter, PodcastTranscriptCue, PodcastTranscriptLink,
+};
+
+/// Transcript formats that can be read, ordered by preference
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum TranscriptFormat {
+    Vtt,
+    Srt,
+    Json,
+}
+
+impl TranscriptFormat {
+    pub(crate) fn from_mime_type(value: &str) -> Option<Self> {
+        let value = value.split(';').next().unwrap_or_default().trim();
+        if value.eq_ignore_ascii_case("text/vtt") {
+            Some(Self::Vtt)
+        } else if ["application/srt", "application/x-subrip", "text/srt"]
+            .iter()
+            .any(|expected| value.eq_ignore_ascii_case(expected))
+        {
+            Some(Self::Srt)
+        } else if value.eq_ignore_ascii_case("application/json") {
+            Some(Self::Json)
+        } else {
+            None
+        }
+    }
+}
+
+fn seconds(value: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(value).ok()
+}
+
+#[derive(serde::Deserialize)]
+struct ChaptersDocument {
+    chapters: Vec<ChaptersItem>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChaptersItem {
+    start_time: f64,
+    #[serde(default)]
+    end_time: Option<f64>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    img: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+/// Parses the JSON chapters format of the podcast namespace
+fn parse_chapters(input: &str) -> anyhow::Result<Vec<PodcastChapter>> {
+    let document: ChaptersDocument =
+        serde_json::from_str(input).context("unable to parse chapters")?;
+    let mut chapters = document
+        .chapters
+        .into_iter()
+        .filter_map(|item| {
+            Some(PodcastChapter {
+                start: seconds(item.start_time)?,
+                end: item.end_time.and_then(seconds),
+                title: item.title,
+                image_url: item.img,
+                url: item.url,
+            })
+        })
+        .collect::<Vec<_>>();
+    chapters.sort_by_key(|item| item.start);
+    Ok(chapters)
+}
+
+#[derive(serde::Deserialize)]
+struct TranscriptDocument {
+    segments: Vec<TranscriptSegment>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptSegment {
+    start_time: f64,
+    end_time: f64,
+    #[serde(default)]
+    speaker: Option<String>,
+    body: String,
+}
+
+/// Parses the JSON transcript format of the podcast namespace
+fn parse_json_transcript(input: &str) -> anyhow::Result<Vec<PodcastTranscriptCue>> {
+    let document: TranscriptDocument =
+        serde_json::from_str(input).context("unable to parse transcript")?;
+    Ok(document
+        .segments
+        .into_iter()
+        .filter_map(|item| {
+            Some(PodcastTranscriptCue {
+                start: seconds(item.start_time)?,
+                end: seconds(item.end_time)?,
+                speaker: item.speaker.filter(|value| !value.trim().is_empty()),
+                text: item.body,
+            })
+        })
+        .collect())
+}
+
+/// Parses `[hh:]mm:ss.mmm`, with a comma as decimal separator for SRT
+fn parse_timestamp(value: &str) -> Option<Duration> {
+    let value = value.trim().replace(',', ".");
+    let parts = value.split(':').collect::<Vec<_>>();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+    let mut result = 0.0;
+    for part in parts {
+        result = result * 60.0 + part.parse::<f64>().ok()?;
+    }
+    seconds(result)
+}
+
+fn parse_timing(line: &str) -> Option<(Duration, Duration)> {
+    let (start, rest) = line.split_once("-->")?;
+    // VTT cue settings follow the end timestamp
+    let end = rest.split_whitespace().next()?;
+    Some((parse_timestamp(start)?, parse_timestamp(end)?))
+}
+
+/// Takes the speaker from the `<v Speaker>` voice tag and removes the markup of the cue text
+fn parse_cue_text(text: &str) -> (Option<String>, String) {
+    let mut speaker = None;
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>').map(|index| start + index) else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let tag = &rest[start + 1..end];
+        if speaker.is_none() && (tag.starts_with("v ") || tag.starts_with("v.")) {
+            speaker = tag
+                .split_once(' ')
+                .map(|(_, name)| name.trim())
+                .filter(|name| !name.is_empty())
+                .map(String::from);
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    (
+        speaker,
+        super::discovery::decode_entities(result.trim()).replace("&nbsp;", " "),
+    )
+}
+
+/// Parses SRT and VTT documents, which only differ by their header and cue settings
+fn parse_text_transcript(input: &str) -> Vec<PodcastTranscriptCue> {
+    input
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .filter_map(|block| {
+            // skips the cue identifier, or the VTT header and notes
+            let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+            let (start, end) = parse_timing(lines.next()?)?;
+            let text = lines.collect::<Vec<_>>().join("\n");
+            let (speaker, text) = parse_cue_text(&text);
+            (!text.is_empty()).then_some(PodcastTranscriptCue {
+                start,
+                end,
+                speaker,
+                text,
+            })
+        })
+        .collect()
+}
+
+impl super::RssClient {
+    async fn fetch_document(&self, url: &str) -> anyhow::Result<String> {
+        self.fetch(url, &FeedCache::default())
+            .await?
+            .map(|fetched| fetched.body)
+            .ok_or_else(|| anyhow::anyhow!("document reported as not modified without cache"))
+    }
+}
+
+impl entertainarr_domain::podcast::prelude::PodcastEpisodeResourceLoader for super::RssClient {
+    #[tracing::instrument(skip(self), err(Debug))]
+    async fn load_chapters(&self, url: &str) -> anyhow::Result<Vec<PodcastChapter>> {
+        let body = self.fetch_document(url).await?;
+        parse_chapters(&body)
+    }
+
+    #[tracing::instrument(skip_all, fields(url = transcript.url), err(Debug))]
+    async fn load_transcript(
+        &self,
+        transcript: &PodcastTranscriptLink,
+    ) -> anyhow::Result<Vec<PodcastTranscriptCue>> {
+        let format = TranscriptFormat::from_mime_type(&transcript.mime_type).ok_or_else(|| {
+            anyhow::anyhow!("unsupported transcript type {:?}", transcript.mime_type)
+        })?;
+        let body = self.fetch_document(&transcript.url).await?;
+        match format {
+            TranscriptFormat::Json => parse_json_transcript(&body),
+            TranscriptFormat::Srt | TranscriptFormat::Vtt => Ok(parse_text_transcript(&body)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::TranscriptFormat;
+
+    #[test]
+    fn should_read_transcript_format() {
+        assert_eq!(
+            TranscriptFormat::from_mime_type("text/vtt; charset=utf-8"),
+            Some(TranscriptFormat::Vtt)
+        );
+        assert_eq!(
+            TranscriptFormat::from_mime_type("application/x-subrip"),
+            Some(TranscriptFormat::Srt)
+        );
+        assert_eq!(TranscriptFormat::from_mime_type("text/html"), None);
+        assert!(TranscriptFormat::Vtt < TranscriptFormat::Json);
+    }
+
+    #[test]
+    fn should_parse_chapters() {
+        let input = r#"{
+            "version": "1.2.0",
+            "chapters": [
+                {"startTime": 62.5, "title": "News", "url": "https://example.com/news"},
+                {"startTime": 0, "endTime": 62.5, "title": "Intro", "img": "https://example.com/intro.jpg", "toc": false},
+                {"startTime": -1, "title": "Invalid"}
+            ]
+        }"#;
+        let chapters = super::parse_chapters(input).unwrap();
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].title.as_deref(), Some("Intro"));
+        assert_eq!(chapters[0].start, Duration::ZERO);
+        assert_eq!(chapters[0].end, Some(Duration::from_secs_f64(62.5)));
+        assert_eq!(
+            chapters[0].image_url.as_deref(),
+            Some("https://example.com/intro.jpg")
+        );
+        assert_eq!(chapters[1].start, Duration::from_secs_f64(62.5));
+        assert_eq!(chapters[1].url.as_deref(), Some("https://example.com/news"));
+        assert!(super::parse_chapters("<html></html>").is_err());
+    }
+
+    #[test]
+    fn should_parse_srt_transcript() {
+        let input = "1\r\n00:00:00,500 --> 00:00:02,250\r\nHello and welcome\r\nto the show\r\n\r\n2\r\n00:00:03,000 --> 00:01:04,000\r\nThanks &amp; bye\r\n";
+        let cues = super::parse_text_transcript(input);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].start, Duration::from_millis(500));
+        assert_eq!(cues[0].end, Duration::from_millis(2250));
+        assert_eq!(cues[0].text, "Hello and welcome\nto the show");
+        assert_eq!(cues[1].end, Duration::from_secs(64));
+        assert_eq!(cues[1].text, "Thanks & bye");
+    }
+
+    #[test]
+    fn should_parse_vtt_transcript() {
+        let input = r#"WEBVTT
+
+NOTE recorded live
+
+intro
+00:00.000 --> 00:02.500 align:start
+<v Jane Doe>Hello <i>everyone</i></v>
+
+00:01:02.000 --> 00:01:05.000
+<v.host John>Hi Jane
+
+00:01:06.000 --> 00:01:07.000
+"#;
+        let cues = super::parse_text_transcript(input);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].start, Duration::ZERO);
+        assert_eq!(cues[0].end, Duration::from_millis(2500));
+        assert_eq!(cues[0].speaker.as_deref(), Some("Jane Doe"));
+        assert_eq!(cues[0].text, "Hello everyone");
+        assert_eq!(cues[1].start, Duration::from_secs(62));
+        assert_eq!(cues[1].speaker.as_deref(), Some("John"));
+        assert_eq!(cues[1].text, "Hi Jane");
+    }
+
+    #[test]
+    fn should_parse_json_transcript() {
+        let input = r#"{
+            "version": "1.0.0",
+            "segments": [
+                {"speaker": "Jane Doe", "startTime": 0.5, "endTime": 1.5, "body": "Hello"},
+                {"startTime": 1.5, "endTime": 2, "body": "world"}
+            ]
+        }"#;
+        let cues = super::parse_json_transcript(input).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].speaker.as_deref(), Some("Jane Doe"));
+        assert_eq!(cues[0].start, Duration::from_millis(500));
+        assert_eq!(cues[1].speaker, None);
+        assert_eq!(cues[1].text, "world");
+    }
+}
@@ -0,0 +1,2 @@
+alter table podcasts add column last_synced_at integer;
+alter table podcasts add column last_sync_error text;
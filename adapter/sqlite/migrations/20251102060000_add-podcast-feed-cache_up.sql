@@ -0,0 +1,2 @@
+alter table podcasts add column feed_etag text;
+alter table podcasts add column feed_last_modified text;
@@ -0,0 +1 @@
+alter table podcast_episodes add column removed_at integer;
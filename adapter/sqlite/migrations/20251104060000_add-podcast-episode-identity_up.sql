@@ -0,0 +1,30 @@
+-- identity of an episode within its podcast, being the guid when provided,
+-- or a combination of the enclosure url, title and publication date otherwise.
+-- see PodcastEpisodeInput::identity in the domain
+alter table podcast_episodes add column identity text not null default '';
+
+update podcast_episodes set identity = guid where guid is not null;
+update podcast_episodes
+set identity = file_url || char(10) || title || char(10) || coalesce(published_at, '')
+where guid is null;
+
+-- keep the progress of the users on the episode that will remain
+insert or ignore into user_podcast_episodes (user_id, podcast_episode_id, progress, completed, created_at, updated_at)
+select user_podcast_episodes.user_id, kept.id, user_podcast_episodes.progress, user_podcast_episodes.completed, user_podcast_episodes.created_at, user_podcast_episodes.updated_at
+from user_podcast_episodes
+join podcast_episodes on podcast_episodes.id = user_podcast_episodes.podcast_episode_id
+join (
+    select podcast_id, identity, min(id) as id
+    from podcast_episodes
+    group by podcast_id, identity
+) as kept on kept.podcast_id = podcast_episodes.podcast_id and kept.identity = podcast_episodes.identity
+where kept.id <> podcast_episodes.id;
+
+delete from podcast_episodes
+where id not in (
+    select min(id)
+    from podcast_episodes
+    group by podcast_id, identity
+);
+
+create unique index podcast_episodes_identity on podcast_episodes (podcast_id, identity);
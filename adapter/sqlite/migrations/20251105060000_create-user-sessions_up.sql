@@ -0,0 +1,11 @@
+create table user_sessions (
+    id integer not null primary key autoincrement,
+    user_id integer not null references users(id) on delete cascade,
+    refresh_token text unique not null,
+    expires_at integer not null,
+    revoked_at integer,
+    created_at integer not null default current_timestamp,
+    updated_at integer not null default current_timestamp
+);
+
+create index user_sessions_user_id on user_sessions (user_id);
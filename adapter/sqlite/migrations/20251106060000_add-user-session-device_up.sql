@@ -0,0 +1,4 @@
+alter table user_sessions add column device_name text;
+alter table user_sessions add column user_agent text;
+alter table user_sessions add column ip_address text;
+alter table user_sessions add column last_seen_at integer;
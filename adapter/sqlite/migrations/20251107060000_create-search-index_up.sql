@@ -0,0 +1,55 @@
+create virtual table podcasts_search using fts5 (
+    title,
+    description,
+    content='podcasts',
+    content_rowid='id',
+    tokenize='unicode61 remove_diacritics 2'
+);
+
+insert into podcasts_search (rowid, title, description)
+select id, title, description from podcasts;
+
+create trigger podcasts_search_insert after insert on podcasts begin
+    insert into podcasts_search (rowid, title, description)
+    values (new.id, new.title, new.description);
+end;
+
+create trigger podcasts_search_delete after delete on podcasts begin
+    insert into podcasts_search (podcasts_search, rowid, title, description)
+    values ('delete', old.id, old.title, old.description);
+end;
+
+create trigger podcasts_search_update after update of title, description on podcasts begin
+    insert into podcasts_search (podcasts_search, rowid, title, description)
+    values ('delete', old.id, old.title, old.description);
+    insert into podcasts_search (rowid, title, description)
+    values (new.id, new.title, new.description);
+end;
+
+create virtual table podcast_episodes_search using fts5 (
+    title,
+    description,
+    content='podcast_episodes',
+    content_rowid='id',
+    tokenize='unicode61 remove_diacritics 2'
+);
+
+insert into podcast_episodes_search (rowid, title, description)
+select id, title, description from podcast_episodes;
+
+create trigger podcast_episodes_search_insert after insert on podcast_episodes begin
+    insert into podcast_episodes_search (rowid, title, description)
+    values (new.id, new.title, new.description);
+end;
+
+create trigger podcast_episodes_search_delete after delete on podcast_episodes begin
+    insert into podcast_episodes_search (podcast_episodes_search, rowid, title, description)
+    values ('delete', old.id, old.title, old.description);
+end;
+
+create trigger podcast_episodes_search_update after update of title, description on podcast_episodes begin
+    insert into podcast_episodes_search (podcast_episodes_search, rowid, title, description)
+    values ('delete', old.id, old.title, old.description);
+    insert into podcast_episodes_search (rowid, title, description)
+    values (new.id, new.title, new.description);
+end;
@@ -0,0 +1,8 @@
+-- previous feed urls of a podcast, kept when its feed moves to another url
+create table podcast_feed_aliases (
+    feed_url text not null primary key,
+    podcast_id integer not null references podcasts(id) on delete cascade,
+    created_at integer not null default current_timestamp
+);
+
+create index podcast_feed_aliases_podcast_id on podcast_feed_aliases (podcast_id);
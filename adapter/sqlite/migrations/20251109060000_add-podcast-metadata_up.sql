@@ -0,0 +1,9 @@
+alter table podcasts add column author text;
+-- newline separated list of categories
+alter table podcasts add column categories text;
+alter table podcasts add column explicit boolean;
+
+alter table podcast_episodes add column image_url text;
+alter table podcast_episodes add column season_number integer;
+alter table podcast_episodes add column episode_number integer;
+alter table podcast_episodes add column episode_type text;
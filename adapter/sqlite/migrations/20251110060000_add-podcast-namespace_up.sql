@@ -0,0 +1,19 @@
+-- values of the podcast namespace, persons and funding are stored as json arrays
+alter table podcasts add column persons text;
+alter table podcasts add column funding text;
+
+alter table podcast_episodes add column chapters_url text;
+alter table podcast_episodes add column transcript_url text;
+alter table podcast_episodes add column transcript_type text;
+alter table podcast_episodes add column transcript_language text;
+alter table podcast_episodes add column persons text;
+
+-- documents fetched on demand from the url of an episode, stored as json
+create table podcast_episode_resources (
+    podcast_episode_id integer not null references podcast_episodes(id) on delete cascade,
+    kind text not null,
+    url text not null,
+    content text not null,
+    created_at integer not null default current_timestamp,
+    primary key (podcast_episode_id, kind)
+);
@@ -0,0 +1,22 @@
+-- number of latest episodes of a podcast kept downloaded on the server
+create table podcast_download_policies (
+    podcast_id integer not null primary key references podcasts(id) on delete cascade,
+    latest integer not null,
+    created_at integer not null default current_timestamp,
+    updated_at integer not null default current_timestamp
+);
+
+-- download queue, the file path is relative to the media directory
+create table podcast_episode_downloads (
+    podcast_episode_id integer not null primary key references podcast_episodes(id) on delete cascade,
+    status text not null default 'pending',
+    attempts integer not null default 0,
+    file_path text,
+    file_size integer,
+    last_error text,
+    next_attempt_at integer,
+    created_at integer not null default current_timestamp,
+    updated_at integer not null default current_timestamp
+);
+
+create index podcast_episode_downloads_status on podcast_episode_downloads (status, next_attempt_at);
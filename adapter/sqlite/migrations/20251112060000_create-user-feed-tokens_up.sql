@@ -0,0 +1,5 @@
+create table user_feed_tokens (
+    user_id integer not null primary key references users(id) on delete cascade,
+    token text unique not null,
+    created_at integer not null default current_timestamp
+);
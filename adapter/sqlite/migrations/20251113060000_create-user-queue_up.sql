@@ -0,0 +1,13 @@
+create table user_queue (
+    user_id integer not null references users(id) on delete cascade,
+    podcast_episode_id integer not null references podcast_episodes(id) on delete cascade,
+    position integer not null,
+    created_at integer not null default current_timestamp,
+    primary key(user_id, podcast_episode_id)
+);
+
+create index user_queue_user_id_position on user_queue (user_id, position);
+
+alter table user_podcasts add column auto_queue boolean not null default false;
+-- episodes up to that identifier were already considered for the queue
+alter table user_podcasts add column auto_queue_after integer not null default 0;
@@ -0,0 +1,8 @@
+-- preferences of the user for a podcast, applied by the clients
+alter table user_podcasts add column playback_speed real not null default 1.0;
+alter table user_podcasts add column skip_start integer not null default 0;
+alter table user_podcasts add column skip_end integer not null default 0;
+-- number of latest episodes kept downloaded, on top of the policy of the podcast
+alter table user_podcasts add column auto_download integer not null default 0;
+alter table user_podcasts add column include_in_home boolean not null default true;
+alter table user_podcasts add column notify boolean not null default false;
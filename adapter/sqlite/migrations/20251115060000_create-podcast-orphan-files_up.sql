@@ -0,0 +1,5 @@
+-- files of the media directory whose download was dropped, removed by the download job
+create table podcast_orphan_files (
+    file_path text not null primary key,
+    created_at integer not null default current_timestamp
+);
@@ -1,10 +1,11 @@
 use anyhow::Context;
 
-use entertainarr_domain::auth::entity::Profile;
+use entertainarr_domain::auth::entity::{Credentials, Profile};
 use entertainarr_domain::auth::prelude::SignupError;
 
-const FIND_BY_CREDS_QUERY: &str = "select id from users where email = ? limit 1";
+const FIND_BY_EMAIL_QUERY: &str = "select id, password from users where email = ? limit 1";
 const CREATE_QUERY: &str = "insert into users (email, password) values (?, ?) returning id";
+const UPDATE_PASSWORD_QUERY: &str = "update users set password = ? where id = ?";
 
 impl entertainarr_domain::auth::prelude::AuthenticationRepository for super::Pool {
     #[tracing::instrument(
@@ -15,7 +16,7 @@ impl entertainarr_domain::auth::prelude::AuthenticationRepository for super::Poo
             db.name = "authentication",
             db.operation = "SELECT",
             db.sql.table = "users",
-            db.query.text = FIND_BY_CREDS_QUERY,
+            db.query.text = FIND_BY_EMAIL_QUERY,
             db.response.returned_rows = tracing::field::Empty,
             error.type = tracing::field::Empty,
             error.message = tracing::field::Empty,
@@ -23,20 +24,15 @@ impl entertainarr_domain::auth::prelude::AuthenticationRepository for super::Poo
         ),
         err(Debug),
     )]
-    async fn find_by_credentials(
-        &self,
-        email: &str,
-        password: &str,
-    ) -> anyhow::Result<Option<Profile>> {
-        sqlx::query_as(FIND_BY_CREDS_QUERY)
+    async fn find_by_email(&self, email: &str) -> anyhow::Result<Option<Credentials>> {
+        sqlx::query_as(FIND_BY_EMAIL_QUERY)
             .bind(email)
-            .bind(password)
             .fetch_optional(&self.0)
             .await
             .inspect(super::record_optional)
             .inspect_err(super::record_error)
             .map(super::Wrapper::maybe_inner)
-            .context("unable to fetch profile by credentials")
+            .context("unable to fetch credentials by email")
     }
 
     #[tracing::instrument(
@@ -69,6 +65,33 @@ impl entertainarr_domain::auth::prelude::AuthenticationRepository for super::Poo
                 _ => SignupError::Internal(anyhow::Error::from(err)),
             })
     }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "UPDATE",
+            db.sql.table = "users",
+            db.query.text = UPDATE_PASSWORD_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn update_password(&self, user_id: u64, password: &str) -> anyhow::Result<()> {
+        sqlx::query(UPDATE_PASSWORD_QUERY)
+            .bind(password)
+            .bind(user_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .context("unable to update password")?;
+        Ok(())
+    }
 }
 
 impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow>
@@ -84,23 +107,33 @@ impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow>
     }
 }
 
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<Credentials> {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        Ok(Self(Credentials {
+            profile: Profile {
+                id: row.try_get(0)?,
+            },
+            password_hash: row.try_get(1)?,
+        }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use entertainarr_domain::auth::prelude::{AuthenticationRepository, SignupError};
 
     #[tokio::test]
-    async fn should_not_find_user_by_creds_when_missing() {
+    async fn should_not_find_user_by_email_when_missing() {
         let tmpdir = tempfile::tempdir().unwrap();
         let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
-        let res = pool
-            .find_by_credentials("user@example.com", "password")
-            .await
-            .unwrap();
+        let res = pool.find_by_email("user@example.com").await.unwrap();
         assert!(res.is_none());
     }
 
     #[tokio::test]
-    async fn should_find_user_by_creds_when_exists() {
+    async fn should_find_user_by_email_when_exists() {
         let tmpdir = tempfile::tempdir().unwrap();
         let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
         sqlx::query("insert into users (email, password) values ('user@example.com', 'password')")
@@ -108,10 +141,26 @@ mod tests {
             .await
             .unwrap();
         let res = pool
-            .find_by_credentials("user@example.com", "password")
+            .find_by_email("user@example.com")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.profile.id, 1);
+        assert_eq!(res.password_hash, "password");
+    }
+
+    #[tokio::test]
+    async fn should_update_password() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        let profile = pool.create("user@example.com", "password").await.unwrap();
+        pool.update_password(profile.id, "other").await.unwrap();
+        let res = pool
+            .find_by_email("user@example.com")
             .await
+            .unwrap()
             .unwrap();
-        assert!(res.is_some());
+        assert_eq!(res.password_hash, "other");
     }
 
     #[tokio::test]
@@ -0,0 +1,123 @@
+use anyhow::Context;
+
+const UPSERT_QUERY: &str = r#"insert into user_feed_tokens (user_id, token)
+values (?, ?)
+on conflict (user_id) do update set token = excluded.token, created_at = CURRENT_TIMESTAMP"#;
+const DELETE_QUERY: &str = "delete from user_feed_tokens where user_id = ?";
+const EXISTS_QUERY: &str = "select 1 from user_feed_tokens where user_id = ? and token = ? limit 1";
+
+impl entertainarr_domain::auth::prelude::FeedTokenRepository for super::Pool {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "INSERT",
+            db.sql.table = "user_feed_tokens",
+            db.query.text = UPSERT_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn upsert(&self, user_id: u64, token: &str) -> anyhow::Result<()> {
+        sqlx::query(UPSERT_QUERY)
+            .bind(user_id as i64)
+            .bind(token)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to upsert feed token")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "DELETE",
+            db.sql.table = "user_feed_tokens",
+            db.query.text = DELETE_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn delete(&self, user_id: u64) -> anyhow::Result<bool> {
+        sqlx::query(DELETE_QUERY)
+            .bind(user_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|res| res.rows_affected() > 0)
+            .context("unable to delete feed token")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "SELECT",
+            db.sql.table = "user_feed_tokens",
+            db.query.text = EXISTS_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn exists(&self, user_id: u64, token: &str) -> anyhow::Result<bool> {
+        sqlx::query(EXISTS_QUERY)
+            .bind(user_id as i64)
+            .bind(token)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(|row| row.is_some())
+            .context("unable to check feed token")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entertainarr_domain::auth::prelude::{AuthenticationRepository, FeedTokenRepository};
+
+    #[tokio::test]
+    async fn should_rotate_feed_token() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        let profile = AuthenticationRepository::create(&pool, "user@example.com", "password")
+            .await
+            .unwrap();
+        assert!(!pool.exists(profile.id, "first").await.unwrap());
+
+        pool.upsert(profile.id, "first").await.unwrap();
+        assert!(pool.exists(profile.id, "first").await.unwrap());
+        assert!(!pool.exists(profile.id + 1, "first").await.unwrap());
+
+        pool.upsert(profile.id, "second").await.unwrap();
+        assert!(!pool.exists(profile.id, "first").await.unwrap());
+        assert!(pool.exists(profile.id, "second").await.unwrap());
+
+        assert!(
+            FeedTokenRepository::delete(&pool, profile.id)
+                .await
+                .unwrap()
+        );
+        assert!(!pool.exists(profile.id, "second").await.unwrap());
+        assert!(
+            !FeedTokenRepository::delete(&pool, profile.id)
+                .await
+                .unwrap()
+        );
+    }
+}
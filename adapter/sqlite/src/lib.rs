@@ -3,8 +3,14 @@ use std::borrow::Cow;
 use anyhow::Context;
 
 mod auth;
+mod feed_token;
 mod podcast;
+mod podcast_download;
 mod podcast_episode;
+mod podcast_namespace;
+mod podcast_queue;
+mod search;
+mod session;
 
 #[derive(serde::Deserialize)]
 pub struct Config {
@@ -3,30 +3,346 @@ use std::collections::BTreeSet;
 use anyhow::Context;
 use tracing::Instrument;
 
-use crate::Wrapper;
-use entertainarr_domain::podcast::entity::{Podcast, PodcastInput};
+use crate::{Wrapper, podcast_namespace};
+use entertainarr_domain::podcast::entity::{
+    FeedCache, Podcast, PodcastInput, PodcastSubscription, PodcastSubscriptionSettings,
+    PodcastSubscriptionSettingsUpdate, SubscribedPodcast,
+};
 
-const FIND_PODCAST_BY_FEED_URL_QUERY: &str = "select id, feed_url, title, description, image_url, language, website, created_at, updated_at from podcasts where feed_url like ? limit 1";
-const UPSERT_PODCAST_QUERY: &str = r#"insert into podcasts (feed_url, title, description, image_url, language, website)
-values (?, ?, ?, ?, ?, ?)
+const FIND_PODCAST_BY_FEED_URL_QUERY: &str = r#"select id, feed_url, title, description, image_url, language, website, last_synced_at, last_sync_error, feed_etag, feed_last_modified, created_at, updated_at, author, categories, explicit, persons, funding from podcasts
+where feed_url like ?1
+    or exists (select 1 from podcast_feed_aliases where podcast_feed_aliases.podcast_id = podcasts.id and podcast_feed_aliases.feed_url = ?1)
+limit 1"#;
+const FIND_PODCAST_BY_ID_QUERY: &str = "select id, feed_url, title, description, image_url, language, website, last_synced_at, last_sync_error, feed_etag, feed_last_modified, created_at, updated_at, author, categories, explicit, persons, funding from podcasts where id = ? limit 1";
+const FIND_OTHER_PODCAST_ID_BY_FEED_URL_QUERY: &str =
+    "select id from podcasts where feed_url = ? and id <> ? limit 1";
+const INSERT_PODCAST_FEED_ALIAS_QUERY: &str = "insert or ignore into podcast_feed_aliases (feed_url, podcast_id) select feed_url, ?1 from podcasts where id = ?2";
+const DELETE_PODCAST_FEED_ALIAS_QUERY: &str = "delete from podcast_feed_aliases where feed_url = ?";
+const UPDATE_PODCAST_FEED_URL_QUERY: &str = r#"update podcasts set feed_url = ?, feed_etag = null, feed_last_modified = null, updated_at = CURRENT_TIMESTAMP
+where id = ?
+returning id, feed_url, title, description, image_url, language, website, last_synced_at, last_sync_error, feed_etag, feed_last_modified, created_at, updated_at, author, categories, explicit, persons, funding"#;
+const MERGE_USER_PODCAST_QUERY: &str = r#"insert or ignore into user_podcasts (user_id, podcast_id, created_at, playback_speed, skip_start, skip_end, auto_download, include_in_home, notify, auto_queue, auto_queue_after)
+select user_id, ?1, created_at, playback_speed, skip_start, skip_end, auto_download, include_in_home, notify, auto_queue, auto_queue_after from user_podcasts where podcast_id = ?2"#;
+const MERGE_USER_PODCAST_EPISODE_QUERY: &str = r#"insert or ignore into user_podcast_episodes (user_id, podcast_episode_id, progress, completed, created_at, updated_at)
+select user_podcast_episodes.user_id, target.id, user_podcast_episodes.progress, user_podcast_episodes.completed, user_podcast_episodes.created_at, user_podcast_episodes.updated_at
+from user_podcast_episodes
+join podcast_episodes as source on source.id = user_podcast_episodes.podcast_episode_id
+join podcast_episodes as target on target.podcast_id = ?1 and target.identity = source.identity
+where source.podcast_id = ?2"#;
+const MERGE_USER_QUEUE_QUERY: &str = r#"update or ignore user_queue set podcast_episode_id = (
+    select target.id from podcast_episodes as source
+    join podcast_episodes as target on target.podcast_id = ?1 and target.identity = source.identity
+    where source.id = user_queue.podcast_episode_id
+)
+where podcast_episode_id in (
+    select source.id from podcast_episodes as source
+    join podcast_episodes as target on target.podcast_id = ?1 and target.identity = source.identity
+    where source.podcast_id = ?2
+)"#;
+const MERGE_PODCAST_DOWNLOAD_POLICY_QUERY: &str = r#"insert into podcast_download_policies (podcast_id, latest)
+select ?1, latest from podcast_download_policies where podcast_id = ?2
+on conflict (podcast_id) do update set
+    latest = max(podcast_download_policies.latest, excluded.latest),
+    updated_at = CURRENT_TIMESTAMP"#;
+/// The episodes already downloaded for the target keep their file
+const MERGE_PODCAST_EPISODE_DOWNLOAD_QUERY: &str = r#"update or ignore podcast_episode_downloads set podcast_episode_id = (
+    select target.id from podcast_episodes as source
+    join podcast_episodes as target on target.podcast_id = ?1 and target.identity = source.identity
+    where source.id = podcast_episode_downloads.podcast_episode_id
+)
+where podcast_episode_id in (
+    select source.id from podcast_episodes as source
+    join podcast_episodes as target on target.podcast_id = ?1 and target.identity = source.identity
+    where source.podcast_id = ?2
+)"#;
+/// Once the other episodes moved, the downloads left are deleted along with the podcast
+const INSERT_PODCAST_ORPHAN_FILE_QUERY: &str = r#"insert or ignore into podcast_orphan_files (file_path)
+select podcast_episode_downloads.file_path from podcast_episode_downloads
+join podcast_episodes on podcast_episodes.id = podcast_episode_downloads.podcast_episode_id
+where podcast_episodes.podcast_id = ?2
+    and podcast_episode_downloads.file_path is not null"#;
+const MERGE_PODCAST_EPISODE_QUERY: &str = r#"update podcast_episodes set podcast_id = ?1
+where podcast_id = ?2
+    and identity not in (select identity from podcast_episodes where podcast_id = ?1)"#;
+const MERGE_PODCAST_FEED_ALIAS_QUERY: &str =
+    "update podcast_feed_aliases set podcast_id = ?1 where podcast_id = ?2";
+const DELETE_PODCAST_QUERY: &str = "delete from podcasts where id = ?";
+const UPSERT_PODCAST_QUERY: &str = r#"insert into podcasts (feed_url, title, description, image_url, language, website, author, categories, explicit, persons, funding, feed_etag, feed_last_modified)
+values (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 on conflict (feed_url) do update set
     title=excluded.title,
     description=excluded.description,
     image_url=excluded.image_url,
     language=excluded.language,
     website=excluded.website,
+    author=excluded.author,
+    categories=excluded.categories,
+    explicit=excluded.explicit,
+    persons=excluded.persons,
+    funding=excluded.funding,
+    feed_etag=excluded.feed_etag,
+    feed_last_modified=excluded.feed_last_modified,
     updated_at=CURRENT_TIMESTAMP
-returning id, feed_url, title, description, image_url, language, website, created_at, updated_at"#;
-const LIST_PODCAST_QUERY: &str = r#"select podcasts.id, podcasts.feed_url, podcasts.title, podcasts.description, podcasts.image_url, podcasts.language, podcasts.website, podcasts.created_at, podcasts.updated_at
+returning id, feed_url, title, description, image_url, language, website, last_synced_at, last_sync_error, feed_etag, feed_last_modified, created_at, updated_at, author, categories, explicit, persons, funding"#;
+/// Lowest limit of bound parameters of the SQLite builds
+const MAX_BINDS: usize = 999;
+/// Each episode binds 20 parameters
+const EPISODE_BATCH_SIZE: usize = MAX_BINDS / 20;
+const LIST_ACTIVE_EPISODE_IDENTITY_QUERY: &str =
+    "select id, identity from podcast_episodes where podcast_id = ? and removed_at is null";
+const UPSERT_PODCAST_EPISODE_CONFLICT: &str = r#" on conflict (podcast_id, identity) do update set
+    guid=excluded.guid,
+    published_at=excluded.published_at,
+    title=excluded.title,
+    description=excluded.description,
+    link=excluded.link,
+    duration=excluded.duration,
+    file_url=excluded.file_url,
+    file_size=excluded.file_size,
+    file_type=excluded.file_type,
+    image_url=excluded.image_url,
+    season_number=excluded.season_number,
+    episode_number=excluded.episode_number,
+    episode_type=excluded.episode_type,
+    chapters_url=excluded.chapters_url,
+    transcript_url=excluded.transcript_url,
+    transcript_type=excluded.transcript_type,
+    transcript_language=excluded.transcript_language,
+    persons=excluded.persons,
+    removed_at=null,
+    updated_at=CURRENT_TIMESTAMP
+where podcast_episodes.guid is not excluded.guid
+    or podcast_episodes.published_at is not excluded.published_at
+    or podcast_episodes.title is not excluded.title
+    or podcast_episodes.description is not excluded.description
+    or podcast_episodes.link is not excluded.link
+    or podcast_episodes.duration is not excluded.duration
+    or podcast_episodes.file_url is not excluded.file_url
+    or podcast_episodes.file_size is not excluded.file_size
+    or podcast_episodes.file_type is not excluded.file_type
+    or podcast_episodes.image_url is not excluded.image_url
+    or podcast_episodes.season_number is not excluded.season_number
+    or podcast_episodes.episode_number is not excluded.episode_number
+    or podcast_episodes.episode_type is not excluded.episode_type
+    or podcast_episodes.chapters_url is not excluded.chapters_url
+    or podcast_episodes.transcript_url is not excluded.transcript_url
+    or podcast_episodes.transcript_type is not excluded.transcript_type
+    or podcast_episodes.transcript_language is not excluded.transcript_language
+    or podcast_episodes.persons is not excluded.persons
+    or podcast_episodes.removed_at is not null"#;
+const LIST_PODCAST_QUERY: &str = r#"select podcasts.id, podcasts.feed_url, podcasts.title, podcasts.description, podcasts.image_url, podcasts.language, podcasts.website, podcasts.last_synced_at, podcasts.last_sync_error, podcasts.feed_etag, podcasts.feed_last_modified, podcasts.created_at, podcasts.updated_at, podcasts.author, podcasts.categories, podcasts.explicit, podcasts.persons, podcasts.funding,
+    user_podcasts.user_id, user_podcasts.podcast_id, user_podcasts.created_at, user_podcasts.auto_queue, user_podcasts.playback_speed, user_podcasts.skip_start, user_podcasts.skip_end, user_podcasts.auto_download, user_podcasts.include_in_home, user_podcasts.notify
 from podcasts
 join user_podcasts on podcasts.id = user_podcasts.podcast_id
 where user_podcasts.user_id = ?
 order by podcasts.title"#;
+const LIST_SUBSCRIBED_PODCAST_QUERY: &str = r#"select id, feed_url, title, description, image_url, language, website, last_synced_at, last_sync_error, feed_etag, feed_last_modified, created_at, updated_at, author, categories, explicit, persons, funding
+from podcasts
+where exists (select 1 from user_podcasts where user_podcasts.podcast_id = podcasts.id)
+order by id"#;
+const UPDATE_PODCAST_SYNC_SUCCESS_QUERY: &str =
+    "update podcasts set last_synced_at = CURRENT_TIMESTAMP, last_sync_error = null where id = ?";
+const UPDATE_PODCAST_SYNC_FAILURE_QUERY: &str =
+    "update podcasts set last_sync_error = ? where id = ?";
 const UPSERT_USER_PODCAST_QUERY: &str = "insert into user_podcasts (user_id, podcast_id) values (?, ?) on conflict (user_id, podcast_id) do nothing";
+const FIND_USER_PODCAST_QUERY: &str = "select user_id, podcast_id, created_at, auto_queue, playback_speed, skip_start, skip_end, auto_download, include_in_home, notify from user_podcasts where user_id = ? and podcast_id = ?";
+const UPDATE_USER_PODCAST_SETTINGS_QUERY: &str = r#"update user_podcasts set
+    playback_speed = coalesce(?, playback_speed),
+    skip_start = coalesce(?, skip_start),
+    skip_end = coalesce(?, skip_end),
+    auto_download = coalesce(?, auto_download),
+    include_in_home = coalesce(?, include_in_home),
+    notify = coalesce(?, notify)
+where user_id = ? and podcast_id = ?
+returning user_id, podcast_id, created_at, auto_queue, playback_speed, skip_start, skip_end, auto_download, include_in_home, notify"#;
 const DELETE_USER_PODCAST_QUERY: &str =
     "delete from user_podcasts where user_id = ? and podcast_id = ?";
 
+fn move_span(operation: &str, table: &str, query: &str) -> tracing::Span {
+    tracing::info_span!(
+        "podcasts.move_feed_url",
+        otel.kind = "client",
+        db.system = "sqlite",
+        db.name = "podcast",
+        db.operation = operation,
+        db.sql.table = table,
+        db.query.text = query,
+        db.response.returned_rows = tracing::field::Empty,
+        error.type = tracing::field::Empty,
+        error.message = tracing::field::Empty,
+        error.stacktrace = tracing::field::Empty,
+    )
+}
+
+impl super::Pool {
+    /// Moves the podcast to the feed url, merging it into the podcast already using that url
+    /// only when allowed
+    async fn move_podcast_feed_url(
+        &self,
+        podcast_id: u64,
+        feed_url: &str,
+        merge: bool,
+    ) -> anyhow::Result<Option<Podcast>> {
+        let mut tx = self
+            .0
+            .begin()
+            .await
+            .context("unable to begin transaction")?;
+
+        let target: Option<(i64,)> = sqlx::query_as(FIND_OTHER_PODCAST_ID_BY_FEED_URL_QUERY)
+            .bind(feed_url)
+            .bind(podcast_id as i64)
+            .fetch_optional(&mut *tx)
+            .instrument(move_span(
+                "SELECT",
+                "podcasts",
+                FIND_OTHER_PODCAST_ID_BY_FEED_URL_QUERY,
+            ))
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .context("unable to find podcast by feed url")?;
+
+        let podcast: Podcast = if let Some((target_id,)) = target {
+            if !merge {
+                return Ok(None);
+            }
+            // another podcast already reads the new feed, everything goes there
+            let statements = [
+                ("INSERT", "user_podcasts", MERGE_USER_PODCAST_QUERY),
+                (
+                    "INSERT",
+                    "user_podcast_episodes",
+                    MERGE_USER_PODCAST_EPISODE_QUERY,
+                ),
+                ("UPDATE", "user_queue", MERGE_USER_QUEUE_QUERY),
+                (
+                    "INSERT",
+                    "podcast_download_policies",
+                    MERGE_PODCAST_DOWNLOAD_POLICY_QUERY,
+                ),
+                (
+                    "UPDATE",
+                    "podcast_episode_downloads",
+                    MERGE_PODCAST_EPISODE_DOWNLOAD_QUERY,
+                ),
+                ("UPDATE", "podcast_episodes", MERGE_PODCAST_EPISODE_QUERY),
+                (
+                    "INSERT",
+                    "podcast_orphan_files",
+                    INSERT_PODCAST_ORPHAN_FILE_QUERY,
+                ),
+                (
+                    "UPDATE",
+                    "podcast_feed_aliases",
+                    MERGE_PODCAST_FEED_ALIAS_QUERY,
+                ),
+                (
+                    "INSERT",
+                    "podcast_feed_aliases",
+                    INSERT_PODCAST_FEED_ALIAS_QUERY,
+                ),
+            ];
+            for (operation, table, query) in statements {
+                sqlx::query(query)
+                    .bind(target_id)
+                    .bind(podcast_id as i64)
+                    .execute(&mut *tx)
+                    .instrument(move_span(operation, table, query))
+                    .await
+                    .inspect_err(super::record_error)
+                    .with_context(|| format!("unable to merge {table}"))?;
+            }
+            sqlx::query(DELETE_PODCAST_QUERY)
+                .bind(podcast_id as i64)
+                .execute(&mut *tx)
+                .instrument(move_span("DELETE", "podcasts", DELETE_PODCAST_QUERY))
+                .await
+                .inspect_err(super::record_error)
+                .context("unable to delete merged podcast")?;
+            sqlx::query_as(FIND_PODCAST_BY_ID_QUERY)
+                .bind(target_id)
+                .fetch_one(&mut *tx)
+                .instrument(move_span("SELECT", "podcasts", FIND_PODCAST_BY_ID_QUERY))
+                .await
+                .inspect(super::record_one)
+                .inspect_err(super::record_error)
+                .map(Wrapper::inner)
+                .context("unable to query podcasts by id")?
+        } else {
+            sqlx::query(INSERT_PODCAST_FEED_ALIAS_QUERY)
+                .bind(podcast_id as i64)
+                .bind(podcast_id as i64)
+                .execute(&mut *tx)
+                .instrument(move_span(
+                    "INSERT",
+                    "podcast_feed_aliases",
+                    INSERT_PODCAST_FEED_ALIAS_QUERY,
+                ))
+                .await
+                .inspect_err(super::record_error)
+                .context("unable to create feed alias")?;
+            // the feed could be moving back to a previous url
+            sqlx::query(DELETE_PODCAST_FEED_ALIAS_QUERY)
+                .bind(feed_url)
+                .execute(&mut *tx)
+                .instrument(move_span(
+                    "DELETE",
+                    "podcast_feed_aliases",
+                    DELETE_PODCAST_FEED_ALIAS_QUERY,
+                ))
+                .await
+                .inspect_err(super::record_error)
+                .context("unable to delete feed alias")?;
+            sqlx::query_as(UPDATE_PODCAST_FEED_URL_QUERY)
+                .bind(feed_url)
+                .bind(podcast_id as i64)
+                .fetch_one(&mut *tx)
+                .instrument(move_span(
+                    "UPDATE",
+                    "podcasts",
+                    UPDATE_PODCAST_FEED_URL_QUERY,
+                ))
+                .await
+                .inspect(super::record_one)
+                .inspect_err(super::record_error)
+                .map(Wrapper::inner)
+                .context("unable to update podcast feed url")?
+        };
+
+        tx.commit().await.context("unable to commit transaction")?;
+        Ok(Some(podcast))
+    }
+}
+
 impl entertainarr_domain::podcast::prelude::PodcastRepository for super::Pool {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcasts",
+            db.query.text = FIND_PODCAST_BY_ID_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn find_by_id(&self, podcast_id: u64) -> anyhow::Result<Option<Podcast>> {
+        sqlx::query_as(FIND_PODCAST_BY_ID_QUERY)
+            .bind(podcast_id as i64)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(Wrapper::maybe_inner)
+            .context("unable to query podcasts by id")
+    }
+
     #[tracing::instrument(
         skip_all,
         fields(
@@ -54,6 +370,21 @@ impl entertainarr_domain::podcast::prelude::PodcastRepository for super::Pool {
             .context("unable to query podcasts by feed url")
     }
 
+    async fn move_feed_url(
+        &self,
+        podcast_id: u64,
+        feed_url: &str,
+    ) -> anyhow::Result<Option<Podcast>> {
+        self.move_podcast_feed_url(podcast_id, feed_url, false)
+            .await
+    }
+
+    async fn merge_feed_url(&self, podcast_id: u64, feed_url: &str) -> anyhow::Result<Podcast> {
+        self.move_podcast_feed_url(podcast_id, feed_url, true)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("podcast not moved"))
+    }
+
     #[tracing::instrument(
         skip_all,
         fields(
@@ -75,7 +406,7 @@ impl entertainarr_domain::podcast::prelude::PodcastRepository for super::Pool {
             return Ok(Vec::default());
         }
         let mut qb = sqlx::QueryBuilder::new(
-            "select podcasts.id, podcasts.feed_url, podcasts.title, podcasts.description, podcasts.image_url, podcasts.language, podcasts.website, podcasts.created_at, podcasts.updated_at from podcasts where",
+            "select podcasts.id, podcasts.feed_url, podcasts.title, podcasts.description, podcasts.image_url, podcasts.language, podcasts.website, podcasts.last_synced_at, podcasts.last_sync_error, podcasts.feed_etag, podcasts.feed_last_modified, podcasts.created_at, podcasts.updated_at, podcasts.author, podcasts.categories, podcasts.explicit, podcasts.persons, podcasts.funding from podcasts where",
         );
         let podcast_ids = BTreeSet::from_iter(podcast_ids.iter().copied());
         for (index, id) in podcast_ids.iter().enumerate() {
@@ -120,6 +451,17 @@ impl entertainarr_domain::podcast::prelude::PodcastRepository for super::Pool {
             .bind(entity.image_url.as_ref())
             .bind(entity.language.as_ref())
             .bind(entity.website.as_ref())
+            .bind(entity.author.as_ref())
+            .bind((!entity.categories.is_empty()).then(|| entity.categories.join("\n")))
+            .bind(entity.explicit)
+            .bind(podcast_namespace::encode::<_, podcast_namespace::Person>(
+                &entity.persons,
+            ))
+            .bind(podcast_namespace::encode::<_, podcast_namespace::Funding>(
+                &entity.funding,
+            ))
+            .bind(entity.feed_cache.etag.as_ref())
+            .bind(entity.feed_cache.last_modified.as_ref())
             .fetch_one(&mut *tx)
             .instrument(span)
             .await
@@ -128,44 +470,187 @@ impl entertainarr_domain::podcast::prelude::PodcastRepository for super::Pool {
             .map(Wrapper::inner)
             .context("unable to upsert podcast")?;
 
-        let mut qb: sqlx::QueryBuilder<'_, sqlx::Sqlite> = sqlx::QueryBuilder::new(
-            "insert into podcast_episodes (podcast_id, guid, published_at, title, description, link, duration, file_url, file_size, file_type)",
-        );
-        qb.push_values(entity.episodes.iter(), |mut b, item| {
-            b.push_bind(podcast.id as i64)
-                .push_bind(&item.guid)
-                .push_bind(item.published_at)
-                .push_bind(&item.title)
-                .push_bind(&item.description)
-                .push_bind(&item.link)
-                .push_bind(item.duration.as_ref().map(|value| value.as_secs() as i64))
-                .push_bind(&item.file_url)
-                .push_bind(item.file_size.map(|value| value as i64))
-                .push_bind(&item.file_type);
-        });
-        qb.push(" on conflict (podcast_id, guid) do nothing");
+        for episodes in entity.episodes.chunks(EPISODE_BATCH_SIZE) {
+            let mut qb: sqlx::QueryBuilder<'_, sqlx::Sqlite> = sqlx::QueryBuilder::new(
+                "insert into podcast_episodes (podcast_id, identity, guid, published_at, title, description, link, duration, file_url, file_size, file_type, image_url, season_number, episode_number, episode_type, chapters_url, transcript_url, transcript_type, transcript_language, persons)",
+            );
+            qb.push_values(episodes, |mut b, item| {
+                b.push_bind(podcast.id as i64)
+                    .push_bind(item.identity())
+                    .push_bind(&item.guid)
+                    .push_bind(item.published_at)
+                    .push_bind(&item.title)
+                    .push_bind(&item.description)
+                    .push_bind(&item.link)
+                    .push_bind(item.duration.as_ref().map(|value| value.as_secs() as i64))
+                    .push_bind(&item.file_url)
+                    .push_bind(item.file_size.map(|value| value as i64))
+                    .push_bind(&item.file_type)
+                    .push_bind(&item.image_url)
+                    .push_bind(item.season_number)
+                    .push_bind(item.episode_number)
+                    .push_bind(item.episode_type.map(|value| value.as_str()))
+                    .push_bind(&item.chapters_url)
+                    .push_bind(item.transcript.as_ref().map(|value| &value.url))
+                    .push_bind(item.transcript.as_ref().map(|value| &value.mime_type))
+                    .push_bind(
+                        item.transcript
+                            .as_ref()
+                            .and_then(|value| value.language.as_ref()),
+                    )
+                    .push_bind(podcast_namespace::encode::<_, podcast_namespace::Person>(
+                        &item.persons,
+                    ));
+            });
+            qb.push(UPSERT_PODCAST_EPISODE_CONFLICT);
 
-        let span = tracing::info_span!(
-            "podcast_episodes.upsert",
+            let span = tracing::info_span!(
+                "podcast_episodes.upsert",
+                otel.kind = "client",
+                db.system = "sqlite",
+                db.name = "podcast",
+                db.operation = "UPSERT",
+                db.sql.table = "podcast_episodes",
+                db.query.text = qb.sql(),
+                db.response.returned_rows = tracing::field::Empty,
+                error.type = tracing::field::Empty,
+                error.message = tracing::field::Empty,
+                error.stacktrace = tracing::field::Empty,
+            );
+            qb.build()
+                .execute(&mut *tx)
+                .instrument(span)
+                .await
+                .inspect_err(super::record_error)?;
+        }
+
+        // an empty feed is most likely a broken one, its episodes are kept as they are
+        if !entity.episodes.is_empty() {
+            // flag the episodes that are not in the feed anymore
+            let identities = entity
+                .episodes
+                .iter()
+                .map(|item| item.identity())
+                .collect::<BTreeSet<_>>();
+            let current: Vec<(i64, String)> = sqlx::query_as(LIST_ACTIVE_EPISODE_IDENTITY_QUERY)
+                .bind(podcast.id as i64)
+                .fetch_all(&mut *tx)
+                .instrument(tracing::info_span!(
+                    "podcast_episodes.list_active",
+                    otel.kind = "client",
+                    db.system = "sqlite",
+                    db.name = "podcast",
+                    db.operation = "SELECT",
+                    db.sql.table = "podcast_episodes",
+                    db.query.text = LIST_ACTIVE_EPISODE_IDENTITY_QUERY,
+                    db.response.returned_rows = tracing::field::Empty,
+                    error.type = tracing::field::Empty,
+                    error.message = tracing::field::Empty,
+                    error.stacktrace = tracing::field::Empty,
+                ))
+                .await
+                .inspect(super::record_all)
+                .inspect_err(super::record_error)
+                .context("unable to list podcast episodes")?;
+            let removed = current
+                .into_iter()
+                .filter(|(_, identity)| !identities.contains(identity.as_str()))
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>();
+
+            for ids in removed.chunks(MAX_BINDS) {
+                let mut qb: sqlx::QueryBuilder<'_, sqlx::Sqlite> = sqlx::QueryBuilder::new(
+                    "update podcast_episodes set removed_at = CURRENT_TIMESTAMP where id in (",
+                );
+                let mut separated = qb.separated(", ");
+                for id in ids {
+                    separated.push_bind(id);
+                }
+                separated.push_unseparated(")");
+
+                let span = tracing::info_span!(
+                    "podcast_episodes.flag_removed",
+                    otel.kind = "client",
+                    db.system = "sqlite",
+                    db.name = "podcast",
+                    db.operation = "UPDATE",
+                    db.sql.table = "podcast_episodes",
+                    db.query.text = qb.sql(),
+                    db.response.returned_rows = tracing::field::Empty,
+                    error.type = tracing::field::Empty,
+                    error.message = tracing::field::Empty,
+                    error.stacktrace = tracing::field::Empty,
+                );
+                qb.build()
+                    .execute(&mut *tx)
+                    .instrument(span)
+                    .await
+                    .inspect_err(super::record_error)?;
+            }
+        }
+
+        tx.commit().await.context("unable to commit transaction")?;
+        Ok(podcast)
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
             otel.kind = "client",
             db.system = "sqlite",
             db.name = "podcast",
-            db.operation = "UPSERT",
-            db.sql.table = "podcast_episodes",
-            db.query.text = qb.sql(),
+            db.operation = "SELECT",
+            db.sql.table = "podcasts",
+            db.query.text = LIST_SUBSCRIBED_PODCAST_QUERY,
             db.response.returned_rows = tracing::field::Empty,
             error.type = tracing::field::Empty,
             error.message = tracing::field::Empty,
             error.stacktrace = tracing::field::Empty,
-        );
-        qb.build()
-            .execute(&mut *tx)
-            .instrument(span)
+        ),
+        err(Debug),
+    )]
+    async fn list_subscribed(&self) -> anyhow::Result<Vec<Podcast>> {
+        sqlx::query_as(LIST_SUBSCRIBED_PODCAST_QUERY)
+            .fetch_all(&self.0)
             .await
-            .inspect_err(super::record_error)?;
+            .inspect(super::record_all)
+            .inspect_err(super::record_error)
+            .map(Wrapper::list)
+            .context("unable to list subscribed podcasts")
+    }
 
-        tx.commit().await.context("unable to commit transaction")?;
-        Ok(podcast)
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "UPDATE",
+            db.sql.table = "podcasts",
+            db.query.text = tracing::field::Empty,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn update_sync_status(&self, podcast_id: u64, error: Option<&str>) -> anyhow::Result<()> {
+        let span = tracing::Span::current();
+        let query = if let Some(error) = error {
+            span.record("db.query.text", UPDATE_PODCAST_SYNC_FAILURE_QUERY);
+            sqlx::query(UPDATE_PODCAST_SYNC_FAILURE_QUERY).bind(error)
+        } else {
+            span.record("db.query.text", UPDATE_PODCAST_SYNC_SUCCESS_QUERY);
+            sqlx::query(UPDATE_PODCAST_SYNC_SUCCESS_QUERY)
+        };
+        query
+            .bind(podcast_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to update podcast sync status")
     }
 }
 
@@ -240,10 +725,7 @@ impl entertainarr_domain::podcast::prelude::PodcastSubscriptionRepository for su
         ),
         err(Debug),
     )]
-    async fn list(
-        &self,
-        user_id: u64,
-    ) -> anyhow::Result<Vec<entertainarr_domain::podcast::entity::Podcast>> {
+    async fn list(&self, user_id: u64) -> anyhow::Result<Vec<SubscribedPodcast>> {
         sqlx::query_as(LIST_PODCAST_QUERY)
             .bind(user_id as i64)
             .fetch_all(&self.0)
@@ -253,6 +735,77 @@ impl entertainarr_domain::podcast::prelude::PodcastSubscriptionRepository for su
             .map(Wrapper::list)
             .context("unable to list podcasts")
     }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "user_podcasts",
+            db.query.text = FIND_USER_PODCAST_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn find(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+    ) -> anyhow::Result<Option<PodcastSubscription>> {
+        sqlx::query_as(FIND_USER_PODCAST_QUERY)
+            .bind(user_id as i64)
+            .bind(podcast_id as i64)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(Wrapper::maybe_inner)
+            .context("unable to find user podcast relation")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "UPDATE",
+            db.sql.table = "user_podcasts",
+            db.query.text = UPDATE_USER_PODCAST_SETTINGS_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn update_settings(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        update: &PodcastSubscriptionSettingsUpdate,
+    ) -> anyhow::Result<Option<PodcastSubscription>> {
+        sqlx::query_as(UPDATE_USER_PODCAST_SETTINGS_QUERY)
+            .bind(update.playback_speed)
+            .bind(update.skip_start)
+            .bind(update.skip_end)
+            .bind(update.auto_download)
+            .bind(update.include_in_home)
+            .bind(update.notify)
+            .bind(user_id as i64)
+            .bind(podcast_id as i64)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(Wrapper::maybe_inner)
+            .context("unable to update user podcast settings")
+    }
 }
 
 impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<Podcast> {
@@ -267,8 +820,62 @@ impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<Podcast>
             image_url: row.try_get(4)?,
             language: row.try_get(5)?,
             website: row.try_get(6)?,
-            created_at: row.try_get(7)?,
-            updated_at: row.try_get(8)?,
+            last_synced_at: row.try_get(7)?,
+            last_sync_error: row.try_get(8)?,
+            feed_cache: FeedCache {
+                etag: row.try_get(9)?,
+                last_modified: row.try_get(10)?,
+            },
+            created_at: row.try_get(11)?,
+            updated_at: row.try_get(12)?,
+            author: row.try_get(13)?,
+            categories: row
+                .try_get::<Option<String>, _>(14)?
+                .map(|value| value.lines().map(String::from).collect())
+                .unwrap_or_default(),
+            explicit: row.try_get(15)?,
+            persons: podcast_namespace::decode::<podcast_namespace::Person, _>(row.try_get(16)?),
+            funding: podcast_namespace::decode::<podcast_namespace::Funding, _>(row.try_get(17)?),
+        }))
+    }
+}
+
+/// Reads the subscription columns, starting at the given index
+fn subscription_from_row(
+    row: &sqlx::sqlite::SqliteRow,
+    offset: usize,
+) -> Result<PodcastSubscription, sqlx::Error> {
+    use sqlx::Row;
+
+    Ok(PodcastSubscription {
+        user_id: row.try_get(offset)?,
+        podcast_id: row.try_get(offset + 1)?,
+        auto_queue: row.try_get(offset + 3)?,
+        settings: PodcastSubscriptionSettings {
+            playback_speed: row.try_get(offset + 4)?,
+            skip_start: row.try_get(offset + 5)?,
+            skip_end: row.try_get(offset + 6)?,
+            auto_download: row.try_get(offset + 7)?,
+            include_in_home: row.try_get(offset + 8)?,
+            notify: row.try_get(offset + 9)?,
+        },
+        created_at: row.try_get(offset + 2)?,
+    })
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<PodcastSubscription> {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        subscription_from_row(row, 0).map(Self)
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<SubscribedPodcast> {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        let Wrapper(podcast) = Wrapper::<Podcast>::from_row(row)?;
+        let subscription = subscription_from_row(row, 18)?;
+        Ok(Self(SubscribedPodcast {
+            podcast,
+            subscription,
         }))
     }
 }
@@ -277,8 +884,11 @@ impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<Podcast>
 mod tests {
     use crate::Pool;
     use entertainarr_domain::podcast::{
-        entity::{PodcastEpisodeInput, PodcastInput},
-        prelude::PodcastRepository,
+        entity::{
+            FeedCache, PodcastEpisodeInput, PodcastEpisodeType, PodcastInput,
+            PodcastSubscriptionSettingsUpdate,
+        },
+        prelude::{PodcastRepository, PodcastSubscriptionRepository},
     };
 
     #[tokio::test]
@@ -336,6 +946,17 @@ mod tests {
             image_url: None,
             language: None,
             website: None,
+            author: Some("Jane Doe".into()),
+            categories: vec!["Technology".into(), "Software How-To".into()],
+            explicit: Some(false),
+            persons: Vec::new(),
+            funding: Vec::new(),
+            new_feed_url: None,
+            redirected_to: None,
+            feed_cache: FeedCache {
+                etag: Some("\"abcdef\"".into()),
+                last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".into()),
+            },
             episodes: vec![
                 PodcastEpisodeInput {
                     guid: Some("aaaaa".into()),
@@ -347,6 +968,13 @@ mod tests {
                     file_url: "http://example.com/first.mp3".into(),
                     file_size: None,
                     file_type: None,
+                    image_url: Some("http://example.com/first.jpg".into()),
+                    season_number: Some(2),
+                    episode_number: Some(1),
+                    episode_type: Some(PodcastEpisodeType::Trailer),
+                    chapters_url: None,
+                    transcript: None,
+                    persons: Vec::new(),
                 },
                 PodcastEpisodeInput {
                     guid: Some("aaaab".into()),
@@ -358,15 +986,489 @@ mod tests {
                     file_url: "http://example.com/second.mp3".into(),
                     file_size: None,
                     file_type: None,
+                    image_url: None,
+                    season_number: None,
+                    episode_number: None,
+                    episode_type: None,
+                    chapters_url: None,
+                    transcript: None,
+                    persons: Vec::new(),
                 },
             ],
         };
-        let _res = pool.upsert(&content).await.unwrap();
+        let res = pool.upsert(&content).await.unwrap();
         assert_eq!(count_postcasts(&pool).await, 1);
         assert_eq!(count_postcast_episodes(&pool).await, 2);
+        assert_eq!(res.feed_cache, content.feed_cache);
+        assert_eq!(res.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(res.categories, content.categories);
+        assert_eq!(res.explicit, Some(false));
+        let episode: (Option<String>, Option<u32>, Option<u32>, Option<String>) = sqlx::query_as(
+            "select image_url, season_number, episode_number, episode_type from podcast_episodes where guid = 'aaaaa'",
+        )
+        .fetch_one(&pool.0)
+        .await
+        .unwrap();
+        assert_eq!(
+            episode,
+            (
+                Some("http://example.com/first.jpg".into()),
+                Some(2),
+                Some(1),
+                Some("trailer".into())
+            )
+        );
         // should not recreate it
         let _res = pool.upsert(&content).await.unwrap();
         assert_eq!(count_postcasts(&pool).await, 1);
         assert_eq!(count_postcast_episodes(&pool).await, 2);
     }
+
+    #[tokio::test]
+    async fn should_update_changed_episodes_and_flag_removed_ones() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        let episode = |guid: &str, title: &str| PodcastEpisodeInput {
+            guid: Some(guid.into()),
+            published_at: None,
+            title: title.into(),
+            description: None,
+            link: None,
+            duration: None,
+            file_url: format!("http://example.com/{guid}.mp3"),
+            file_size: None,
+            file_type: None,
+            image_url: None,
+            season_number: None,
+            episode_number: None,
+            episode_type: None,
+            chapters_url: None,
+            transcript: None,
+            persons: Vec::new(),
+        };
+        let mut content = PodcastInput {
+            feed_url: "http://example.com/atom.rss".into(),
+            title: "Example".into(),
+            description: None,
+            image_url: None,
+            language: None,
+            website: None,
+            author: None,
+            categories: Vec::new(),
+            explicit: None,
+            persons: Vec::new(),
+            funding: Vec::new(),
+            new_feed_url: None,
+            redirected_to: None,
+            feed_cache: FeedCache::default(),
+            episodes: vec![episode("aaaaa", "First"), episode("aaaab", "Second")],
+        };
+        pool.upsert(&content).await.unwrap();
+
+        content.episodes = vec![episode("aaaaa", "First episode")];
+        pool.upsert(&content).await.unwrap();
+
+        let rows: Vec<(String, String, bool)> = sqlx::query_as(
+            "select guid, title, removed_at is not null from podcast_episodes order by guid",
+        )
+        .fetch_all(&pool.0)
+        .await
+        .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                ("aaaaa".into(), "First episode".into(), false),
+                ("aaaab".into(), "Second".into(), true),
+            ]
+        );
+
+        // coming back in the feed
+        content.episodes = vec![
+            episode("aaaaa", "First episode"),
+            episode("aaaab", "Second"),
+        ];
+        pool.upsert(&content).await.unwrap();
+        let removed: u32 = sqlx::query_scalar(
+            "select count(*) from podcast_episodes where removed_at is not null",
+        )
+        .fetch_one(&pool.0)
+        .await
+        .unwrap();
+        assert_eq!(removed, 0);
+    }
+
+    #[tokio::test]
+    async fn should_upsert_large_feeds_and_keep_episodes_of_empty_ones() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        let episode = |index: usize| PodcastEpisodeInput {
+            guid: Some(format!("episode-{index}")),
+            published_at: None,
+            title: format!("Episode {index}"),
+            description: None,
+            link: None,
+            duration: None,
+            file_url: format!("http://example.com/{index}.mp3"),
+            file_size: None,
+            file_type: None,
+            image_url: None,
+            season_number: None,
+            episode_number: None,
+            episode_type: None,
+            chapters_url: None,
+            transcript: None,
+            persons: Vec::new(),
+        };
+        let mut content = PodcastInput {
+            feed_url: "http://example.com/atom.rss".into(),
+            title: "Example".into(),
+            description: None,
+            image_url: None,
+            language: None,
+            website: None,
+            author: None,
+            categories: Vec::new(),
+            explicit: None,
+            persons: Vec::new(),
+            funding: Vec::new(),
+            new_feed_url: None,
+            redirected_to: None,
+            feed_cache: FeedCache::default(),
+            episodes: (0..2500).map(episode).collect(),
+        };
+        let count_removed = async |pool: &Pool| -> (u32, u32) {
+            sqlx::query_as(
+                "select count(*), coalesce(sum(removed_at is not null), 0) from podcast_episodes",
+            )
+            .fetch_one(&pool.0)
+            .await
+            .unwrap()
+        };
+
+        pool.upsert(&content).await.unwrap();
+        assert_eq!(count_removed(&pool).await, (2500, 0));
+
+        content.episodes = Vec::new();
+        pool.upsert(&content).await.unwrap();
+        assert_eq!(count_removed(&pool).await, (2500, 0));
+
+        content.episodes = vec![episode(0)];
+        pool.upsert(&content).await.unwrap();
+        assert_eq!(count_removed(&pool).await, (2500, 2499));
+    }
+
+    #[tokio::test]
+    async fn should_not_duplicate_episodes_without_guid() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        let episode = |title: &str| PodcastEpisodeInput {
+            guid: None,
+            published_at: Some(
+                sqlx::types::chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            ),
+            title: title.into(),
+            description: None,
+            link: None,
+            duration: None,
+            file_url: "http://example.com/episode.mp3".into(),
+            file_size: None,
+            file_type: None,
+            image_url: None,
+            season_number: None,
+            episode_number: None,
+            episode_type: None,
+            chapters_url: None,
+            transcript: None,
+            persons: Vec::new(),
+        };
+        let content = PodcastInput {
+            feed_url: "http://example.com/atom.rss".into(),
+            title: "Example".into(),
+            description: None,
+            image_url: None,
+            language: None,
+            website: None,
+            author: None,
+            categories: Vec::new(),
+            explicit: None,
+            persons: Vec::new(),
+            funding: Vec::new(),
+            new_feed_url: None,
+            redirected_to: None,
+            feed_cache: FeedCache::default(),
+            episodes: vec![episode("First"), episode("Second")],
+        };
+        pool.upsert(&content).await.unwrap();
+        assert_eq!(count_postcast_episodes(&pool).await, 2);
+        pool.upsert(&content).await.unwrap();
+        assert_eq!(count_postcast_episodes(&pool).await, 2);
+
+        let identity: String =
+            sqlx::query_scalar("select identity from podcast_episodes where title = 'First'")
+                .fetch_one(&pool.0)
+                .await
+                .unwrap();
+        assert_eq!(identity, content.episodes[0].identity());
+    }
+
+    #[tokio::test]
+    async fn should_find_podcast_and_subscription() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        sqlx::query("insert into users (id, email, password) values (1, 'user1@example.com', 'password'), (2, 'user2@example.com', 'password')").execute(&pool.0).await.unwrap();
+        sqlx::query("insert into podcasts (id, feed_url, title) values (1, 'first', 'first'), (2, 'second', 'second')").execute(&pool.0).await.unwrap();
+        sqlx::query("insert into user_podcasts (user_id, podcast_id) values (1, 1)")
+            .execute(&pool.0)
+            .await
+            .unwrap();
+
+        let podcast = pool.find_by_id(1).await.unwrap().unwrap();
+        assert_eq!(podcast.title, "first");
+        assert!(pool.find_by_id(42).await.unwrap().is_none());
+
+        let subscription = pool.find(1, 1).await.unwrap().unwrap();
+        assert_eq!(subscription.user_id, 1);
+        assert_eq!(subscription.podcast_id, 1);
+        assert!(pool.find(2, 1).await.unwrap().is_none());
+        assert!(pool.find(1, 2).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn should_update_subscription_settings() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        sqlx::query(
+            "insert into users (id, email, password) values (1, 'user1@example.com', 'password')",
+        )
+        .execute(&pool.0)
+        .await
+        .unwrap();
+        sqlx::query("insert into podcasts (id, feed_url, title) values (1, 'first', 'first'), (2, 'second', 'second')").execute(&pool.0).await.unwrap();
+        sqlx::query("insert into user_podcasts (user_id, podcast_id) values (1, 1), (1, 2)")
+            .execute(&pool.0)
+            .await
+            .unwrap();
+
+        let subscription = pool
+            .update_settings(
+                1,
+                2,
+                &PodcastSubscriptionSettingsUpdate {
+                    playback_speed: Some(1.5),
+                    skip_start: Some(30),
+                    include_in_home: Some(false),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(subscription.settings.playback_speed, 1.5);
+        assert_eq!(subscription.settings.skip_start, 30);
+        assert_eq!(subscription.settings.skip_end, 0);
+        assert!(!subscription.settings.include_in_home);
+
+        let list = PodcastSubscriptionRepository::list(&pool, 1).await.unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].podcast.id, 1);
+        assert_eq!(list[0].subscription.settings, Default::default());
+        assert_eq!(list[1].podcast.id, 2);
+        assert_eq!(list[1].subscription.settings, subscription.settings);
+
+        assert!(
+            pool.update_settings(1, 3, &Default::default())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_move_feed_url_and_keep_alias() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        sqlx::query("insert into podcasts (id, feed_url, title, feed_etag) values (1, 'http://old.example.com/feed.rss', 'first', 'abcdef')").execute(&pool.0).await.unwrap();
+
+        let moved = pool
+            .move_feed_url(1, "https://new.example.com/feed.rss")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.id, 1);
+        assert_eq!(moved.feed_url, "https://new.example.com/feed.rss");
+        assert_eq!(moved.feed_cache, FeedCache::default());
+
+        let found = pool
+            .find_by_feed_url("http://old.example.com/feed.rss")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, 1);
+
+        // moving back to the previous url
+        let moved = pool
+            .move_feed_url(1, "http://old.example.com/feed.rss")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.feed_url, "http://old.example.com/feed.rss");
+        let found = pool
+            .find_by_feed_url("https://new.example.com/feed.rss")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, 1);
+    }
+
+    #[tokio::test]
+    async fn should_not_move_to_feed_used_by_another_podcast() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        sqlx::query("insert into podcasts (id, feed_url, title) values (1, 'old', 'old'), (2, 'new', 'new')").execute(&pool.0).await.unwrap();
+
+        assert!(pool.move_feed_url(1, "new").await.unwrap().is_none());
+        assert_eq!(pool.find_by_id(1).await.unwrap().unwrap().feed_url, "old");
+        assert_eq!(pool.find_by_feed_url("new").await.unwrap().unwrap().id, 2);
+        let aliases: i64 = sqlx::query_scalar("select count(*) from podcast_feed_aliases")
+            .fetch_one(&pool.0)
+            .await
+            .unwrap();
+        assert_eq!(aliases, 0);
+    }
+
+    #[tokio::test]
+    async fn should_merge_podcast_when_moving_to_known_feed() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        sqlx::query("insert into users (id, email, password) values (1, 'user1@example.com', 'password'), (2, 'user2@example.com', 'password')").execute(&pool.0).await.unwrap();
+        sqlx::query("insert into podcasts (id, feed_url, title) values (1, 'old', 'old'), (2, 'new', 'new')").execute(&pool.0).await.unwrap();
+        sqlx::query("insert into podcast_episodes (id, podcast_id, identity, guid, title, file_url) values (1, 1, 'a', 'a', 'a', 'a.mp3'), (2, 1, 'b', 'b', 'b', 'b.mp3'), (3, 2, 'a', 'a', 'a', 'a.mp3'), (4, 1, 'c', 'c', 'c', 'c.mp3'), (5, 2, 'c', 'c', 'c', 'c.mp3')").execute(&pool.0).await.unwrap();
+        sqlx::query(
+            "insert into podcast_download_policies (podcast_id, latest) values (1, 3), (2, 1)",
+        )
+        .execute(&pool.0)
+        .await
+        .unwrap();
+        sqlx::query("insert into podcast_episode_downloads (podcast_episode_id, status, file_path) values (1, 'completed', '1/1.mp3'), (2, 'completed', '1/2.mp3'), (4, 'completed', '1/4.mp3'), (5, 'completed', '2/5.mp3')").execute(&pool.0).await.unwrap();
+        sqlx::query(
+            "insert into user_podcasts (user_id, podcast_id, auto_queue, auto_queue_after) values (1, 1, true, 2), (2, 1, false, 0), (2, 2, false, 0)",
+        )
+        .execute(&pool.0)
+        .await
+        .unwrap();
+        sqlx::query("insert into user_podcast_episodes (user_id, podcast_episode_id, progress, completed) values (1, 1, 42, false), (1, 2, 10, true)").execute(&pool.0).await.unwrap();
+        sqlx::query("insert into user_queue (user_id, podcast_episode_id, position) values (1, 1, 0), (1, 2, 1)").execute(&pool.0).await.unwrap();
+
+        let merged = pool.merge_feed_url(1, "new").await.unwrap();
+        assert_eq!(merged.id, 2);
+        assert!(pool.find_by_id(1).await.unwrap().is_none());
+        assert_eq!(pool.find_by_feed_url("old").await.unwrap().unwrap().id, 2);
+        assert!(pool.find(1, 2).await.unwrap().is_some());
+        assert!(pool.find(2, 2).await.unwrap().is_some());
+
+        let progress: Vec<(i64, i64)> = sqlx::query_as("select podcast_episode_id, progress from user_podcast_episodes where user_id = 1 order by podcast_episode_id")
+            .fetch_all(&pool.0)
+            .await
+            .unwrap();
+        assert_eq!(progress, vec![(2, 10), (3, 42)]);
+        let episodes: Vec<(i64,)> =
+            sqlx::query_as("select id from podcast_episodes where podcast_id = 2 order by id")
+                .fetch_all(&pool.0)
+                .await
+                .unwrap();
+        assert_eq!(episodes, vec![(2,), (3,), (5,)]);
+        let latest: i64 =
+            sqlx::query_scalar("select latest from podcast_download_policies where podcast_id = 2")
+                .fetch_one(&pool.0)
+                .await
+                .unwrap();
+        assert_eq!(latest, 3);
+        let downloads: Vec<(i64, String)> = sqlx::query_as(
+            "select podcast_episode_id, file_path from podcast_episode_downloads order by podcast_episode_id",
+        )
+        .fetch_all(&pool.0)
+        .await
+        .unwrap();
+        assert_eq!(
+            downloads,
+            vec![
+                (2, "1/2.mp3".into()),
+                (3, "1/1.mp3".into()),
+                (5, "2/5.mp3".into())
+            ]
+        );
+        let orphans: Vec<String> = sqlx::query_scalar("select file_path from podcast_orphan_files")
+            .fetch_all(&pool.0)
+            .await
+            .unwrap();
+        assert_eq!(orphans, vec!["1/4.mp3"]);
+        let settings: (bool, i64) = sqlx::query_as(
+            "select auto_queue, auto_queue_after from user_podcasts where user_id = 1 and podcast_id = 2",
+        )
+        .fetch_one(&pool.0)
+        .await
+        .unwrap();
+        assert_eq!(settings, (true, 2));
+        let queue: Vec<(i64, i64)> = sqlx::query_as(
+            "select podcast_episode_id, position from user_queue where user_id = 1 order by position",
+        )
+        .fetch_all(&pool.0)
+        .await
+        .unwrap();
+        assert_eq!(queue, vec![(3, 0), (2, 1)]);
+    }
+
+    #[tokio::test]
+    async fn should_list_subscribed_podcasts() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        sqlx::query("insert into users (id, email, password) values (1, 'user1@example.com', 'password'), (2, 'user2@example.com', 'password')").execute(&pool.0).await.unwrap();
+        sqlx::query("insert into podcasts (id, feed_url, title) values (1, 'first', 'first'), (2, 'second', 'second'), (3, 'third', 'third')").execute(&pool.0).await.unwrap();
+        sqlx::query(
+            "insert into user_podcasts (user_id, podcast_id) values (1, 1), (2, 1), (2, 3)",
+        )
+        .execute(&pool.0)
+        .await
+        .unwrap();
+
+        let list = pool.list_subscribed().await.unwrap();
+        assert_eq!(
+            list.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_update_sync_status() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        sqlx::query("insert into podcasts (id, feed_url, title) values (1, 'first', 'first')")
+            .execute(&pool.0)
+            .await
+            .unwrap();
+
+        pool.update_sync_status(1, None).await.unwrap();
+        let podcast = pool.find_by_feed_url("first").await.unwrap().unwrap();
+        assert!(podcast.last_synced_at.is_some());
+        assert!(podcast.last_sync_error.is_none());
+
+        pool.update_sync_status(1, Some("oops")).await.unwrap();
+        let failed = pool.find_by_feed_url("first").await.unwrap().unwrap();
+        assert_eq!(failed.last_synced_at, podcast.last_synced_at);
+        assert_eq!(failed.last_sync_error.as_deref(), Some("oops"));
+
+        pool.update_sync_status(1, None).await.unwrap();
+        let podcast = pool.find_by_feed_url("first").await.unwrap().unwrap();
+        assert!(podcast.last_sync_error.is_none());
+    }
 }
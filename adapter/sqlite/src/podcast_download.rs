@@ -0,0 +1,649 @@
+use anyhow::Context;
+use entertainarr_domain::podcast::entity::{
+    PendingPodcastEpisodeDownload, PodcastDownloadPolicy, PodcastEpisodeDownload,
+    PodcastEpisodeDownloadStatus, StoredMedia,
+};
+
+use crate::Wrapper;
+
+const FIND_POLICY_QUERY: &str =
+    "select podcast_id, latest from podcast_download_policies where podcast_id = ? limit 1";
+const UPSERT_POLICY_QUERY: &str = r#"insert into podcast_download_policies (podcast_id, latest)
+values (?, ?)
+on conflict (podcast_id) do update set
+    latest = excluded.latest,
+    updated_at = CURRENT_TIMESTAMP"#;
+const DELETE_POLICY_QUERY: &str = "delete from podcast_download_policies where podcast_id = ?";
+const FIND_DOWNLOAD_QUERY: &str = r#"select podcast_episode_id, status, attempts, file_path, file_size, last_error, next_attempt_at, created_at, updated_at
+from podcast_episode_downloads
+where podcast_episode_id = ?
+limit 1"#;
+/// Queues the latest episodes still in their feed, for each podcast having a policy or
+/// subscribers asking for downloads, the largest count winning
+const ENQUEUE_LATEST_QUERY: &str = r#"insert into podcast_episode_downloads (podcast_episode_id)
+select latest_episodes.id from (
+    select
+        podcast_episodes.id,
+        row_number() over (partition by podcast_episodes.podcast_id order by podcast_episodes.published_at desc nulls last, podcast_episodes.id desc) as position,
+        download_targets.latest
+    from podcast_episodes
+    join (
+        select podcast_id, max(latest) as latest from (
+            select podcast_id, latest from podcast_download_policies
+            union all
+            select podcast_id, auto_download from user_podcasts where auto_download > 0
+        ) group by podcast_id
+    ) download_targets on download_targets.podcast_id = podcast_episodes.podcast_id
+) latest_episodes
+join podcast_episodes on podcast_episodes.id = latest_episodes.id
+where latest_episodes.position <= latest_episodes.latest
+    and podcast_episodes.removed_at is null
+on conflict (podcast_episode_id) do nothing"#;
+/// Episodes removed from their feed are kept as long as they are part of the latest ones
+const LIST_OUTDATED_QUERY: &str = r#"select podcast_episode_id, status, attempts, file_path, file_size, last_error, next_attempt_at, created_at, updated_at
+from podcast_episode_downloads
+where podcast_episode_id not in (
+    select id from (
+        select
+            podcast_episodes.id,
+            row_number() over (partition by podcast_episodes.podcast_id order by podcast_episodes.published_at desc nulls last, podcast_episodes.id desc) as position,
+            download_targets.latest
+        from podcast_episodes
+        join (
+            select podcast_id, max(latest) as latest from (
+                select podcast_id, latest from podcast_download_policies
+                union all
+                select podcast_id, auto_download from user_podcasts where auto_download > 0
+            ) group by podcast_id
+        ) download_targets on download_targets.podcast_id = podcast_episodes.podcast_id
+    )
+    where position <= latest
+)"#;
+const DELETE_DOWNLOAD_QUERY: &str =
+    "delete from podcast_episode_downloads where podcast_episode_id = ?";
+const LIST_ORPHAN_FILES_QUERY: &str =
+    "select file_path from podcast_orphan_files order by created_at, file_path";
+const DELETE_ORPHAN_FILE_QUERY: &str = "delete from podcast_orphan_files where file_path = ?";
+const LIST_PENDING_QUERY: &str = r#"select podcast_episodes.podcast_id, podcast_episodes.id, podcast_episodes.file_url, podcast_episodes.file_type, podcast_episode_downloads.attempts
+from podcast_episode_downloads
+join podcast_episodes on podcast_episodes.id = podcast_episode_downloads.podcast_episode_id
+where podcast_episode_downloads.status = 'pending'
+    and (podcast_episode_downloads.next_attempt_at is null or podcast_episode_downloads.next_attempt_at <= ?)
+order by podcast_episode_downloads.created_at, podcast_episode_downloads.podcast_episode_id
+limit ?"#;
+const COMPLETE_DOWNLOAD_QUERY: &str = r#"update podcast_episode_downloads set
+    status = 'completed',
+    file_path = ?,
+    file_size = ?,
+    last_error = null,
+    next_attempt_at = null,
+    updated_at = CURRENT_TIMESTAMP
+where podcast_episode_id = ?"#;
+const FAIL_DOWNLOAD_QUERY: &str = r#"update podcast_episode_downloads set
+    status = ?,
+    attempts = attempts + 1,
+    last_error = ?,
+    next_attempt_at = ?,
+    updated_at = CURRENT_TIMESTAMP
+where podcast_episode_id = ?"#;
+
+impl entertainarr_domain::podcast::prelude::PodcastDownloadRepository for super::Pool {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_download_policies",
+            db.query.text = FIND_POLICY_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn find_policy(&self, podcast_id: u64) -> anyhow::Result<Option<PodcastDownloadPolicy>> {
+        sqlx::query_as(FIND_POLICY_QUERY)
+            .bind(podcast_id as i64)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(Wrapper::maybe_inner)
+            .context("unable to find podcast download policy")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "UPSERT",
+            db.sql.table = "podcast_download_policies",
+            db.query.text = UPSERT_POLICY_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn upsert_policy(&self, policy: &PodcastDownloadPolicy) -> anyhow::Result<()> {
+        sqlx::query(UPSERT_POLICY_QUERY)
+            .bind(policy.podcast_id as i64)
+            .bind(policy.latest)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to upsert podcast download policy")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "DELETE",
+            db.sql.table = "podcast_download_policies",
+            db.query.text = DELETE_POLICY_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn delete_policy(&self, podcast_id: u64) -> anyhow::Result<()> {
+        sqlx::query(DELETE_POLICY_QUERY)
+            .bind(podcast_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to delete podcast download policy")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_episode_downloads",
+            db.query.text = FIND_DOWNLOAD_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn find(
+        &self,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<PodcastEpisodeDownload>> {
+        sqlx::query_as(FIND_DOWNLOAD_QUERY)
+            .bind(podcast_episode_id as i64)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(Wrapper::maybe_inner)
+            .context("unable to find podcast episode download")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "INSERT",
+            db.sql.table = "podcast_episode_downloads",
+            db.query.text = ENQUEUE_LATEST_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn enqueue_latest(&self) -> anyhow::Result<u64> {
+        sqlx::query(ENQUEUE_LATEST_QUERY)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|result| result.rows_affected())
+            .context("unable to enqueue podcast episode downloads")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_episode_downloads",
+            db.query.text = LIST_OUTDATED_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn list_outdated(&self) -> anyhow::Result<Vec<PodcastEpisodeDownload>> {
+        sqlx::query_as(LIST_OUTDATED_QUERY)
+            .fetch_all(&self.0)
+            .await
+            .inspect(super::record_all)
+            .inspect_err(super::record_error)
+            .map(Wrapper::list)
+            .context("unable to list outdated podcast episode downloads")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "DELETE",
+            db.sql.table = "podcast_episode_downloads",
+            db.query.text = DELETE_DOWNLOAD_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn delete(&self, podcast_episode_id: u64) -> anyhow::Result<()> {
+        sqlx::query(DELETE_DOWNLOAD_QUERY)
+            .bind(podcast_episode_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to delete podcast episode download")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_orphan_files",
+            db.query.text = LIST_ORPHAN_FILES_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn list_orphan_files(&self) -> anyhow::Result<Vec<String>> {
+        sqlx::query_scalar(LIST_ORPHAN_FILES_QUERY)
+            .fetch_all(&self.0)
+            .await
+            .inspect(super::record_all)
+            .inspect_err(super::record_error)
+            .context("unable to list orphan podcast files")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "DELETE",
+            db.sql.table = "podcast_orphan_files",
+            db.query.text = DELETE_ORPHAN_FILE_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn delete_orphan_file(&self, file_path: &str) -> anyhow::Result<()> {
+        sqlx::query(DELETE_ORPHAN_FILE_QUERY)
+            .bind(file_path)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to delete orphan podcast file")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_episode_downloads",
+            db.query.text = LIST_PENDING_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn list_pending(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<PendingPodcastEpisodeDownload>> {
+        sqlx::query_as(LIST_PENDING_QUERY)
+            .bind(now)
+            .bind(limit)
+            .fetch_all(&self.0)
+            .await
+            .inspect(super::record_all)
+            .inspect_err(super::record_error)
+            .map(Wrapper::list)
+            .context("unable to list pending podcast episode downloads")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "UPDATE",
+            db.sql.table = "podcast_episode_downloads",
+            db.query.text = COMPLETE_DOWNLOAD_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn complete(&self, podcast_episode_id: u64, media: &StoredMedia) -> anyhow::Result<()> {
+        sqlx::query(COMPLETE_DOWNLOAD_QUERY)
+            .bind(&media.file_path)
+            .bind(media.file_size as i64)
+            .bind(podcast_episode_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to complete podcast episode download")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "UPDATE",
+            db.sql.table = "podcast_episode_downloads",
+            db.query.text = FAIL_DOWNLOAD_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn fail(
+        &self,
+        podcast_episode_id: u64,
+        error: &str,
+        retry_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> anyhow::Result<()> {
+        let status = if retry_at.is_some() {
+            PodcastEpisodeDownloadStatus::Pending
+        } else {
+            PodcastEpisodeDownloadStatus::Failed
+        };
+        sqlx::query(FAIL_DOWNLOAD_QUERY)
+            .bind(status.as_str())
+            .bind(error)
+            .bind(retry_at)
+            .bind(podcast_episode_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to record podcast episode download failure")
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<PodcastDownloadPolicy> {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        Ok(Self(PodcastDownloadPolicy {
+            podcast_id: row.try_get(0)?,
+            latest: row.try_get(1)?,
+        }))
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<PodcastEpisodeDownload> {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        Ok(Self(PodcastEpisodeDownload {
+            podcast_episode_id: row.try_get(0)?,
+            status: row
+                .try_get::<String, _>(1)?
+                .parse()
+                .map_err(|err: anyhow::Error| sqlx::Error::ColumnDecode {
+                    index: "status".into(),
+                    source: err.into(),
+                })?,
+            attempts: row.try_get(2)?,
+            file_path: row.try_get(3)?,
+            file_size: row.try_get(4)?,
+            last_error: row.try_get(5)?,
+            next_attempt_at: row.try_get(6)?,
+            created_at: row.try_get(7)?,
+            updated_at: row.try_get(8)?,
+        }))
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow>
+    for super::Wrapper<PendingPodcastEpisodeDownload>
+{
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        Ok(Self(PendingPodcastEpisodeDownload {
+            podcast_id: row.try_get(0)?,
+            podcast_episode_id: row.try_get(1)?,
+            file_url: row.try_get(2)?,
+            file_type: row.try_get(3)?,
+            attempts: row.try_get(4)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entertainarr_domain::podcast::entity::{
+        PodcastDownloadPolicy, PodcastEpisodeDownloadStatus, StoredMedia,
+    };
+    use entertainarr_domain::podcast::prelude::{
+        PodcastDownloadRepository, PodcastEpisodeRepository,
+    };
+
+    use crate::Pool;
+
+    async fn seed(pool: &Pool) {
+        let _: Vec<u64> = sqlx::query_scalar("insert into podcasts (id, feed_url, title) values (1, 'first', 'first'), (2, 'second', 'second') returning id").fetch_all(pool.as_ref()).await.unwrap();
+        let _: Vec<u64> = sqlx::query_scalar("insert into podcast_episodes (id, podcast_id, identity, title, file_url, published_at, removed_at) values (1, 1, 'guid 1', 'title 1', 'url 1', '2025-01-01 00:00:00', null), (2, 1, 'guid 2', 'title 2', 'url 2', '2025-01-02 00:00:00', null), (3, 1, 'guid 3', 'title 3', 'url 3', '2025-01-03 00:00:00', '2025-02-01 00:00:00'), (4, 2, 'guid 4', 'title 4', 'url 4', '2025-01-04 00:00:00', null) returning id").fetch_all(pool.as_ref()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_manage_policy() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        assert!(pool.find_policy(1).await.unwrap().is_none());
+        let policy = PodcastDownloadPolicy {
+            podcast_id: 1,
+            latest: 3,
+        };
+        pool.upsert_policy(&policy).await.unwrap();
+        assert_eq!(pool.find_policy(1).await.unwrap(), Some(policy));
+        pool.delete_policy(1).await.unwrap();
+        assert!(pool.find_policy(1).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn should_queue_latest_episodes() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        pool.upsert_policy(&PodcastDownloadPolicy {
+            podcast_id: 1,
+            latest: 2,
+        })
+        .await
+        .unwrap();
+
+        // the latest episode was removed from the feed, it can't be downloaded anymore
+        assert_eq!(pool.enqueue_latest().await.unwrap(), 1);
+        assert_eq!(pool.enqueue_latest().await.unwrap(), 0);
+
+        let now = chrono::Utc::now();
+        let pending = pool.list_pending(now, 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].podcast_id, 1);
+        assert_eq!(pending[0].podcast_episode_id, 2);
+        assert_eq!(pending[0].file_url, "url 2");
+        assert_eq!(pending[0].attempts, 0);
+
+        pool.fail(2, "oops", Some(now + chrono::TimeDelta::minutes(10)))
+            .await
+            .unwrap();
+        assert!(pool.list_pending(now, 10).await.unwrap().is_empty());
+        let pending = pool
+            .list_pending(now + chrono::TimeDelta::minutes(20), 10)
+            .await
+            .unwrap();
+        assert_eq!(pending[0].attempts, 1);
+
+        let media = StoredMedia {
+            file_path: "1/2.mp3".into(),
+            file_size: 42,
+        };
+        pool.complete(2, &media).await.unwrap();
+        let download = pool.find(2).await.unwrap().unwrap();
+        assert_eq!(download.status, PodcastEpisodeDownloadStatus::Completed);
+        assert_eq!(download.file_path.as_deref(), Some("1/2.mp3"));
+        assert_eq!(download.file_size, Some(42));
+        assert_eq!(download.last_error, None);
+        assert!(pool.find_by_id(2).await.unwrap().unwrap().downloaded);
+        assert!(!pool.find_by_id(1).await.unwrap().unwrap().downloaded);
+
+        assert!(pool.list_outdated().await.unwrap().is_empty());
+        pool.upsert_policy(&PodcastDownloadPolicy {
+            podcast_id: 1,
+            latest: 1,
+        })
+        .await
+        .unwrap();
+        let outdated = pool.list_outdated().await.unwrap();
+        assert_eq!(outdated.len(), 1);
+        assert_eq!(outdated[0].podcast_episode_id, 2);
+        pool.delete(2).await.unwrap();
+        assert!(pool.find(2).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn should_queue_latest_episodes_asked_by_subscribers() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        sqlx::query(
+            "insert into users (id, email, password) values (1, 'user1@example.com', 'password')",
+        )
+        .execute(pool.as_ref())
+        .await
+        .unwrap();
+        sqlx::query(
+            "insert into user_podcasts (user_id, podcast_id, auto_download) values (1, 2, 1)",
+        )
+        .execute(pool.as_ref())
+        .await
+        .unwrap();
+        pool.upsert_policy(&PodcastDownloadPolicy {
+            podcast_id: 2,
+            latest: 0,
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(pool.enqueue_latest().await.unwrap(), 1);
+        let pending = pool.list_pending(chrono::Utc::now(), 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].podcast_episode_id, 4);
+        assert!(pool.list_outdated().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_give_up_download() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        pool.upsert_policy(&PodcastDownloadPolicy {
+            podcast_id: 2,
+            latest: 1,
+        })
+        .await
+        .unwrap();
+        assert_eq!(pool.enqueue_latest().await.unwrap(), 1);
+        pool.fail(4, "not found", None).await.unwrap();
+        let download = pool.find(4).await.unwrap().unwrap();
+        assert_eq!(download.status, PodcastEpisodeDownloadStatus::Failed);
+        assert_eq!(download.last_error.as_deref(), Some("not found"));
+        let later = chrono::Utc::now() + chrono::TimeDelta::days(1);
+        assert!(pool.list_pending(later, 10).await.unwrap().is_empty());
+        // failed downloads are not queued again
+        assert_eq!(pool.enqueue_latest().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn should_manage_orphan_files() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        sqlx::query("insert into podcast_orphan_files (file_path) values ('1/1.mp3'), ('1/2.mp3')")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(
+            pool.list_orphan_files().await.unwrap(),
+            vec!["1/1.mp3", "1/2.mp3"]
+        );
+        pool.delete_orphan_file("1/1.mp3").await.unwrap();
+        assert_eq!(pool.list_orphan_files().await.unwrap(), vec!["1/2.mp3"]);
+    }
+}
@@ -1,11 +1,251 @@
+use std::time::Duration;
+
 use anyhow::Context;
+use sqlx::types::Json;
 
-use crate::Wrapper;
-use entertainarr_domain::podcast::entity::PodcastEpisode;
-use entertainarr_domain::podcast::prelude::{ListPodcastEpisodeParams, PodcastEpisodeField};
+use crate::{Wrapper, podcast_namespace};
+use entertainarr_domain::podcast::entity::{
+    PodcastChapter, PodcastEpisode, PodcastEpisodeProgress, PodcastTranscriptCue,
+    PodcastTranscriptLink,
+};
+use entertainarr_domain::podcast::prelude::{
+    ListPodcastEpisodeFilter, ListPodcastEpisodeParams, PodcastEpisodeCursor, PodcastEpisodeField,
+    PodcastEpisodePage, PodcastEpisodeSortValue,
+};
 use entertainarr_domain::prelude::SortOrder;
 
+const UPSERT_PROGRESS_QUERY: &str = r#"insert into user_podcast_episodes (user_id, podcast_episode_id, progress, completed)
+select ?, id, ?, ? from podcast_episodes where id = ?
+on conflict (user_id, podcast_episode_id) do update set
+    progress = excluded.progress,
+    completed = excluded.completed,
+    updated_at = CURRENT_TIMESTAMP
+returning user_id, podcast_episode_id, progress, completed, created_at, updated_at"#;
+
+const SET_COMPLETED_QUERY: &str = r#"insert into user_podcast_episodes (user_id, podcast_episode_id, progress, completed)
+select ?, id, 0, ? from podcast_episodes where id = ?
+on conflict (user_id, podcast_episode_id) do update set
+    progress = case when excluded.completed then user_podcast_episodes.progress else 0 end,
+    completed = excluded.completed,
+    updated_at = CURRENT_TIMESTAMP
+returning user_id, podcast_episode_id, progress, completed, created_at, updated_at"#;
+
+/// Column used to sort the episodes, dates are normalized as they are written in different formats
+/// and missing values are replaced so they can be compared with a cursor
+const fn sort_column(field: PodcastEpisodeField) -> &'static str {
+    match field {
+        PodcastEpisodeField::PublishedAt => "coalesce(datetime(podcast_episodes.published_at), '')",
+        PodcastEpisodeField::Duration => "coalesce(podcast_episodes.duration, -1)",
+        PodcastEpisodeField::Title => "podcast_episodes.title collate nocase",
+        PodcastEpisodeField::ProgressUpdatedAt => {
+            "coalesce(datetime(user_podcast_episodes.updated_at), '')"
+        }
+    }
+}
+
+/// Pushes the value of a cursor, normalized the same way as the matching [sort_column]
+fn push_sort_value<'a>(
+    qb: &mut sqlx::QueryBuilder<'a, sqlx::Sqlite>,
+    value: &'a PodcastEpisodeSortValue,
+) {
+    match value {
+        PodcastEpisodeSortValue::PublishedAt(value)
+        | PodcastEpisodeSortValue::ProgressUpdatedAt(value) => {
+            qb.push("coalesce(datetime(")
+                .push_bind(*value)
+                .push("), '')");
+        }
+        PodcastEpisodeSortValue::Duration(value) => {
+            qb.push("coalesce(")
+                .push_bind(value.map(|value| value.as_secs() as i64))
+                .push(", -1)");
+        }
+        PodcastEpisodeSortValue::Title(value) => {
+            qb.push_bind(value.as_str());
+        }
+    }
+}
+
+/// Joins the progress of the user and pushes the conditions of the filter, returns the keyword to
+/// prefix the next condition with
+fn push_filter<'a>(
+    qb: &mut sqlx::QueryBuilder<'a, sqlx::Sqlite>,
+    user_id: u64,
+    filter: &'a ListPodcastEpisodeFilter,
+) -> &'static str {
+    qb.push(" left outer join user_podcast_episodes on user_podcast_episodes.podcast_episode_id = podcast_episodes.id ");
+    qb.push(" and user_podcast_episodes.user_id = ")
+        .push_bind(user_id as i64);
+
+    let mut clause = " where ";
+    if !filter.podcast_ids.is_empty() {
+        qb.push(clause).push("podcast_episodes.podcast_id in (");
+        let mut separated = qb.separated(", ");
+        for podcast_id in filter.podcast_ids.iter() {
+            separated.push_bind(*podcast_id as i64);
+        }
+        separated.push_unseparated(")");
+        clause = " and ";
+    }
+
+    if let Some(subscribed) = filter.subscribed {
+        qb.push(clause);
+        if !subscribed {
+            qb.push("not ");
+        }
+        qb.push("exists (select 1 from user_podcasts where user_podcasts.podcast_id = podcast_episodes.podcast_id and user_podcasts.user_id = ")
+            .push_bind(user_id as i64)
+            .push(")");
+        clause = " and ";
+    }
+
+    if let Some(watched) = filter.watched {
+        qb.push(clause);
+        if watched {
+            qb.push("user_podcast_episodes.completed");
+        } else {
+            qb.push(
+                "(user_podcast_episodes.completed is null or not user_podcast_episodes.completed)",
+            );
+        }
+        clause = " and ";
+    }
+
+    if let Some(home) = filter.home {
+        qb.push(clause)
+            .push("exists (select 1 from user_podcasts where user_podcasts.podcast_id = podcast_episodes.podcast_id and user_podcasts.user_id = ")
+            .push_bind(user_id as i64)
+            .push(" and user_podcasts.include_in_home = ")
+            .push_bind(home)
+            .push(")");
+        clause = " and ";
+    }
+
+    if let Some(in_progress) = filter.in_progress {
+        qb.push(clause);
+        if in_progress {
+            qb.push("(user_podcast_episodes.progress > 0 and not user_podcast_episodes.completed)");
+        } else {
+            qb.push("(user_podcast_episodes.progress is null or user_podcast_episodes.progress = 0 or user_podcast_episodes.completed)");
+        }
+        clause = " and ";
+    }
+
+    if let Some(published_after) = filter.published_after {
+        qb.push(clause)
+            .push("datetime(podcast_episodes.published_at) >= datetime(")
+            .push_bind(published_after)
+            .push(")");
+        clause = " and ";
+    }
+
+    if let Some(published_before) = filter.published_before {
+        qb.push(clause)
+            .push("datetime(podcast_episodes.published_at) < datetime(")
+            .push_bind(published_before)
+            .push(")");
+        clause = " and ";
+    }
+
+    if let Some(min_duration) = filter.min_duration {
+        qb.push(clause)
+            .push("podcast_episodes.duration >= ")
+            .push_bind(min_duration.as_secs() as i64);
+        clause = " and ";
+    }
+
+    if let Some(max_duration) = filter.max_duration {
+        qb.push(clause)
+            .push("podcast_episodes.duration <= ")
+            .push_bind(max_duration.as_secs() as i64);
+        clause = " and ";
+    }
+
+    if let Some(file_type) = filter.file_type.as_deref() {
+        qb.push(clause)
+            .push("podcast_episodes.file_type = ")
+            .push_bind(file_type);
+        clause = " and ";
+    }
+
+    clause
+}
+
+/// Reads the episode without the progress of a user, the progress columns being null
+const FIND_PODCAST_EPISODE_BY_ID_QUERY: &str = r#"select
+    id,
+    podcast_id,
+    guid,
+    published_at,
+    title,
+    description,
+    link,
+    duration,
+    file_url,
+    file_size,
+    file_type,
+    removed_at,
+    created_at,
+    updated_at,
+    image_url,
+    season_number,
+    episode_number,
+    episode_type,
+    chapters_url,
+    transcript_url,
+    transcript_type,
+    transcript_language,
+    persons,
+    null,
+    null,
+    null,
+    null,
+    null,
+    null,
+    exists (select 1 from podcast_episode_downloads where podcast_episode_downloads.podcast_episode_id = podcast_episodes.id and podcast_episode_downloads.status = 'completed')
+from podcast_episodes
+where id = ?
+limit 1"#;
+
+const FIND_RESOURCE_QUERY: &str = "select content from podcast_episode_resources where podcast_episode_id = ? and kind = ? and url = ?";
+const UPSERT_RESOURCE_QUERY: &str = r#"insert into podcast_episode_resources (podcast_episode_id, kind, url, content)
+values (?, ?, ?, ?)
+on conflict (podcast_episode_id, kind) do update set
+    url = excluded.url,
+    content = excluded.content,
+    created_at = CURRENT_TIMESTAMP"#;
+
+const CHAPTERS_RESOURCE: &str = "chapters";
+const TRANSCRIPT_RESOURCE: &str = "transcript";
+
 impl entertainarr_domain::podcast::prelude::PodcastEpisodeRepository for super::Pool {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_episodes",
+            db.query.text = FIND_PODCAST_EPISODE_BY_ID_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn find_by_id(&self, podcast_episode_id: u64) -> anyhow::Result<Option<PodcastEpisode>> {
+        sqlx::query_as(FIND_PODCAST_EPISODE_BY_ID_QUERY)
+            .bind(podcast_episode_id as i64)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(Wrapper::maybe_inner)
+            .context("unable to find podcast episode")
+    }
+
     #[tracing::instrument(
         skip_all,
         fields(
@@ -22,7 +262,17 @@ impl entertainarr_domain::podcast::prelude::PodcastEpisodeRepository for super::
         ),
         err(Debug),
     )]
-    async fn list(&self, params: ListPodcastEpisodeParams) -> anyhow::Result<Vec<PodcastEpisode>> {
+    async fn list(&self, params: ListPodcastEpisodeParams) -> anyhow::Result<PodcastEpisodePage> {
+        let mut count_qb: sqlx::QueryBuilder<'_, sqlx::Sqlite> =
+            sqlx::QueryBuilder::new("select count(*) from podcast_episodes");
+        push_filter(&mut count_qb, params.user_id, &params.filter);
+        let total: i64 = count_qb
+            .build_query_scalar()
+            .fetch_one(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .context("unable to count podcast episodes")?;
+
         let mut qb: sqlx::QueryBuilder<'_, sqlx::Sqlite> = sqlx::QueryBuilder::new(
             r#"select
     podcast_episodes.id,
@@ -36,55 +286,320 @@ impl entertainarr_domain::podcast::prelude::PodcastEpisodeRepository for super::
     podcast_episodes.file_url,
     podcast_episodes.file_size,
     podcast_episodes.file_type,
+    podcast_episodes.removed_at,
     podcast_episodes.created_at,
-    podcast_episodes.updated_at
+    podcast_episodes.updated_at,
+    podcast_episodes.image_url,
+    podcast_episodes.season_number,
+    podcast_episodes.episode_number,
+    podcast_episodes.episode_type,
+    podcast_episodes.chapters_url,
+    podcast_episodes.transcript_url,
+    podcast_episodes.transcript_type,
+    podcast_episodes.transcript_language,
+    podcast_episodes.persons,
+    user_podcast_episodes.user_id,
+    user_podcast_episodes.podcast_episode_id,
+    user_podcast_episodes.progress,
+    user_podcast_episodes.completed,
+    user_podcast_episodes.created_at,
+    user_podcast_episodes.updated_at,
+    exists (select 1 from podcast_episode_downloads where podcast_episode_downloads.podcast_episode_id = podcast_episodes.id and podcast_episode_downloads.status = 'completed')
 from podcast_episodes"#,
         );
 
-        if let Some(subscribed) = params.filter.subscribed {
-            if subscribed {
-                qb.push(" join user_podcasts on user_podcasts.podcast_id = podcast_episodes.podcast_id and user_podcasts.user_id = ").push_bind(params.user_id as i64);
-            } else {
-                // TODO handle filtered those where the user is not subscribed
-            }
-        } else {
-            // nothing to do here
-        }
+        let clause = push_filter(&mut qb, params.user_id, &params.filter);
+
+        let column = sort_column(params.sort.field);
+        let (operator, order) = match params.sort.order {
+            SortOrder::Asc => (" > ", " asc"),
+            SortOrder::Desc => (" < ", " desc"),
+        };
 
-        if let Some(watched) = params.filter.watched {
-            qb.push(" left outer join user_podcast_episodes on user_podcast_episodes.podcast_episode_id = podcast_episodes.id ");
-            qb.push(" and user_podcast_episodes.user_id = ")
-                .push_bind(params.user_id as i64);
-            if watched {
-                qb.push(" where user_podcast_episodes.completed");
-            } else {
-                qb.push(" where (user_podcast_episodes.completed is null or not user_podcast_episodes.completed)");
-            }
+        if let Some(cursor) = params.cursor.as_ref() {
+            qb.push(clause).push("(").push(column).push(operator);
+            push_sort_value(&mut qb, &cursor.value);
+            qb.push(" or (").push(column).push(" = ");
+            push_sort_value(&mut qb, &cursor.value);
+            qb.push(" and podcast_episodes.id")
+                .push(operator)
+                .push_bind(cursor.id as i64)
+                .push("))");
         }
 
-        match params.sort.field {
-            PodcastEpisodeField::PublishedAt => qb.push(" order by podcast_episodes.published_at"),
-        };
-        match params.sort.order {
-            SortOrder::Asc => qb.push(" asc"),
-            SortOrder::Desc => qb.push(" desc"),
-        };
+        qb.push(" order by ")
+            .push(column)
+            .push(order)
+            .push(", podcast_episodes.id")
+            .push(order);
 
+        // one more episode is fetched to know if a next page exists
         qb.push(" limit ")
-            .push_bind(params.page.limit)
-            .push(" offset ")
-            .push_bind(params.page.offset);
+            .push_bind(params.page.limit.saturating_add(1));
+        if params.cursor.is_none() {
+            qb.push(" offset ").push_bind(params.page.offset);
+        }
 
         let span = tracing::Span::current();
         span.record("db.query.text", qb.sql());
 
-        qb.build_query_as()
+        let mut items: Vec<PodcastEpisode> = qb
+            .build_query_as()
             .fetch_all(&self.0)
             .await
             .inspect(super::record_all)
             .inspect_err(super::record_error)
             .map(Wrapper::list)
-            .context("unable to query podcast episodes")
+            .context("unable to query podcast episodes")?;
+
+        let next_cursor = if items.len() > params.page.limit as usize {
+            items.truncate(params.page.limit as usize);
+            items
+                .last()
+                .map(|last| PodcastEpisodeCursor::new(params.sort.field, last))
+        } else {
+            None
+        };
+
+        Ok(PodcastEpisodePage {
+            items,
+            total: total as u64,
+            next_cursor,
+        })
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "INSERT",
+            db.sql.table = "user_podcast_episodes",
+            db.query.text = UPSERT_PROGRESS_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn upsert_progress(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        progress: Duration,
+        completed: bool,
+    ) -> anyhow::Result<Option<PodcastEpisodeProgress>> {
+        sqlx::query_as(UPSERT_PROGRESS_QUERY)
+            .bind(user_id as i64)
+            .bind(progress.as_secs() as i64)
+            .bind(completed)
+            .bind(podcast_episode_id as i64)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(Wrapper::maybe_inner)
+            .context("unable to upsert podcast episode progress")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "INSERT",
+            db.sql.table = "user_podcast_episodes",
+            db.query.text = SET_COMPLETED_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn set_completed(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        completed: bool,
+    ) -> anyhow::Result<Option<PodcastEpisodeProgress>> {
+        sqlx::query_as(SET_COMPLETED_QUERY)
+            .bind(user_id as i64)
+            .bind(completed)
+            .bind(podcast_episode_id as i64)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(Wrapper::maybe_inner)
+            .context("unable to update podcast episode completion")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_episode_resources",
+            db.query.text = FIND_RESOURCE_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn find_chapters(
+        &self,
+        podcast_episode_id: u64,
+        url: &str,
+    ) -> anyhow::Result<Option<Vec<PodcastChapter>>> {
+        sqlx::query_scalar(FIND_RESOURCE_QUERY)
+            .bind(podcast_episode_id as i64)
+            .bind(CHAPTERS_RESOURCE)
+            .bind(url)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(|value: Option<Json<Vec<podcast_namespace::Chapter>>>| {
+                value.map(|Json(items)| items.into_iter().map(PodcastChapter::from).collect())
+            })
+            .context("unable to find podcast episode chapters")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "INSERT",
+            db.sql.table = "podcast_episode_resources",
+            db.query.text = UPSERT_RESOURCE_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn save_chapters(
+        &self,
+        podcast_episode_id: u64,
+        url: &str,
+        chapters: &[PodcastChapter],
+    ) -> anyhow::Result<()> {
+        sqlx::query(UPSERT_RESOURCE_QUERY)
+            .bind(podcast_episode_id as i64)
+            .bind(CHAPTERS_RESOURCE)
+            .bind(url)
+            .bind(Json(
+                chapters
+                    .iter()
+                    .map(podcast_namespace::Chapter::from)
+                    .collect::<Vec<_>>(),
+            ))
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to save podcast episode chapters")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_episode_resources",
+            db.query.text = FIND_RESOURCE_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn find_transcript(
+        &self,
+        podcast_episode_id: u64,
+        url: &str,
+    ) -> anyhow::Result<Option<Vec<PodcastTranscriptCue>>> {
+        sqlx::query_scalar(FIND_RESOURCE_QUERY)
+            .bind(podcast_episode_id as i64)
+            .bind(TRANSCRIPT_RESOURCE)
+            .bind(url)
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(
+                |value: Option<Json<Vec<podcast_namespace::TranscriptCue>>>| {
+                    value.map(|Json(items)| {
+                        items.into_iter().map(PodcastTranscriptCue::from).collect()
+                    })
+                },
+            )
+            .context("unable to find podcast episode transcript")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "INSERT",
+            db.sql.table = "podcast_episode_resources",
+            db.query.text = UPSERT_RESOURCE_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn save_transcript(
+        &self,
+        podcast_episode_id: u64,
+        url: &str,
+        cues: &[PodcastTranscriptCue],
+    ) -> anyhow::Result<()> {
+        sqlx::query(UPSERT_RESOURCE_QUERY)
+            .bind(podcast_episode_id as i64)
+            .bind(TRANSCRIPT_RESOURCE)
+            .bind(url)
+            .bind(Json(
+                cues.iter()
+                    .map(podcast_namespace::TranscriptCue::from)
+                    .collect::<Vec<_>>(),
+            ))
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to save podcast episode transcript")
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<PodcastEpisodeProgress> {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        Ok(Self(PodcastEpisodeProgress {
+            user_id: row.try_get(0)?,
+            podcast_episode_id: row.try_get(1)?,
+            progress: row.try_get(2).map(Duration::from_secs)?,
+            completed: row.try_get(3)?,
+            created_at: row.try_get(4)?,
+            updated_at: row.try_get(5)?,
+        }))
     }
 }
 
@@ -102,29 +617,66 @@ impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<PodcastEp
             link: row.try_get(6)?,
             duration: row
                 .try_get(7)
-                .map(|value: Option<u64>| value.map(std::time::Duration::from_secs))?,
+                .map(|value: Option<u64>| value.map(Duration::from_secs))?,
             file_url: row.try_get(8)?,
             file_size: row.try_get(9)?,
             file_type: row.try_get(10)?,
-            created_at: row.try_get(11)?,
-            updated_at: row.try_get(12)?,
+            removed_at: row.try_get(11)?,
+            downloaded: row.try_get(29)?,
+            image_url: row.try_get(14)?,
+            season_number: row.try_get(15)?,
+            episode_number: row.try_get(16)?,
+            episode_type: row
+                .try_get::<Option<String>, _>(17)?
+                .map(|value| value.parse())
+                .transpose()
+                .map_err(|err: anyhow::Error| sqlx::Error::ColumnDecode {
+                    index: "episode_type".into(),
+                    source: err.into(),
+                })?,
+            chapters_url: row.try_get(18)?,
+            transcript: match row.try_get::<Option<String>, _>(19)? {
+                Some(url) => Some(PodcastTranscriptLink {
+                    url,
+                    mime_type: row.try_get(20)?,
+                    language: row.try_get(21)?,
+                }),
+                None => None,
+            },
+            persons: podcast_namespace::decode::<podcast_namespace::Person, _>(row.try_get(22)?),
+            progress: match row.try_get::<Option<u64>, _>(23)? {
+                Some(user_id) => Some(PodcastEpisodeProgress {
+                    user_id,
+                    podcast_episode_id: row.try_get(24)?,
+                    progress: row.try_get(25).map(Duration::from_secs)?,
+                    completed: row.try_get(26)?,
+                    created_at: row.try_get(27)?,
+                    updated_at: row.try_get(28)?,
+                }),
+                None => None,
+            },
+            created_at: row.try_get(12)?,
+            updated_at: row.try_get(13)?,
         }))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::Pool;
+    use entertainarr_domain::podcast::entity::PodcastChapter;
     use entertainarr_domain::podcast::prelude::{
         ListPodcastEpisodeFilter, ListPodcastEpisodeParams, PodcastEpisodeField,
-        PodcastEpisodeRepository,
+        PodcastEpisodeRepository, PodcastEpisodeSortValue,
     };
     use entertainarr_domain::prelude::{Page, Sort, SortOrder};
 
     async fn seed(pool: &Pool) {
         let _: Vec<u64> = sqlx::query_scalar("insert into users (id, email, password) values (1, 'user1@example.com', 'password'), (2, 'user2@example.com', 'password') returning id").fetch_all(pool.as_ref()).await.unwrap();
         let _: Vec<u64> = sqlx::query_scalar("insert into podcasts (id, feed_url, title) values (1, 'first', 'first'), (2, 'second', 'second'), (3, 'third', 'third') returning id").fetch_all(pool.as_ref()).await.unwrap();
-        let _: Vec<u64> = sqlx::query_scalar("insert into podcast_episodes (id, podcast_id, title, file_url) values (1, 1, 'title 1', 'url 1'), (2, 1, 'title 2', 'url 2'), (3, 1, 'title 3', 'url 3'), (4, 2, 'title 4', 'url 4'), (5, 2, 'title 5', 'url 5'), (6, 3, 'title 6', 'url 6') returning id").fetch_all(pool.as_ref()).await.unwrap();
+        let _: Vec<u64> = sqlx::query_scalar("insert into podcast_episodes (id, podcast_id, identity, title, file_url) values (1, 1, 'guid 1', 'title 1', 'url 1'), (2, 1, 'guid 2', 'title 2', 'url 2'), (3, 1, 'guid 3', 'title 3', 'url 3'), (4, 2, 'guid 4', 'title 4', 'url 4'), (5, 2, 'guid 5', 'title 5', 'url 5'), (6, 3, 'guid 6', 'title 6', 'url 6') returning id").fetch_all(pool.as_ref()).await.unwrap();
         let _: Vec<(u64, u64)> = sqlx::query_as(
             "insert into user_podcasts (user_id, podcast_id) values (1, 1), (1, 2), (2, 2), (2, 3) returning user_id, podcast_id",
         )
@@ -148,6 +700,7 @@ mod tests {
                 filter: ListPodcastEpisodeFilter {
                     subscribed: Some(true),
                     watched: None,
+                    ..Default::default()
                 },
                 sort: Sort {
                     field: PodcastEpisodeField::PublishedAt,
@@ -157,10 +710,266 @@ mod tests {
                     limit: 10,
                     offset: 0,
                 },
+                cursor: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(list.items.len(), 5);
+        assert_eq!(list.total, 5);
+        assert!(list.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_list_episodes_included_in_home() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        sqlx::query(
+            "update user_podcasts set include_in_home = false where user_id = 1 and podcast_id = 2",
+        )
+        .execute(pool.as_ref())
+        .await
+        .unwrap();
+        let list = pool
+            .list(ListPodcastEpisodeParams {
+                user_id: 1,
+                filter: ListPodcastEpisodeFilter {
+                    subscribed: None,
+                    watched: None,
+                    home: Some(true),
+                    ..Default::default()
+                },
+                sort: Sort {
+                    field: PodcastEpisodeField::PublishedAt,
+                    order: SortOrder::Asc,
+                },
+                page: Page {
+                    limit: 10,
+                    offset: 0,
+                },
+                cursor: None,
+            })
+            .await
+            .unwrap();
+        let mut ids = list.items.iter().map(|item| item.id).collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    async fn list_ids(
+        pool: &Pool,
+        filter: ListPodcastEpisodeFilter,
+        field: PodcastEpisodeField,
+        order: SortOrder,
+    ) -> Vec<u64> {
+        pool.list(ListPodcastEpisodeParams {
+            user_id: 1,
+            filter,
+            sort: Sort { field, order },
+            page: Page {
+                limit: 10,
+                offset: 0,
+            },
+            cursor: None,
+        })
+        .await
+        .unwrap()
+        .items
+        .into_iter()
+        .map(|item| item.id)
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn should_list_unsubscribed_episodes() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        let ids = list_ids(
+            &pool,
+            ListPodcastEpisodeFilter {
+                subscribed: Some(false),
+                ..Default::default()
+            },
+            PodcastEpisodeField::Title,
+            SortOrder::Asc,
+        )
+        .await;
+        assert_eq!(ids, vec![6]);
+    }
+
+    #[tokio::test]
+    async fn should_list_episodes_with_largest_limit() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        let list = pool
+            .list(ListPodcastEpisodeParams {
+                user_id: 1,
+                filter: ListPodcastEpisodeFilter::default(),
+                sort: Sort {
+                    field: PodcastEpisodeField::PublishedAt,
+                    order: SortOrder::Asc,
+                },
+                page: Page {
+                    limit: u32::MAX,
+                    offset: 0,
+                },
+                cursor: None,
             })
             .await
             .unwrap();
-        assert_eq!(list.len(), 5);
+        assert_eq!(list.items.len(), 6);
+        assert!(list.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_paginate_episodes_with_cursor() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        sqlx::query("update podcast_episodes set duration = case id when 3 then null when 4 then 1200 when 6 then null else 600 end")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+
+        let mut params = ListPodcastEpisodeParams {
+            user_id: 1,
+            filter: ListPodcastEpisodeFilter::default(),
+            sort: Sort {
+                field: PodcastEpisodeField::Duration,
+                order: SortOrder::Desc,
+            },
+            page: Page {
+                limit: 2,
+                offset: 0,
+            },
+            cursor: None,
+        };
+
+        let first = pool.list(params.clone()).await.unwrap();
+        assert_eq!(
+            first.items.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+        assert_eq!(first.total, 6);
+        let cursor = first.next_cursor.unwrap();
+        assert_eq!(cursor.id, 5);
+        assert_eq!(
+            cursor.value,
+            PodcastEpisodeSortValue::Duration(Some(Duration::from_secs(600)))
+        );
+
+        // an episode landing before the cursor doesn't shift the next pages
+        sqlx::query("insert into podcast_episodes (id, podcast_id, identity, title, file_url, duration) values (7, 1, 'guid 7', 'title 7', 'url 7', 3000)")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+
+        params.cursor = Some(cursor);
+        let second = pool.list(params.clone()).await.unwrap();
+        assert_eq!(
+            second.items.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(second.total, 7);
+
+        params.cursor = second.next_cursor;
+        let third = pool.list(params.clone()).await.unwrap();
+        assert_eq!(
+            third.items.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![6, 3]
+        );
+        assert!(third.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_filter_and_sort_episodes() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        sqlx::query("update podcast_episodes set duration = id * 600, file_type = case when id % 2 = 0 then 'audio/mpeg' else 'video/mp4' end, published_at = '2025-01-0' || id || ' 00:00:00'")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+        sqlx::query("update user_podcast_episodes set updated_at = case podcast_episode_id when 1 then '2025-02-01 00:00:00' else '2025-01-15 00:00:00' end where user_id = 1")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+
+        let ids = list_ids(
+            &pool,
+            ListPodcastEpisodeFilter {
+                podcast_ids: vec![1, 3],
+                ..Default::default()
+            },
+            PodcastEpisodeField::Duration,
+            SortOrder::Desc,
+        )
+        .await;
+        assert_eq!(ids, vec![6, 3, 2, 1]);
+
+        let ids = list_ids(
+            &pool,
+            ListPodcastEpisodeFilter {
+                min_duration: Some(Duration::from_secs(1200)),
+                max_duration: Some(Duration::from_secs(3000)),
+                file_type: Some("audio/mpeg".into()),
+                ..Default::default()
+            },
+            PodcastEpisodeField::Title,
+            SortOrder::Asc,
+        )
+        .await;
+        assert_eq!(ids, vec![2, 4]);
+
+        let ids = list_ids(
+            &pool,
+            ListPodcastEpisodeFilter {
+                published_after: Some("2025-01-02T00:00:00Z".parse().unwrap()),
+                published_before: Some("2025-01-05T00:00:00Z".parse().unwrap()),
+                ..Default::default()
+            },
+            PodcastEpisodeField::PublishedAt,
+            SortOrder::Desc,
+        )
+        .await;
+        assert_eq!(ids, vec![4, 3, 2]);
+
+        let ids = list_ids(
+            &pool,
+            ListPodcastEpisodeFilter {
+                in_progress: Some(true),
+                ..Default::default()
+            },
+            PodcastEpisodeField::PublishedAt,
+            SortOrder::Asc,
+        )
+        .await;
+        assert_eq!(ids, vec![4]);
+
+        let ids = list_ids(
+            &pool,
+            ListPodcastEpisodeFilter {
+                podcast_ids: vec![1, 2],
+                ..Default::default()
+            },
+            PodcastEpisodeField::ProgressUpdatedAt,
+            SortOrder::Desc,
+        )
+        .await;
+        assert_eq!(&ids[..2], &[1, 4]);
     }
 
     #[tokio::test]
@@ -177,6 +986,7 @@ mod tests {
                 filter: ListPodcastEpisodeFilter {
                     subscribed: Some(true),
                     watched: Some(true),
+                    ..Default::default()
                 },
                 sort: Sort {
                     field: PodcastEpisodeField::PublishedAt,
@@ -186,10 +996,11 @@ mod tests {
                     limit: 10,
                     offset: 0,
                 },
+                cursor: None,
             })
             .await
             .unwrap();
-        assert_eq!(list.len(), 1);
+        assert_eq!(list.items.len(), 1);
     }
 
     #[tokio::test]
@@ -206,6 +1017,7 @@ mod tests {
                 filter: ListPodcastEpisodeFilter {
                     subscribed: Some(true),
                     watched: Some(false),
+                    ..Default::default()
                 },
                 sort: Sort {
                     field: PodcastEpisodeField::PublishedAt,
@@ -215,9 +1027,138 @@ mod tests {
                     limit: 10,
                     offset: 0,
                 },
+                cursor: None,
             })
             .await
             .unwrap();
-        assert_eq!(list.len(), 4);
+        assert_eq!(list.items.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn should_list_unwatched_episodes_of_podcast() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        let list = pool
+            .list(ListPodcastEpisodeParams {
+                user_id: 1,
+                filter: ListPodcastEpisodeFilter {
+                    podcast_ids: vec![1],
+                    subscribed: None,
+                    watched: Some(false),
+                    ..Default::default()
+                },
+                sort: Sort {
+                    field: PodcastEpisodeField::PublishedAt,
+                    order: SortOrder::Asc,
+                },
+                page: Page {
+                    limit: 10,
+                    offset: 0,
+                },
+                cursor: None,
+            })
+            .await
+            .unwrap();
+        let mut ids = list.items.iter().map(|item| item.id).collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn should_track_progress() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        let progress = pool
+            .upsert_progress(1, 2, Duration::from_secs(42), false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(progress.progress, Duration::from_secs(42));
+        assert!(!progress.completed);
+
+        let progress = pool.set_completed(1, 2, true).await.unwrap().unwrap();
+        assert_eq!(progress.progress, Duration::from_secs(42));
+        assert!(progress.completed);
+
+        let progress = pool.set_completed(1, 2, false).await.unwrap().unwrap();
+        assert_eq!(progress.progress, Duration::ZERO);
+        assert!(!progress.completed);
+
+        let list = pool
+            .list(ListPodcastEpisodeParams {
+                user_id: 1,
+                filter: ListPodcastEpisodeFilter {
+                    subscribed: Some(true),
+                    watched: None,
+                    ..Default::default()
+                },
+                sort: Sort {
+                    field: PodcastEpisodeField::PublishedAt,
+                    order: SortOrder::Asc,
+                },
+                page: Page {
+                    limit: 10,
+                    offset: 0,
+                },
+                cursor: None,
+            })
+            .await
+            .unwrap();
+        let first = list.items.iter().find(|item| item.id == 1).unwrap();
+        assert!(first.progress.as_ref().unwrap().completed);
+        let third = list.items.iter().find(|item| item.id == 3).unwrap();
+        assert!(third.progress.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_not_track_progress_of_unknown_episode() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        let progress = pool
+            .upsert_progress(1, 42, Duration::from_secs(42), false)
+            .await
+            .unwrap();
+        assert!(progress.is_none());
+        let progress = pool.set_completed(1, 42, true).await.unwrap();
+        assert!(progress.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_cache_chapters_by_url() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+
+        seed(&pool).await;
+        let episode = pool.find_by_id(2).await.unwrap().unwrap();
+        assert_eq!(episode.title, "title 2");
+        assert!(pool.find_by_id(42).await.unwrap().is_none());
+
+        assert!(pool.find_chapters(2, "first").await.unwrap().is_none());
+        let chapters = vec![PodcastChapter {
+            start: Duration::from_millis(1500),
+            end: None,
+            title: Some("Intro".into()),
+            image_url: None,
+            url: None,
+        }];
+        pool.save_chapters(2, "first", &chapters).await.unwrap();
+        assert_eq!(
+            pool.find_chapters(2, "first").await.unwrap(),
+            Some(chapters)
+        );
+        assert!(pool.find_chapters(2, "second").await.unwrap().is_none());
+        assert!(pool.find_transcript(2, "first").await.unwrap().is_none());
     }
 }
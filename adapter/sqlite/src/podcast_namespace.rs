@@ -0,0 +1,157 @@
+//! Values of the podcast namespace, stored as json
+
+use std::time::Duration;
+
+use entertainarr_domain::podcast::entity::{
+    PodcastChapter, PodcastFunding, PodcastPerson, PodcastTranscriptCue,
+};
+use sqlx::types::Json;
+
+/// Encodes a list, stored as null when empty
+pub(crate) fn encode<'a, T, R>(items: &'a [T]) -> Option<Json<Vec<R>>>
+where
+    R: From<&'a T>,
+{
+    (!items.is_empty()).then(|| Json(items.iter().map(R::from).collect()))
+}
+
+pub(crate) fn decode<R, T>(value: Option<Json<Vec<R>>>) -> Vec<T>
+where
+    T: From<R>,
+{
+    value
+        .map(|Json(items)| items.into_iter().map(T::from).collect())
+        .unwrap_or_default()
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub(crate) struct Person {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    href: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image_url: Option<String>,
+}
+
+impl From<&PodcastPerson> for Person {
+    fn from(value: &PodcastPerson) -> Self {
+        Self {
+            name: value.name.clone(),
+            role: value.role.clone(),
+            group: value.group.clone(),
+            href: value.href.clone(),
+            image_url: value.image_url.clone(),
+        }
+    }
+}
+
+impl From<Person> for PodcastPerson {
+    fn from(value: Person) -> Self {
+        Self {
+            name: value.name,
+            role: value.role,
+            group: value.group,
+            href: value.href,
+            image_url: value.image_url,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub(crate) struct Funding {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+}
+
+impl From<&PodcastFunding> for Funding {
+    fn from(value: &PodcastFunding) -> Self {
+        Self {
+            url: value.url.clone(),
+            title: value.title.clone(),
+        }
+    }
+}
+
+impl From<Funding> for PodcastFunding {
+    fn from(value: Funding) -> Self {
+        Self {
+            url: value.url,
+            title: value.title,
+        }
+    }
+}
+
+/// Chapter with its times in seconds
+#[derive(serde::Deserialize, serde::Serialize)]
+pub(crate) struct Chapter {
+    start: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+impl From<&PodcastChapter> for Chapter {
+    fn from(value: &PodcastChapter) -> Self {
+        Self {
+            start: value.start.as_secs_f64(),
+            end: value.end.as_ref().map(Duration::as_secs_f64),
+            title: value.title.clone(),
+            image_url: value.image_url.clone(),
+            url: value.url.clone(),
+        }
+    }
+}
+
+impl From<Chapter> for PodcastChapter {
+    fn from(value: Chapter) -> Self {
+        Self {
+            start: Duration::from_secs_f64(value.start),
+            end: value.end.map(Duration::from_secs_f64),
+            title: value.title,
+            image_url: value.image_url,
+            url: value.url,
+        }
+    }
+}
+
+/// Transcript cue with its times in seconds
+#[derive(serde::Deserialize, serde::Serialize)]
+pub(crate) struct TranscriptCue {
+    start: f64,
+    end: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    speaker: Option<String>,
+    text: String,
+}
+
+impl From<&PodcastTranscriptCue> for TranscriptCue {
+    fn from(value: &PodcastTranscriptCue) -> Self {
+        Self {
+            start: value.start.as_secs_f64(),
+            end: value.end.as_secs_f64(),
+            speaker: value.speaker.clone(),
+            text: value.text.clone(),
+        }
+    }
+}
+
+impl From<TranscriptCue> for PodcastTranscriptCue {
+    fn from(value: TranscriptCue) -> Self {
+        Self {
+            start: Duration::from_secs_f64(value.start),
+            end: Duration::from_secs_f64(value.end),
+            speaker: value.speaker,
+            text: value.text,
+        }
+    }
+}
@@ -0,0 +1,442 @@
+use anyhow::Context;
+
+use crate::Wrapper;
+use entertainarr_domain::podcast::entity::PodcastEpisode;
+
+const LIST_QUERY: &str = r#"select
+    podcast_episodes.id,
+    podcast_episodes.podcast_id,
+    podcast_episodes.guid,
+    podcast_episodes.published_at,
+    podcast_episodes.title,
+    podcast_episodes.description,
+    podcast_episodes.link,
+    podcast_episodes.duration,
+    podcast_episodes.file_url,
+    podcast_episodes.file_size,
+    podcast_episodes.file_type,
+    podcast_episodes.removed_at,
+    podcast_episodes.created_at,
+    podcast_episodes.updated_at,
+    podcast_episodes.image_url,
+    podcast_episodes.season_number,
+    podcast_episodes.episode_number,
+    podcast_episodes.episode_type,
+    podcast_episodes.chapters_url,
+    podcast_episodes.transcript_url,
+    podcast_episodes.transcript_type,
+    podcast_episodes.transcript_language,
+    podcast_episodes.persons,
+    user_podcast_episodes.user_id,
+    user_podcast_episodes.podcast_episode_id,
+    user_podcast_episodes.progress,
+    user_podcast_episodes.completed,
+    user_podcast_episodes.created_at,
+    user_podcast_episodes.updated_at,
+    exists (select 1 from podcast_episode_downloads where podcast_episode_downloads.podcast_episode_id = podcast_episodes.id and podcast_episode_downloads.status = 'completed')
+from user_queue
+join podcast_episodes on podcast_episodes.id = user_queue.podcast_episode_id
+left outer join user_podcast_episodes on user_podcast_episodes.podcast_episode_id = podcast_episodes.id and user_podcast_episodes.user_id = ?1
+where user_queue.user_id = ?1
+order by user_queue.position"#;
+const PUSH_BACK_QUERY: &str = r#"insert into user_queue (user_id, podcast_episode_id, position)
+select ?1, ?2, coalesce((select max(position) + 1 from user_queue where user_id = ?1), 0)
+on conflict (user_id, podcast_episode_id) do nothing"#;
+const DELETE_QUERY: &str =
+    "delete from user_queue where user_id = ? and podcast_episode_id = ? returning position";
+const COMPACT_QUERY: &str =
+    "update user_queue set position = position - 1 where user_id = ? and position > ?";
+/// Makes room at the head of the queue, for the episodes that were before the removed one
+const SHIFT_QUERY: &str = "update user_queue set position = position + 1 where user_id = ?1 and (?2 is null or position < ?2)";
+const PUSH_FRONT_QUERY: &str =
+    "insert into user_queue (user_id, podcast_episode_id, position) values (?, ?, 0)";
+const FIND_POSITION_QUERY: &str = r#"select position, (select count(*) from user_queue where user_id = ?1)
+from user_queue
+where user_id = ?1 and podcast_episode_id = ?2"#;
+/// Moves the episode from `?3` to `?4`, shifting the episodes in between
+const MOVE_QUERY: &str = r#"update user_queue set position = case
+    when podcast_episode_id = ?2 then ?4
+    when ?4 < ?3 then position + 1
+    else position - 1
+end
+where user_id = ?1 and position between min(?3, ?4) and max(?3, ?4)"#;
+/// Existing episodes are not queued when enabling the option
+const SET_AUTO_QUEUE_QUERY: &str = r#"update user_podcasts set
+    auto_queue_after = case
+        when auto_queue and ?3 then auto_queue_after
+        else coalesce((select max(id) from podcast_episodes where podcast_id = ?2), 0)
+    end,
+    auto_queue = ?3
+where user_id = ?1 and podcast_id = ?2"#;
+/// Appends the episodes still in their feed and not played yet, oldest first
+const APPEND_NEW_EPISODES_QUERY: &str = r#"insert into user_queue (user_id, podcast_episode_id, position)
+select
+    user_podcasts.user_id,
+    podcast_episodes.id,
+    (select coalesce(max(position) + 1, 0) from user_queue where user_queue.user_id = user_podcasts.user_id)
+        + row_number() over (partition by user_podcasts.user_id order by podcast_episodes.published_at asc nulls last, podcast_episodes.id asc)
+        - 1
+from user_podcasts
+join podcast_episodes on podcast_episodes.podcast_id = user_podcasts.podcast_id
+where user_podcasts.auto_queue
+    and podcast_episodes.id > user_podcasts.auto_queue_after
+    and podcast_episodes.removed_at is null
+    and not exists (select 1 from user_queue where user_queue.user_id = user_podcasts.user_id and user_queue.podcast_episode_id = podcast_episodes.id)
+    and not exists (select 1 from user_podcast_episodes where user_podcast_episodes.user_id = user_podcasts.user_id and user_podcast_episodes.podcast_episode_id = podcast_episodes.id and user_podcast_episodes.completed)"#;
+const UPDATE_AUTO_QUEUE_AFTER_QUERY: &str = r#"update user_podcasts
+set auto_queue_after = coalesce((select max(id) from podcast_episodes where podcast_id = user_podcasts.podcast_id), auto_queue_after)
+where auto_queue"#;
+
+impl entertainarr_domain::podcast::prelude::PodcastQueueRepository for super::Pool {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "SELECT",
+            db.sql.table = "user_queue",
+            db.query.text = LIST_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn list(&self, user_id: u64) -> anyhow::Result<Vec<PodcastEpisode>> {
+        sqlx::query_as(LIST_QUERY)
+            .bind(user_id as i64)
+            .fetch_all(&self.0)
+            .await
+            .inspect(super::record_all)
+            .inspect_err(super::record_error)
+            .map(Wrapper::list)
+            .context("unable to list queued podcast episodes")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "INSERT",
+            db.sql.table = "user_queue",
+            db.query.text = PUSH_BACK_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn push_back(&self, user_id: u64, podcast_episode_id: u64) -> anyhow::Result<()> {
+        sqlx::query(PUSH_BACK_QUERY)
+            .bind(user_id as i64)
+            .bind(podcast_episode_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|_| ())
+            .context("unable to queue podcast episode")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "INSERT",
+            db.sql.table = "user_queue",
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn push_front(&self, user_id: u64, podcast_episode_id: u64) -> anyhow::Result<()> {
+        let mut tx = self
+            .0
+            .begin()
+            .await
+            .context("unable to begin transaction")?;
+
+        let previous: Option<i64> = sqlx::query_scalar(DELETE_QUERY)
+            .bind(user_id as i64)
+            .bind(podcast_episode_id as i64)
+            .fetch_optional(&mut *tx)
+            .await
+            .inspect_err(super::record_error)
+            .context("unable to remove queued podcast episode")?;
+        sqlx::query(SHIFT_QUERY)
+            .bind(user_id as i64)
+            .bind(previous)
+            .execute(&mut *tx)
+            .await
+            .inspect_err(super::record_error)
+            .context("unable to shift queued podcast episodes")?;
+        sqlx::query(PUSH_FRONT_QUERY)
+            .bind(user_id as i64)
+            .bind(podcast_episode_id as i64)
+            .execute(&mut *tx)
+            .await
+            .inspect_err(super::record_error)
+            .context("unable to queue podcast episode")?;
+
+        tx.commit().await.context("unable to commit transaction")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "DELETE",
+            db.sql.table = "user_queue",
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn remove(&self, user_id: u64, podcast_episode_id: u64) -> anyhow::Result<bool> {
+        let mut tx = self
+            .0
+            .begin()
+            .await
+            .context("unable to begin transaction")?;
+
+        let Some(position): Option<i64> = sqlx::query_scalar(DELETE_QUERY)
+            .bind(user_id as i64)
+            .bind(podcast_episode_id as i64)
+            .fetch_optional(&mut *tx)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .context("unable to remove queued podcast episode")?
+        else {
+            return Ok(false);
+        };
+        sqlx::query(COMPACT_QUERY)
+            .bind(user_id as i64)
+            .bind(position)
+            .execute(&mut *tx)
+            .await
+            .inspect_err(super::record_error)
+            .context("unable to shift queued podcast episodes")?;
+
+        tx.commit().await.context("unable to commit transaction")?;
+        Ok(true)
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "UPDATE",
+            db.sql.table = "user_queue",
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn move_to(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        position: u32,
+    ) -> anyhow::Result<bool> {
+        let mut tx = self
+            .0
+            .begin()
+            .await
+            .context("unable to begin transaction")?;
+
+        let Some((current, count)): Option<(i64, i64)> = sqlx::query_as(FIND_POSITION_QUERY)
+            .bind(user_id as i64)
+            .bind(podcast_episode_id as i64)
+            .fetch_optional(&mut *tx)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .context("unable to find queued podcast episode")?
+        else {
+            return Ok(false);
+        };
+        let target = (position as i64).min(count - 1);
+        sqlx::query(MOVE_QUERY)
+            .bind(user_id as i64)
+            .bind(podcast_episode_id as i64)
+            .bind(current)
+            .bind(target)
+            .execute(&mut *tx)
+            .await
+            .inspect_err(super::record_error)
+            .context("unable to move queued podcast episode")?;
+
+        tx.commit().await.context("unable to commit transaction")?;
+        Ok(true)
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "UPDATE",
+            db.sql.table = "user_podcasts",
+            db.query.text = SET_AUTO_QUEUE_QUERY,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn set_auto_queue(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        enabled: bool,
+    ) -> anyhow::Result<bool> {
+        sqlx::query(SET_AUTO_QUEUE_QUERY)
+            .bind(user_id as i64)
+            .bind(podcast_id as i64)
+            .bind(enabled)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|res| res.rows_affected() > 0)
+            .context("unable to update podcast auto queue")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "podcast",
+            db.operation = "INSERT",
+            db.sql.table = "user_queue",
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn append_new_episodes(&self) -> anyhow::Result<u64> {
+        let mut tx = self
+            .0
+            .begin()
+            .await
+            .context("unable to begin transaction")?;
+
+        let queued = sqlx::query(APPEND_NEW_EPISODES_QUERY)
+            .execute(&mut *tx)
+            .await
+            .inspect_err(super::record_error)
+            .map(|res| res.rows_affected())
+            .context("unable to queue new podcast episodes")?;
+        sqlx::query(UPDATE_AUTO_QUEUE_AFTER_QUERY)
+            .execute(&mut *tx)
+            .await
+            .inspect_err(super::record_error)
+            .context("unable to update podcast auto queue")?;
+
+        tx.commit().await.context("unable to commit transaction")?;
+        Ok(queued)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entertainarr_domain::podcast::prelude::PodcastQueueRepository;
+
+    use crate::Pool;
+
+    async fn seed(pool: &Pool) {
+        let _: Vec<u64> = sqlx::query_scalar("insert into users (id, email, password) values (1, 'user@example.com', 'password') returning id").fetch_all(pool.as_ref()).await.unwrap();
+        let _: Vec<u64> = sqlx::query_scalar("insert into podcasts (id, feed_url, title) values (1, 'first', 'first'), (2, 'second', 'second') returning id").fetch_all(pool.as_ref()).await.unwrap();
+        let _: Vec<u64> = sqlx::query_scalar("insert into podcast_episodes (id, podcast_id, identity, title, file_url, published_at) values (1, 1, 'guid 1', 'title 1', 'url 1', '2025-01-01 00:00:00'), (2, 1, 'guid 2', 'title 2', 'url 2', '2025-01-02 00:00:00'), (3, 1, 'guid 3', 'title 3', 'url 3', '2025-01-03 00:00:00'), (4, 2, 'guid 4', 'title 4', 'url 4', '2025-01-04 00:00:00') returning id").fetch_all(pool.as_ref()).await.unwrap();
+        let _: Vec<u64> = sqlx::query_scalar(
+            "insert into user_podcasts (user_id, podcast_id) values (1, 1) returning user_id",
+        )
+        .fetch_all(pool.as_ref())
+        .await
+        .unwrap();
+    }
+
+    async fn queued(pool: &Pool) -> Vec<u64> {
+        pool.list(1)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|episode| episode.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_order_queue() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        seed(&pool).await;
+
+        pool.push_back(1, 1).await.unwrap();
+        pool.push_back(1, 2).await.unwrap();
+        pool.push_back(1, 3).await.unwrap();
+        pool.push_back(1, 1).await.unwrap();
+        assert_eq!(queued(&pool).await, vec![1, 2, 3]);
+
+        pool.push_front(1, 4).await.unwrap();
+        assert_eq!(queued(&pool).await, vec![4, 1, 2, 3]);
+        pool.push_front(1, 2).await.unwrap();
+        assert_eq!(queued(&pool).await, vec![2, 4, 1, 3]);
+
+        assert!(pool.move_to(1, 2, 2).await.unwrap());
+        assert_eq!(queued(&pool).await, vec![4, 1, 2, 3]);
+        assert!(pool.move_to(1, 3, 0).await.unwrap());
+        assert_eq!(queued(&pool).await, vec![3, 4, 1, 2]);
+        assert!(pool.move_to(1, 3, 42).await.unwrap());
+        assert_eq!(queued(&pool).await, vec![4, 1, 2, 3]);
+
+        assert!(pool.remove(1, 1).await.unwrap());
+        assert!(!pool.remove(1, 1).await.unwrap());
+        assert!(!pool.move_to(1, 1, 0).await.unwrap());
+        assert_eq!(queued(&pool).await, vec![4, 2, 3]);
+        pool.push_back(1, 1).await.unwrap();
+        assert_eq!(queued(&pool).await, vec![4, 2, 3, 1]);
+    }
+
+    #[tokio::test]
+    async fn should_append_new_episodes() {
+        let _ = tracing_subscriber::fmt::try_init();
+
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        seed(&pool).await;
+
+        assert!(!pool.set_auto_queue(1, 2, true).await.unwrap());
+        assert!(pool.set_auto_queue(1, 1, true).await.unwrap());
+        pool.push_back(1, 4).await.unwrap();
+        // existing episodes are not queued
+        assert_eq!(pool.append_new_episodes().await.unwrap(), 0);
+
+        let _: Vec<u64> = sqlx::query_scalar("insert into podcast_episodes (id, podcast_id, identity, title, file_url, published_at) values (5, 1, 'guid 5', 'title 5', 'url 5', '2025-01-06 00:00:00'), (6, 1, 'guid 6', 'title 6', 'url 6', '2025-01-05 00:00:00'), (7, 2, 'guid 7', 'title 7', 'url 7', '2025-01-07 00:00:00') returning id").fetch_all(pool.as_ref()).await.unwrap();
+        assert_eq!(pool.append_new_episodes().await.unwrap(), 2);
+        assert_eq!(queued(&pool).await, vec![4, 6, 5]);
+
+        // dequeued episodes are not queued again
+        assert!(pool.remove(1, 6).await.unwrap());
+        assert_eq!(pool.append_new_episodes().await.unwrap(), 0);
+        assert_eq!(queued(&pool).await, vec![4, 5]);
+    }
+}
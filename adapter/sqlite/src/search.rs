@@ -0,0 +1,233 @@
+use anyhow::Context;
+
+use crate::Wrapper;
+use entertainarr_domain::podcast::entity::{Podcast, PodcastEpisode};
+
+const SEARCH_PODCASTS_QUERY: &str = r#"select podcasts.id, podcasts.feed_url, podcasts.title, podcasts.description, podcasts.image_url, podcasts.language, podcasts.website, podcasts.last_synced_at, podcasts.last_sync_error, podcasts.feed_etag, podcasts.feed_last_modified, podcasts.created_at, podcasts.updated_at, podcasts.author, podcasts.categories, podcasts.explicit, podcasts.persons, podcasts.funding
+from podcasts_search
+join podcasts on podcasts.id = podcasts_search.rowid
+where podcasts_search match ?
+order by bm25(podcasts_search, 10.0, 1.0)
+limit ?"#;
+const SEARCH_PODCAST_EPISODES_QUERY: &str = r#"select
+    podcast_episodes.id,
+    podcast_episodes.podcast_id,
+    podcast_episodes.guid,
+    podcast_episodes.published_at,
+    podcast_episodes.title,
+    podcast_episodes.description,
+    podcast_episodes.link,
+    podcast_episodes.duration,
+    podcast_episodes.file_url,
+    podcast_episodes.file_size,
+    podcast_episodes.file_type,
+    podcast_episodes.removed_at,
+    podcast_episodes.created_at,
+    podcast_episodes.updated_at,
+    podcast_episodes.image_url,
+    podcast_episodes.season_number,
+    podcast_episodes.episode_number,
+    podcast_episodes.episode_type,
+    podcast_episodes.chapters_url,
+    podcast_episodes.transcript_url,
+    podcast_episodes.transcript_type,
+    podcast_episodes.transcript_language,
+    podcast_episodes.persons,
+    user_podcast_episodes.user_id,
+    user_podcast_episodes.podcast_episode_id,
+    user_podcast_episodes.progress,
+    user_podcast_episodes.completed,
+    user_podcast_episodes.created_at,
+    user_podcast_episodes.updated_at,
+    exists (select 1 from podcast_episode_downloads where podcast_episode_downloads.podcast_episode_id = podcast_episodes.id and podcast_episode_downloads.status = 'completed')
+from podcast_episodes_search
+join podcast_episodes on podcast_episodes.id = podcast_episodes_search.rowid
+left outer join user_podcast_episodes on user_podcast_episodes.podcast_episode_id = podcast_episodes.id
+    and user_podcast_episodes.user_id = ?
+where podcast_episodes_search match ?
+    and podcast_episodes.removed_at is null
+order by bm25(podcast_episodes_search, 10.0, 1.0)
+limit ?"#;
+
+/// Turns the user input into a FTS5 expression, where every word must match as a prefix
+///
+/// Each word is quoted so the FTS5 syntax characters of the input cannot break the query.
+fn match_expression(query: &str) -> Option<String> {
+    let words = query
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+impl entertainarr_domain::search::prelude::SearchRepository for super::Pool {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "search",
+            db.operation = "SELECT",
+            db.sql.table = "podcasts_search",
+            db.query.text = SEARCH_PODCASTS_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn search_podcasts(&self, query: &str, limit: u32) -> anyhow::Result<Vec<Podcast>> {
+        let Some(expression) = match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        sqlx::query_as(SEARCH_PODCASTS_QUERY)
+            .bind(expression)
+            .bind(limit)
+            .fetch_all(&self.0)
+            .await
+            .inspect(super::record_all)
+            .inspect_err(super::record_error)
+            .map(Wrapper::list)
+            .context("unable to search podcasts")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "search",
+            db.operation = "SELECT",
+            db.sql.table = "podcast_episodes_search",
+            db.query.text = SEARCH_PODCAST_EPISODES_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn search_podcast_episodes(
+        &self,
+        user_id: u64,
+        query: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<PodcastEpisode>> {
+        let Some(expression) = match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        sqlx::query_as(SEARCH_PODCAST_EPISODES_QUERY)
+            .bind(user_id as i64)
+            .bind(expression)
+            .bind(limit)
+            .fetch_all(&self.0)
+            .await
+            .inspect(super::record_all)
+            .inspect_err(super::record_error)
+            .map(Wrapper::list)
+            .context("unable to search podcast episodes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entertainarr_domain::search::prelude::SearchRepository;
+
+    use crate::Pool;
+
+    async fn seed(pool: &Pool) {
+        sqlx::query(
+            "insert into users (id, email, password) values (1, 'user1@example.com', 'password')",
+        )
+        .execute(pool.as_ref())
+        .await
+        .unwrap();
+        sqlx::query("insert into podcasts (id, feed_url, title, description) values (1, 'first', 'Rustacean Station', 'A community podcast about Rust'), (2, 'second', 'Café Tech', 'Talks about the web')").execute(pool.as_ref()).await.unwrap();
+        sqlx::query("insert into podcast_episodes (id, podcast_id, identity, title, description, file_url) values (1, 1, 'guid 1', 'Async Rust', 'Talking about futures', 'url 1'), (2, 1, 'guid 2', 'Embedded', 'Rust on microcontrollers', 'url 2'), (3, 2, 'guid 3', 'CSS grids', null, 'url 3')").execute(pool.as_ref()).await.unwrap();
+        sqlx::query("insert into user_podcast_episodes (user_id, podcast_episode_id, progress, completed) values (1, 1, 10, false)").execute(pool.as_ref()).await.unwrap();
+    }
+
+    #[test]
+    fn should_build_match_expression() {
+        assert_eq!(
+            super::match_expression(r#"rust "async"#).as_deref(),
+            Some(r#""rust"* """async"*"#)
+        );
+        assert_eq!(super::match_expression("  - * "), None);
+    }
+
+    #[tokio::test]
+    async fn should_search_podcasts_with_ranking() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        seed(&pool).await;
+
+        let list = pool.search_podcasts("rust", 10).await.unwrap();
+        assert_eq!(list.iter().map(|item| item.id).collect::<Vec<_>>(), vec![1]);
+        // diacritics and prefixes are ignored
+        let list = pool.search_podcasts("cafe te", 10).await.unwrap();
+        assert_eq!(list.iter().map(|item| item.id).collect::<Vec<_>>(), vec![2]);
+        assert!(pool.search_podcasts("\"", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_keep_index_in_sync() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        seed(&pool).await;
+
+        sqlx::query("update podcasts set title = 'Ferris Station' where id = 1")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+        let list = pool.search_podcasts("ferris", 10).await.unwrap();
+        assert_eq!(list.iter().map(|item| item.id).collect::<Vec<_>>(), vec![1]);
+        assert!(
+            pool.search_podcasts("rustacean", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        sqlx::query("delete from podcasts where id = 1")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+        assert!(pool.search_podcasts("ferris", 10).await.unwrap().is_empty());
+        assert!(
+            pool.search_podcast_episodes(1, "rust", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_search_podcast_episodes() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        seed(&pool).await;
+
+        let list = pool.search_podcast_episodes(1, "rust", 10).await.unwrap();
+        // title matches rank before description matches
+        assert_eq!(
+            list.iter().map(|item| item.id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(list[0].progress.is_some());
+        assert!(list[1].progress.is_none());
+
+        sqlx::query("update podcast_episodes set removed_at = CURRENT_TIMESTAMP where id = 1")
+            .execute(pool.as_ref())
+            .await
+            .unwrap();
+        let list = pool.search_podcast_episodes(1, "rust", 10).await.unwrap();
+        assert_eq!(list.iter().map(|item| item.id).collect::<Vec<_>>(), vec![2]);
+    }
+}
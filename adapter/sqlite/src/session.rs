@@ -0,0 +1,403 @@
+use anyhow::Context;
+use sqlx::types::chrono;
+
+use entertainarr_domain::auth::entity::{DeviceInfo, Session};
+
+const CREATE_QUERY: &str = r#"insert into user_sessions (user_id, refresh_token, expires_at, device_name, user_agent, ip_address)
+values (?, ?, ?, ?, ?, ?)
+returning id, user_id, expires_at, revoked_at, device_name, user_agent, ip_address, last_seen_at, created_at, updated_at"#;
+const ROTATE_QUERY: &str = r#"update user_sessions
+set refresh_token = ?, expires_at = ?, updated_at = CURRENT_TIMESTAMP
+where refresh_token = ? and revoked_at is null and expires_at > ?
+returning id, user_id, expires_at, revoked_at, device_name, user_agent, ip_address, last_seen_at, created_at, updated_at"#;
+const REVOKE_QUERY: &str = r#"update user_sessions
+set revoked_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+where id = ? and user_id = ? and revoked_at is null"#;
+const LIST_QUERY: &str = r#"select id, user_id, expires_at, revoked_at, device_name, user_agent, ip_address, last_seen_at, created_at, updated_at
+from user_sessions
+where user_id = ? and revoked_at is null and expires_at > ?
+order by coalesce(last_seen_at, created_at) desc, id desc"#;
+const FIND_ACTIVE_QUERY: &str = r#"select id, user_id, expires_at, revoked_at, device_name, user_agent, ip_address, last_seen_at, created_at, updated_at
+from user_sessions
+where id = ? and user_id = ? and revoked_at is null and expires_at > ?
+limit 1"#;
+const TOUCH_QUERY: &str = r#"update user_sessions
+set last_seen_at = ?
+where id = ? and user_id = ? and revoked_at is null and expires_at > ?"#;
+
+impl entertainarr_domain::auth::prelude::SessionRepository for super::Pool {
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "INSERT",
+            db.sql.table = "user_sessions",
+            db.query.text = CREATE_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn create(
+        &self,
+        user_id: u64,
+        refresh_token: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+        device: &DeviceInfo,
+    ) -> anyhow::Result<Session> {
+        sqlx::query_as(CREATE_QUERY)
+            .bind(user_id as i64)
+            .bind(refresh_token)
+            .bind(expires_at)
+            .bind(device.name.as_deref())
+            .bind(device.user_agent.as_deref())
+            .bind(device.ip_address.as_deref())
+            .fetch_one(&self.0)
+            .await
+            .inspect(super::record_one)
+            .inspect_err(super::record_error)
+            .map(super::Wrapper::inner)
+            .context("unable to create session")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "UPDATE",
+            db.sql.table = "user_sessions",
+            db.query.text = ROTATE_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn rotate(
+        &self,
+        previous_refresh_token: &str,
+        refresh_token: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Option<Session>> {
+        sqlx::query_as(ROTATE_QUERY)
+            .bind(refresh_token)
+            .bind(expires_at)
+            .bind(previous_refresh_token)
+            .bind(chrono::Utc::now())
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(super::Wrapper::maybe_inner)
+            .context("unable to rotate session")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "UPDATE",
+            db.sql.table = "user_sessions",
+            db.query.text = REVOKE_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn revoke(&self, user_id: u64, session_id: u64) -> anyhow::Result<bool> {
+        sqlx::query(REVOKE_QUERY)
+            .bind(session_id as i64)
+            .bind(user_id as i64)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|res| res.rows_affected() > 0)
+            .context("unable to revoke session")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "SELECT",
+            db.sql.table = "user_sessions",
+            db.query.text = LIST_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn list(&self, user_id: u64) -> anyhow::Result<Vec<Session>> {
+        sqlx::query_as(LIST_QUERY)
+            .bind(user_id as i64)
+            .bind(chrono::Utc::now())
+            .fetch_all(&self.0)
+            .await
+            .inspect(super::record_all)
+            .inspect_err(super::record_error)
+            .map(super::Wrapper::list)
+            .context("unable to list sessions")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "SELECT",
+            db.sql.table = "user_sessions",
+            db.query.text = FIND_ACTIVE_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn find_active(&self, user_id: u64, session_id: u64) -> anyhow::Result<Option<Session>> {
+        sqlx::query_as(FIND_ACTIVE_QUERY)
+            .bind(session_id as i64)
+            .bind(user_id as i64)
+            .bind(chrono::Utc::now())
+            .fetch_optional(&self.0)
+            .await
+            .inspect(super::record_optional)
+            .inspect_err(super::record_error)
+            .map(super::Wrapper::maybe_inner)
+            .context("unable to find session")
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "sqlite",
+            db.name = "authentication",
+            db.operation = "UPDATE",
+            db.sql.table = "user_sessions",
+            db.query.text = TOUCH_QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+            error.stacktrace = tracing::field::Empty,
+        ),
+        err(Debug),
+    )]
+    async fn touch(&self, user_id: u64, session_id: u64) -> anyhow::Result<bool> {
+        let now = chrono::Utc::now();
+        sqlx::query(TOUCH_QUERY)
+            .bind(now)
+            .bind(session_id as i64)
+            .bind(user_id as i64)
+            .bind(now)
+            .execute(&self.0)
+            .await
+            .inspect_err(super::record_error)
+            .map(|res| res.rows_affected() > 0)
+            .context("unable to touch session")
+    }
+}
+
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for super::Wrapper<Session> {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row;
+
+        Ok(Self(Session {
+            id: row.try_get(0)?,
+            user_id: row.try_get(1)?,
+            expires_at: row.try_get(2)?,
+            revoked_at: row.try_get(3)?,
+            device: DeviceInfo {
+                name: row.try_get(4)?,
+                user_agent: row.try_get(5)?,
+                ip_address: row.try_get(6)?,
+            },
+            last_seen_at: row.try_get(7)?,
+            created_at: row.try_get(8)?,
+            updated_at: row.try_get(9)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use sqlx::types::chrono;
+
+    use entertainarr_domain::auth::entity::DeviceInfo;
+    use entertainarr_domain::auth::prelude::{AuthenticationRepository, SessionRepository};
+
+    #[tokio::test]
+    async fn should_rotate_refresh_token() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        let profile = AuthenticationRepository::create(&pool, "user@example.com", "password")
+            .await
+            .unwrap();
+        let expires_at = chrono::Utc::now() + Duration::from_secs(60 * 60 * 24);
+        let session =
+            SessionRepository::create(&pool, profile.id, "first", expires_at, &Default::default())
+                .await
+                .unwrap();
+        assert_eq!(session.user_id, profile.id);
+
+        let rotated = pool
+            .rotate("first", "second", expires_at)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rotated.id, session.id);
+        // the previous token cannot be used anymore
+        assert!(
+            pool.rotate("first", "third", expires_at)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_rotate_expired_session() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        let profile = AuthenticationRepository::create(&pool, "user@example.com", "password")
+            .await
+            .unwrap();
+        let expires_at = chrono::Utc::now() - Duration::from_secs(60 * 60 * 24);
+        SessionRepository::create(&pool, profile.id, "first", expires_at, &Default::default())
+            .await
+            .unwrap();
+        let rotated = pool
+            .rotate("first", "second", chrono::Utc::now())
+            .await
+            .unwrap();
+        assert!(rotated.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_not_rotate_revoked_session() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        let profile = AuthenticationRepository::create(&pool, "user@example.com", "password")
+            .await
+            .unwrap();
+        let expires_at = chrono::Utc::now() + Duration::from_secs(60 * 60 * 24);
+        let session =
+            SessionRepository::create(&pool, profile.id, "first", expires_at, &Default::default())
+                .await
+                .unwrap();
+        // another user cannot revoke the session
+        assert!(!pool.revoke(profile.id + 1, session.id).await.unwrap());
+        assert!(pool.revoke(profile.id, session.id).await.unwrap());
+        assert!(!pool.revoke(profile.id, session.id).await.unwrap());
+        let rotated = pool.rotate("first", "second", expires_at).await.unwrap();
+        assert!(rotated.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_list_active_sessions() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        let profile = AuthenticationRepository::create(&pool, "user@example.com", "password")
+            .await
+            .unwrap();
+        let expires_at = chrono::Utc::now() + Duration::from_secs(60 * 60 * 24);
+        let device = DeviceInfo {
+            name: Some(String::from("Living room TV")),
+            user_agent: Some(String::from("Mozilla/5.0")),
+            ip_address: Some(String::from("192.168.1.12")),
+        };
+        let tv = SessionRepository::create(&pool, profile.id, "tv", expires_at, &device)
+            .await
+            .unwrap();
+        assert_eq!(tv.device, device);
+        assert!(tv.last_seen_at.is_none());
+        let phone =
+            SessionRepository::create(&pool, profile.id, "phone", expires_at, &Default::default())
+                .await
+                .unwrap();
+        SessionRepository::create(
+            &pool,
+            profile.id,
+            "expired",
+            chrono::Utc::now() - Duration::from_secs(60),
+            &Default::default(),
+        )
+        .await
+        .unwrap();
+        assert!(pool.revoke(profile.id, phone.id).await.unwrap());
+
+        let sessions = pool.list(profile.id).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, tv.id);
+        assert!(pool.list(profile.id + 1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_touch_active_session_only() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        let profile = AuthenticationRepository::create(&pool, "user@example.com", "password")
+            .await
+            .unwrap();
+        let expires_at = chrono::Utc::now() + Duration::from_secs(60 * 60 * 24);
+        let session =
+            SessionRepository::create(&pool, profile.id, "first", expires_at, &Default::default())
+                .await
+                .unwrap();
+        assert!(pool.touch(profile.id, session.id).await.unwrap());
+        assert!(!pool.touch(profile.id + 1, session.id).await.unwrap());
+        let sessions = pool.list(profile.id).await.unwrap();
+        assert!(sessions[0].last_seen_at.is_some());
+
+        assert!(pool.revoke(profile.id, session.id).await.unwrap());
+        assert!(!pool.touch(profile.id, session.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_find_active_session_only() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let pool = crate::Pool::test(&tmpdir.path().join("db")).await;
+        let profile = AuthenticationRepository::create(&pool, "user@example.com", "password")
+            .await
+            .unwrap();
+        let expires_at = chrono::Utc::now() + Duration::from_secs(60 * 60 * 24);
+        let session =
+            SessionRepository::create(&pool, profile.id, "first", expires_at, &Default::default())
+                .await
+                .unwrap();
+        let found = pool.find_active(profile.id, session.id).await.unwrap();
+        assert_eq!(found.map(|item| item.id), Some(session.id));
+        assert!(
+            pool.find_active(profile.id + 1, session.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        assert!(pool.revoke(profile.id, session.id).await.unwrap());
+        assert!(
+            pool.find_active(profile.id, session.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
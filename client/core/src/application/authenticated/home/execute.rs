@@ -4,29 +4,31 @@ use entertainarr_adapter_http::entity::{
     podcast_episode::{PodcastEpisodeDocument, PodcastEpisodeRelation},
 };
 
+use crate::application::session::retry_on_token_expired;
 use crate::effect::http::HttpError;
 use crate::entity::podcast_episode::PodcastEpisode;
 
 pub fn list_podcast_episodes(base_url: &str, token: &str) -> crate::ApplicationCommand {
-    let url = format!("{base_url}/api/podcast-episodes");
+    // the brackets of the filter must not be encoded
+    let url = format!(
+        "{base_url}/api/podcast-episodes?filter[home]=true&include=podcast&sort=-published_at"
+    );
     Http::get(url)
-        .query(&serde_json::json!({
-            "include": "podcast",
-            "sort": "-published_at",
-        }))
-        .unwrap()
         .header("Authorization", format!("Bearer {token}"))
         .expect_json::<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>>()
         .build()
-        .then_send(|res| {
-            match res {
-                Ok(mut res) => {
-                    let payload = res.take_body().unwrap();
-                    let episodes = PodcastEpisode::from_episode_document_list(payload);
-                    super::HomeEvent::ListPodcastEpisodesSuccess(episodes)
+        .then_send(retry_on_token_expired(
+            super::HomeEvent::ListPodcastEpisodesRequest,
+            |res| {
+                match res {
+                    Ok(mut res) => {
+                        let payload = res.take_body().unwrap();
+                        let episodes = PodcastEpisode::from_episode_document_list(payload);
+                        super::HomeEvent::ListPodcastEpisodesSuccess(episodes)
+                    }
+                    Err(err) => super::HomeEvent::ListPodcastEpisodesError(HttpError::from(err)),
                 }
-                Err(err) => super::HomeEvent::ListPodcastEpisodesError(HttpError::from(err)),
-            }
-            .into()
-        })
+                .into()
+            },
+        ))
 }
@@ -37,3 +37,36 @@ impl crate::application::ApplicationModel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::application::{
+        ApplicationModel, InitializationEvent, authenticated::home::HomeEvent, router::Route,
+    };
+
+    #[test]
+    fn should_only_list_episodes_included_in_home() {
+        let mut model = ApplicationModel::default();
+        let _ = model.update(
+            InitializationEvent {
+                server_url: "http://localhost".into(),
+                authentication_token: Some("token".into()),
+                refresh_token: None,
+                route: Some(Route::Home),
+            }
+            .into(),
+        );
+        let mut cmd = model.update(HomeEvent::ListPodcastEpisodesRequest.into());
+        let request = cmd
+            .effects()
+            .find_map(|effect| match effect {
+                crate::effect::Effect::Http(request) => Some(request),
+                _ => None,
+            })
+            .expect("expected http request");
+        assert_eq!(
+            request.operation.url,
+            "http://localhost/api/podcast-episodes?filter[home]=true&include=podcast&sort=-published_at"
+        );
+    }
+}
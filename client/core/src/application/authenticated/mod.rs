@@ -7,6 +7,8 @@ pub mod podcast;
 pub enum AuthenticatedModel {
     Home(home::HomeModel),
     PodcastDashboard(podcast::dashboard::PodcastDashboardModel),
+    PodcastDiscover(podcast::discover::PodcastDiscoverModel),
+    PodcastQueue(podcast::queue::PodcastQueueModel),
     PodcastSubscribe(podcast::subscribe::PodcastSubscribeModel),
 }
 
@@ -19,7 +21,8 @@ impl AuthenticatedModel {
         match self {
             Self::Home(inner) => inner.on_mount(),
             Self::PodcastDashboard(inner) => inner.on_mount(),
-            Self::PodcastSubscribe(_) => render(),
+            Self::PodcastQueue(inner) => inner.on_mount(),
+            Self::PodcastDiscover(_) | Self::PodcastSubscribe(_) => render(),
         }
     }
 }
@@ -0,0 +1,61 @@
+use crux_http::command::Http;
+use entertainarr_adapter_http::entity::{
+    ApiResource,
+    podcast::{PodcastDirectoryDocument, PodcastSubscribeDocument},
+};
+
+use crate::application::session::retry_on_token_expired;
+use crate::{effect::http::Operation, entity::podcast::PodcastDirectoryEntry};
+
+#[derive(serde::Serialize)]
+struct SearchQuery<'a> {
+    q: &'a str,
+}
+
+pub fn search(base_url: &str, token: &str, query: &str) -> crate::ApplicationCommand {
+    let url = format!("{base_url}/api/podcast-directory");
+    Http::get(url)
+        .header("Authorization", format!("Bearer {token}"))
+        .query(&SearchQuery { q: query })
+        .expect("query params")
+        .expect_json::<ApiResource<Vec<PodcastDirectoryDocument>>>()
+        .build()
+        .then_send(retry_on_token_expired(
+            super::PodcastDiscoverEvent::Search(Operation::Request(query.to_string())),
+            |res| {
+                match res {
+                    Ok(mut res) => {
+                        let body: ApiResource<Vec<PodcastDirectoryDocument>> =
+                            res.take_body().unwrap();
+                        let body = PodcastDirectoryEntry::from_document_list(body);
+                        super::PodcastDiscoverEvent::Search(Operation::Success(body))
+                    }
+                    Err(err) => super::PodcastDiscoverEvent::Search(Operation::Error(err.into())),
+                }
+                .into()
+            },
+        ))
+}
+
+pub fn subscribe(base_url: &str, token: &str, feed_url: String) -> crate::ApplicationCommand {
+    let url = format!("{base_url}/api/users/me/podcasts");
+    Http::post(url)
+        .header("Authorization", format!("Bearer {token}"))
+        .body_json(&ApiResource::new(PodcastSubscribeDocument::new(
+            feed_url.as_str(),
+        )))
+        .expect("json body")
+        .build()
+        .then_send(retry_on_token_expired(
+            super::PodcastDiscoverEvent::Subscribe(Operation::Request(feed_url.clone())),
+            move |res| {
+                match res {
+                    Ok(_) => super::PodcastDiscoverEvent::Subscribe(Operation::Success(feed_url)),
+                    Err(err) => {
+                        super::PodcastDiscoverEvent::Subscribe(Operation::Error(err.into()))
+                    }
+                }
+                .into()
+            },
+        ))
+}
@@ -0,0 +1,26 @@
+use crate::{
+    effect::http::{HttpError, Operation},
+    entity::podcast::PodcastDirectoryEntry,
+};
+
+mod execute;
+mod update;
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct PodcastDiscoverModel {
+    pub query: String,
+    pub data: Vec<PodcastDirectoryEntry>,
+    pub error: Option<HttpError>,
+    pub loading: bool,
+    /// Feed urls subscribed from the results
+    pub subscribed: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, facet::Facet, serde::Serialize, serde::Deserialize)]
+#[repr(C)]
+pub enum PodcastDiscoverEvent {
+    /// Searches the directory with the given query
+    Search(Operation<String, Vec<PodcastDirectoryEntry>>),
+    /// Subscribes to the given feed url
+    Subscribe(Operation<String, String>),
+}
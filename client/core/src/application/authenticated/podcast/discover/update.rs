@@ -0,0 +1,141 @@
+use crux_core::render::render;
+
+use crate::{
+    application::{ApplicationState, authenticated::AuthenticatedModel},
+    effect::http::Operation,
+};
+
+impl crate::application::ApplicationModel {
+    pub fn handle_podcast_discover_event(
+        &mut self,
+        event: super::PodcastDiscoverEvent,
+    ) -> crate::ApplicationCommand {
+        let Some(token) = self.session.as_ref().map(|session| session.token.as_str()) else {
+            return render();
+        };
+        let Some(server_url) = self.server_url.as_deref() else {
+            return render();
+        };
+        let ApplicationState::Authenticated(AuthenticatedModel::PodcastDiscover(model)) =
+            &mut self.state
+        else {
+            return render();
+        };
+        match event {
+            super::PodcastDiscoverEvent::Search(Operation::Request(query)) => {
+                model.query = query;
+                model.error = None;
+                if model.query.trim().is_empty() {
+                    model.data.clear();
+                    return render();
+                }
+                model.loading = true;
+                crate::ApplicationCommand::all([
+                    super::execute::search(server_url, token, &model.query),
+                    render(),
+                ])
+            }
+            super::PodcastDiscoverEvent::Search(Operation::Success(data)) => {
+                model.data = data;
+                model.error = None;
+                model.loading = false;
+                render()
+            }
+            super::PodcastDiscoverEvent::Search(Operation::Error(err))
+            | super::PodcastDiscoverEvent::Subscribe(Operation::Error(err)) => {
+                model.error = Some(err);
+                model.loading = false;
+                render()
+            }
+            super::PodcastDiscoverEvent::Subscribe(Operation::Request(feed_url)) => {
+                model.error = None;
+                model.loading = true;
+                crate::ApplicationCommand::all([
+                    super::execute::subscribe(server_url, token, feed_url),
+                    render(),
+                ])
+            }
+            super::PodcastDiscoverEvent::Subscribe(Operation::Success(feed_url)) => {
+                model.loading = false;
+                if !model.subscribed.contains(&feed_url) {
+                    model.subscribed.push(feed_url);
+                }
+                render()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::{
+        ApplicationModel, InitializationEvent,
+        authenticated::podcast::discover::PodcastDiscoverEvent, router::Route,
+    };
+    use crate::effect::http::Operation;
+
+    fn model() -> ApplicationModel {
+        let mut model = ApplicationModel::default();
+        let _ = model.update(
+            InitializationEvent {
+                server_url: "http://localhost".into(),
+                authentication_token: Some("token".into()),
+                refresh_token: None,
+                route: Some(Route::PodcastDiscover),
+            }
+            .into(),
+        );
+        model
+    }
+
+    #[test]
+    fn should_search_directory() {
+        let mut model = model();
+        let mut cmd =
+            model.update(PodcastDiscoverEvent::Search(Operation::Request("rust".into())).into());
+        let effects: Vec<_> = cmd.effects().collect();
+        let request = effects
+            .into_iter()
+            .find_map(|effect| match effect {
+                crate::effect::Effect::Http(request) => Some(request),
+                _ => None,
+            })
+            .expect("expected http request");
+        assert_eq!(
+            request.operation.url,
+            "http://localhost/api/podcast-directory?q=rust"
+        );
+    }
+
+    #[test]
+    fn should_not_search_with_empty_query() {
+        let mut model = model();
+        let mut cmd =
+            model.update(PodcastDiscoverEvent::Search(Operation::Request("  ".into())).into());
+        let effects: Vec<_> = cmd.effects().collect();
+        assert_eq!(effects.len(), 1);
+        assert!(effects[0].is_render());
+    }
+
+    #[test]
+    fn should_mark_subscribed_feed() {
+        let mut model = model();
+        let url = String::from("https://example.com/feed.rss");
+        let mut cmd =
+            model.update(PodcastDiscoverEvent::Subscribe(Operation::Request(url.clone())).into());
+        let effects: Vec<_> = cmd.effects().collect();
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            crate::effect::Effect::Http(request) if request.operation.url == "http://localhost/api/users/me/podcasts"
+        )));
+        let _ =
+            model.update(PodcastDiscoverEvent::Subscribe(Operation::Success(url.clone())).into());
+        let crate::application::ApplicationState::Authenticated(
+            crate::application::authenticated::AuthenticatedModel::PodcastDiscover(inner),
+        ) = &model.state
+        else {
+            panic!("expected discover state");
+        };
+        assert_eq!(inner.subscribed, vec![url]);
+    }
+}
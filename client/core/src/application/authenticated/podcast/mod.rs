@@ -1,2 +1,4 @@
 pub mod dashboard;
+pub mod discover;
+pub mod queue;
 pub mod subscribe;
@@ -0,0 +1,97 @@
+use crux_http::command::{Http, RequestBuilder};
+use entertainarr_adapter_http::entity::{
+    ApiResource,
+    podcast_episode::{PodcastEpisodeDocument, PodcastEpisodeRelation, PodcastQueueMoveDocument},
+};
+
+use crate::application::session::retry_on_token_expired;
+use crate::effect::http::{HttpError, Operation};
+use crate::entity::podcast_episode::PodcastEpisode;
+
+/// Every queue endpoint answers with the whole queue, including the podcasts
+fn send<Req, F>(
+    builder: RequestBuilder<crate::effect::Effect, crate::application::ApplicationEvent>,
+    token: &str,
+    request: Req,
+    callback: F,
+) -> crate::ApplicationCommand
+where
+    F: Fn(Operation<Req, Vec<PodcastEpisode>>) -> super::PodcastQueueEvent + Send + 'static,
+{
+    let retry = callback(Operation::Request(request));
+    builder
+        .header("Authorization", format!("Bearer {token}"))
+        .expect_json::<ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>>()
+        .build()
+        .then_send(retry_on_token_expired(retry, move |res| {
+            let operation = match res {
+                Ok(mut res) => {
+                    let payload = res.take_body().unwrap();
+                    Operation::Success(PodcastEpisode::from_episode_document_list(payload))
+                }
+                Err(err) => Operation::Error(HttpError::from(err)),
+            };
+            callback(operation).into()
+        }))
+}
+
+pub fn list(base_url: &str, token: &str) -> crate::ApplicationCommand {
+    let url = format!("{base_url}/api/users/me/queue");
+    send(Http::get(url), token, (), super::PodcastQueueEvent::List)
+}
+
+pub fn enqueue(base_url: &str, token: &str, podcast_episode_id: u64) -> crate::ApplicationCommand {
+    let url = format!("{base_url}/api/users/me/queue/{podcast_episode_id}");
+    send(
+        Http::put(url),
+        token,
+        podcast_episode_id,
+        super::PodcastQueueEvent::Enqueue,
+    )
+}
+
+pub fn play_next(
+    base_url: &str,
+    token: &str,
+    podcast_episode_id: u64,
+) -> crate::ApplicationCommand {
+    let url = format!("{base_url}/api/users/me/queue/{podcast_episode_id}/play-next");
+    send(
+        Http::post(url),
+        token,
+        podcast_episode_id,
+        super::PodcastQueueEvent::PlayNext,
+    )
+}
+
+pub fn dequeue(base_url: &str, token: &str, podcast_episode_id: u64) -> crate::ApplicationCommand {
+    let url = format!("{base_url}/api/users/me/queue/{podcast_episode_id}");
+    send(
+        Http::delete(url),
+        token,
+        podcast_episode_id,
+        super::PodcastQueueEvent::Dequeue,
+    )
+}
+
+pub fn reorder(
+    base_url: &str,
+    token: &str,
+    request: &super::PodcastQueueMove,
+) -> crate::ApplicationCommand {
+    let url = format!(
+        "{base_url}/api/users/me/queue/{}",
+        request.podcast_episode_id
+    );
+    let builder = Http::patch(url)
+        .body_json(&ApiResource::new(PodcastQueueMoveDocument::new(
+            request.position,
+        )))
+        .expect("json body");
+    send(
+        builder,
+        token,
+        request.clone(),
+        super::PodcastQueueEvent::Move,
+    )
+}
@@ -0,0 +1,40 @@
+use crate::{
+    effect::http::{HttpError, Operation},
+    entity::podcast_episode::PodcastEpisode,
+};
+
+mod execute;
+mod update;
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct PodcastQueueModel {
+    /// Queued episodes, the next one to play first
+    pub data: Vec<PodcastEpisode>,
+    pub error: Option<HttpError>,
+    pub loading: bool,
+}
+
+impl PodcastQueueModel {
+    pub fn on_mount(&self) -> crate::ApplicationCommand {
+        crate::ApplicationCommand::event(PodcastQueueEvent::List(Operation::Request(())).into())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, facet::Facet, serde::Serialize, serde::Deserialize)]
+pub struct PodcastQueueMove {
+    pub podcast_episode_id: u64,
+    pub position: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, facet::Facet, serde::Serialize, serde::Deserialize)]
+#[repr(C)]
+pub enum PodcastQueueEvent {
+    List(Operation<(), Vec<PodcastEpisode>>),
+    /// Appends the given episode to the queue
+    Enqueue(Operation<u64, Vec<PodcastEpisode>>),
+    /// Moves the given episode to the head of the queue
+    PlayNext(Operation<u64, Vec<PodcastEpisode>>),
+    /// Removes the given episode from the queue
+    Dequeue(Operation<u64, Vec<PodcastEpisode>>),
+    Move(Operation<PodcastQueueMove, Vec<PodcastEpisode>>),
+}
@@ -0,0 +1,152 @@
+use crux_core::render::render;
+
+use crate::{
+    application::{ApplicationState, authenticated::AuthenticatedModel},
+    effect::http::Operation,
+};
+
+impl crate::application::ApplicationModel {
+    pub fn handle_podcast_queue_event(
+        &mut self,
+        event: super::PodcastQueueEvent,
+    ) -> crate::ApplicationCommand {
+        use super::PodcastQueueEvent;
+
+        let Some(token) = self.session.as_ref().map(|session| session.token.as_str()) else {
+            return render();
+        };
+        let Some(server_url) = self.server_url.as_deref() else {
+            return render();
+        };
+        let ApplicationState::Authenticated(AuthenticatedModel::PodcastQueue(model)) =
+            &mut self.state
+        else {
+            return render();
+        };
+        let command = match event {
+            PodcastQueueEvent::List(Operation::Request(_)) => {
+                super::execute::list(server_url, token)
+            }
+            PodcastQueueEvent::Enqueue(Operation::Request(podcast_episode_id)) => {
+                super::execute::enqueue(server_url, token, podcast_episode_id)
+            }
+            PodcastQueueEvent::PlayNext(Operation::Request(podcast_episode_id)) => {
+                super::execute::play_next(server_url, token, podcast_episode_id)
+            }
+            PodcastQueueEvent::Dequeue(Operation::Request(podcast_episode_id)) => {
+                super::execute::dequeue(server_url, token, podcast_episode_id)
+            }
+            PodcastQueueEvent::Move(Operation::Request(request)) => {
+                super::execute::reorder(server_url, token, &request)
+            }
+            PodcastQueueEvent::List(Operation::Success(data))
+            | PodcastQueueEvent::Enqueue(Operation::Success(data))
+            | PodcastQueueEvent::PlayNext(Operation::Success(data))
+            | PodcastQueueEvent::Dequeue(Operation::Success(data))
+            | PodcastQueueEvent::Move(Operation::Success(data)) => {
+                model.data = data;
+                model.error = None;
+                model.loading = false;
+                return render();
+            }
+            PodcastQueueEvent::List(Operation::Error(err))
+            | PodcastQueueEvent::Enqueue(Operation::Error(err))
+            | PodcastQueueEvent::PlayNext(Operation::Error(err))
+            | PodcastQueueEvent::Dequeue(Operation::Error(err))
+            | PodcastQueueEvent::Move(Operation::Error(err)) => {
+                model.error = Some(err);
+                model.loading = false;
+                return render();
+            }
+        };
+        model.error = None;
+        model.loading = true;
+        crate::ApplicationCommand::all([command, render()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::{
+        ApplicationModel, InitializationEvent,
+        authenticated::podcast::queue::{PodcastQueueEvent, PodcastQueueMove},
+        router::Route,
+    };
+    use crate::effect::http::Operation;
+
+    fn model() -> ApplicationModel {
+        let mut model = ApplicationModel::default();
+        let _ = model.update(
+            InitializationEvent {
+                server_url: "http://localhost".into(),
+                authentication_token: Some("token".into()),
+                refresh_token: None,
+                route: Some(Route::PodcastQueue),
+            }
+            .into(),
+        );
+        model
+    }
+
+    fn request(model: &mut ApplicationModel, event: PodcastQueueEvent) -> (String, String) {
+        let mut cmd = model.update(event.into());
+        let effects: Vec<_> = cmd.effects().collect();
+        effects
+            .into_iter()
+            .find_map(|effect| match effect {
+                crate::effect::Effect::Http(request) => Some((
+                    request.operation.method.clone(),
+                    request.operation.url.clone(),
+                )),
+                _ => None,
+            })
+            .expect("expected http request")
+    }
+
+    #[test]
+    fn should_list_queue() {
+        let mut model = model();
+        let (method, url) = request(&mut model, PodcastQueueEvent::List(Operation::Request(())));
+        assert_eq!(method, "GET");
+        assert_eq!(url, "http://localhost/api/users/me/queue");
+    }
+
+    #[test]
+    fn should_play_next() {
+        let mut model = model();
+        let (method, url) = request(
+            &mut model,
+            PodcastQueueEvent::PlayNext(Operation::Request(2)),
+        );
+        assert_eq!(method, "POST");
+        assert_eq!(url, "http://localhost/api/users/me/queue/2/play-next");
+    }
+
+    #[test]
+    fn should_move_episode() {
+        let mut model = model();
+        let (method, url) = request(
+            &mut model,
+            PodcastQueueEvent::Move(Operation::Request(PodcastQueueMove {
+                podcast_episode_id: 3,
+                position: 0,
+            })),
+        );
+        assert_eq!(method, "PATCH");
+        assert_eq!(url, "http://localhost/api/users/me/queue/3");
+    }
+
+    #[test]
+    fn should_replace_queue_on_success() {
+        let mut model = model();
+        let _ = model.update(PodcastQueueEvent::Dequeue(Operation::Success(Vec::new())).into());
+        let crate::application::ApplicationState::Authenticated(
+            crate::application::authenticated::AuthenticatedModel::PodcastQueue(inner),
+        ) = &model.state
+        else {
+            panic!("expected queue state");
+        };
+        assert!(inner.data.is_empty());
+        assert!(!inner.loading);
+    }
+}
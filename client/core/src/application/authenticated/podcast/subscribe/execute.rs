@@ -1,21 +1,28 @@
 use crux_http::command::Http;
 use entertainarr_adapter_http::entity::{ApiResource, podcast::PodcastSubscribeDocument};
 
+use crate::application::session::retry_on_token_expired;
+
 impl super::PodcastSubscribeRequest {
     pub fn execute(self, base_url: &str, token: &str) -> crate::ApplicationCommand {
         let url = format!("{base_url}/api/users/me/podcasts");
         Http::post(url)
             .header("Authorization", format!("Bearer {token}"))
-            .body_json(&ApiResource::new(PodcastSubscribeDocument::new(self.url)))
+            .body_json(&ApiResource::new(PodcastSubscribeDocument::new(
+                self.url.as_str(),
+            )))
             .expect("json body")
             .build()
-            .then_send(|res| {
-                match res {
-                    Ok(_) => super::PodcastSubscribeEvent::Success,
-                    Err(err) => super::PodcastSubscribeEvent::Error(err.into()),
-                }
-                .into()
-            })
+            .then_send(retry_on_token_expired(
+                super::PodcastSubscribeEvent::Submit(self),
+                |res| {
+                    match res {
+                        Ok(_) => super::PodcastSubscribeEvent::Success,
+                        Err(err) => super::PodcastSubscribeEvent::Error(err.into()),
+                    }
+                    .into()
+                },
+            ))
     }
 }
 
@@ -26,6 +26,7 @@ impl super::AuthenticationRequest {
                 attributes: AuthenticationRequestAttributes {
                     email: self.email.into(),
                     password: self.password.into(),
+                    device_name: None,
                 },
             }))
             .expect("json body")
@@ -37,6 +38,7 @@ impl super::AuthenticationRequest {
                         let payload = res.take_body().unwrap();
                         super::AuthenticationEvent::Success(super::AuthenticationSuccess {
                             token: payload.data.id,
+                            refresh_token: payload.data.attributes.refresh_token,
                         })
                     }
                     Err(err) => {
@@ -48,6 +50,20 @@ impl super::AuthenticationRequest {
     }
 }
 
+/// Revokes the session on the server, failures are ignored as the client forgets it anyway
+pub fn logout(base_url: &str, token: &str) -> crate::ApplicationCommand {
+    let url = format!("{base_url}/api/auth/logout");
+    Http::post(url)
+        .header("Authorization", format!("Bearer {token}"))
+        .build()
+        .then_send(|res| {
+            if let Err(err) = res {
+                tracing::warn!(error = ?err, "unable to revoke session");
+            }
+            crate::application::ApplicationEvent::Noop
+        })
+}
+
 impl From<crux_http::HttpError> for super::AuthenticationError {
     fn from(err: crux_http::HttpError) -> Self {
         match err {
@@ -52,6 +52,7 @@ pub struct AuthenticationRequest {
 #[repr(C)]
 pub struct AuthenticationSuccess {
     pub token: String,
+    pub refresh_token: String,
 }
 
 impl crate::application::ApplicationModel {
@@ -63,9 +64,15 @@ impl crate::application::ApplicationModel {
             return render();
         };
         if let AuthenticationEvent::Logout = event {
+            let revoke = match self.session.take() {
+                Some(session) => execute::logout(server_url, &session.token),
+                None => ApplicationCommand::done(),
+            };
             return ApplicationCommand::all([
+                revoke,
                 Route::Authentication.into(),
                 Persistence::clear("authentication-token"),
+                Persistence::clear("refresh-token"),
                 render(),
             ]);
         };
@@ -77,12 +84,14 @@ impl crate::application::ApplicationModel {
                 }
                 AuthenticationEvent::Success(res) => {
                     model.loading = false;
-                    self.session = Some(super::session::Session {
-                        token: res.token.clone(),
-                    });
+                    self.session = Some(super::session::Session::new(
+                        res.token.clone(),
+                        Some(res.refresh_token.clone()),
+                    ));
                     ApplicationCommand::all([
                         Route::Home.into(),
                         Persistence::store("authentication-token", res.token),
+                        Persistence::store("refresh-token", res.refresh_token),
                         render(),
                     ])
                 }
@@ -51,6 +51,12 @@ impl ApplicationState {
             Self::Authenticated(authenticated::AuthenticatedModel::PodcastDashboard(inner)) => {
                 ApplicationView::PodcastDashboard(inner.clone())
             }
+            Self::Authenticated(authenticated::AuthenticatedModel::PodcastDiscover(inner)) => {
+                ApplicationView::PodcastDiscover(inner.clone())
+            }
+            Self::Authenticated(authenticated::AuthenticatedModel::PodcastQueue(inner)) => {
+                ApplicationView::PodcastQueue(inner.clone())
+            }
             Self::Authenticated(authenticated::AuthenticatedModel::PodcastSubscribe(inner)) => {
                 ApplicationView::PodcastSubscribe(inner.clone())
             }
@@ -76,14 +82,19 @@ pub enum ApplicationEvent {
     Initialization(InitializationEvent),
     Noop, // does nothing
     PodcastDashboard(authenticated::podcast::dashboard::PodcastDashboardEvent),
+    PodcastDiscover(authenticated::podcast::discover::PodcastDiscoverEvent),
+    PodcastQueue(authenticated::podcast::queue::PodcastQueueEvent),
     PodcastSubscribe(authenticated::podcast::subscribe::PodcastSubscribeEvent),
     RouteChange(router::Route),
+    Session(session::SessionEvent),
 }
 
 impl ApplicationEvent {
     pub fn name(&self) -> &'static str {
         use crate::application::authenticated::home::HomeEvent;
         use crate::application::authenticated::podcast::dashboard::PodcastDashboardEvent;
+        use crate::application::authenticated::podcast::discover::PodcastDiscoverEvent;
+        use crate::application::authenticated::podcast::queue::PodcastQueueEvent;
         use crate::application::authenticated::podcast::subscribe::PodcastSubscribeEvent;
         use crate::application::authentication::AuthenticationEvent;
 
@@ -113,6 +124,69 @@ impl ApplicationEvent {
             Self::PodcastDashboard(PodcastDashboardEvent::ListPodcastSubscription(
                 Operation::Error(_),
             )) => "authenticated.podcast-dashboard.list-podcast-subscription.error",
+            Self::PodcastDiscover(PodcastDiscoverEvent::Search(Operation::Request(_))) => {
+                "authenticated.podcast-discover.search.request"
+            }
+            Self::PodcastDiscover(PodcastDiscoverEvent::Search(Operation::Success(_))) => {
+                "authenticated.podcast-discover.search.success"
+            }
+            Self::PodcastDiscover(PodcastDiscoverEvent::Search(Operation::Error(_))) => {
+                "authenticated.podcast-discover.search.error"
+            }
+            Self::PodcastDiscover(PodcastDiscoverEvent::Subscribe(Operation::Request(_))) => {
+                "authenticated.podcast-discover.subscribe.request"
+            }
+            Self::PodcastDiscover(PodcastDiscoverEvent::Subscribe(Operation::Success(_))) => {
+                "authenticated.podcast-discover.subscribe.success"
+            }
+            Self::PodcastDiscover(PodcastDiscoverEvent::Subscribe(Operation::Error(_))) => {
+                "authenticated.podcast-discover.subscribe.error"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::List(Operation::Request(_))) => {
+                "authenticated.podcast-queue.list.request"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::List(Operation::Success(_))) => {
+                "authenticated.podcast-queue.list.success"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::List(Operation::Error(_))) => {
+                "authenticated.podcast-queue.list.error"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Enqueue(Operation::Request(_))) => {
+                "authenticated.podcast-queue.enqueue.request"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Enqueue(Operation::Success(_))) => {
+                "authenticated.podcast-queue.enqueue.success"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Enqueue(Operation::Error(_))) => {
+                "authenticated.podcast-queue.enqueue.error"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::PlayNext(Operation::Request(_))) => {
+                "authenticated.podcast-queue.play-next.request"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::PlayNext(Operation::Success(_))) => {
+                "authenticated.podcast-queue.play-next.success"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::PlayNext(Operation::Error(_))) => {
+                "authenticated.podcast-queue.play-next.error"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Dequeue(Operation::Request(_))) => {
+                "authenticated.podcast-queue.dequeue.request"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Dequeue(Operation::Success(_))) => {
+                "authenticated.podcast-queue.dequeue.success"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Dequeue(Operation::Error(_))) => {
+                "authenticated.podcast-queue.dequeue.error"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Move(Operation::Request(_))) => {
+                "authenticated.podcast-queue.move.request"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Move(Operation::Success(_))) => {
+                "authenticated.podcast-queue.move.success"
+            }
+            Self::PodcastQueue(PodcastQueueEvent::Move(Operation::Error(_))) => {
+                "authenticated.podcast-queue.move.error"
+            }
             Self::PodcastSubscribe(PodcastSubscribeEvent::Submit(_)) => {
                 "authenticated.podcast-subscribe.submit"
             }
@@ -125,21 +199,12 @@ impl ApplicationEvent {
             Self::RouteChange(router::Route::Authentication) => "route.change.authentication",
             Self::RouteChange(router::Route::Home) => "route.change.home",
             Self::RouteChange(router::Route::PodcastDashboard) => "route.change.podcast-dashboard",
+            Self::RouteChange(router::Route::PodcastDiscover) => "route.change.podcast-discover",
+            Self::RouteChange(router::Route::PodcastQueue) => "route.change.podcast-queue",
             Self::RouteChange(router::Route::PodcastSubscribe) => "route.change.podcast-subscribe",
-        }
-    }
-
-    fn is_token_expired(&self) -> bool {
-        match self {
-            ApplicationEvent::PodcastDashboard(
-                authenticated::podcast::dashboard::PodcastDashboardEvent::ListPodcastSubscription(
-                    Operation::Error(err),
-                ),
-            )
-            | ApplicationEvent::Home(authenticated::home::HomeEvent::ListPodcastEpisodesError(
-                err,
-            )) => err.is_token_expired(),
-            _ => false,
+            Self::Session(session::SessionEvent::Expired(_)) => "session.expired",
+            Self::Session(session::SessionEvent::RefreshSuccess(_)) => "session.refresh.success",
+            Self::Session(session::SessionEvent::RefreshError(_)) => "session.refresh.error",
         }
     }
 }
@@ -158,6 +223,7 @@ impl ApplicationEvent {
 pub struct InitializationEvent {
     pub server_url: String,
     pub authentication_token: Option<String>,
+    pub refresh_token: Option<String>,
     pub route: Option<self::router::Route>,
 }
 
@@ -168,7 +234,7 @@ impl ApplicationModel {
     ) -> crate::ApplicationCommand {
         self.server_url = Some(event.server_url);
         if let Some(token) = event.authentication_token {
-            self.session = Some(self::session::Session { token });
+            self.session = Some(self::session::Session::new(token, event.refresh_token));
         }
         self.handle_router_event(event.route.unwrap_or(router::Route::Home))
     }
@@ -182,20 +248,17 @@ impl ApplicationModel {
             };
         }
 
-        if event.is_token_expired() {
-            return crate::ApplicationCommand::event(
-                authentication::AuthenticationEvent::Logout.into(),
-            );
-        }
-
         match event {
             ApplicationEvent::Authentication(event) => self.handle_authentication_event(event),
             ApplicationEvent::Home(event) => self.handle_home_event(event),
             ApplicationEvent::Initialization(_) => render(),
             ApplicationEvent::Authenticated => render(),
             ApplicationEvent::PodcastDashboard(event) => self.handle_podcast_dashboard_event(event),
+            ApplicationEvent::PodcastDiscover(event) => self.handle_podcast_discover_event(event),
+            ApplicationEvent::PodcastQueue(event) => self.handle_podcast_queue_event(event),
             ApplicationEvent::PodcastSubscribe(event) => self.handle_podcast_subscribe_event(event),
             ApplicationEvent::RouteChange(route) => self.handle_router_event(route),
+            ApplicationEvent::Session(event) => self.handle_session_event(event),
             ApplicationEvent::Noop => render(),
         }
     }
@@ -222,6 +285,8 @@ pub enum ApplicationView {
     Authentication(self::authentication::AuthenticationModel),
     Home(self::authenticated::home::HomeModel),
     PodcastDashboard(self::authenticated::podcast::dashboard::PodcastDashboardModel),
+    PodcastDiscover(self::authenticated::podcast::discover::PodcastDiscoverModel),
+    PodcastQueue(self::authenticated::podcast::queue::PodcastQueueModel),
     PodcastSubscribe(self::authenticated::podcast::subscribe::PodcastSubscribeModel),
 }
 
@@ -238,6 +303,7 @@ mod tests {
             InitializationEvent {
                 server_url: "http://localhost".into(),
                 authentication_token: None,
+                refresh_token: None,
                 route: None,
             },
         ));
@@ -258,6 +324,7 @@ mod tests {
             InitializationEvent {
                 server_url: "http://localhost".into(),
                 authentication_token: Some("token".into()),
+                refresh_token: Some("refresh-token".into()),
                 route: Some(Route::Home),
             },
         ));
@@ -271,4 +338,61 @@ mod tests {
         let effects: Vec<_> = cmd.effects().collect();
         assert!(effects.is_empty());
     }
+
+    #[test]
+    fn should_refresh_token_and_retry() {
+        use crate::application::authenticated::home::HomeEvent;
+        use crate::application::authentication::AuthenticationSuccess;
+        use crate::application::session::{SessionEvent, retry_on_token_expired};
+
+        let mut model = crate::application::ApplicationModel::default();
+        let _ = model.update(crate::application::ApplicationEvent::Initialization(
+            InitializationEvent {
+                server_url: "http://localhost".into(),
+                authentication_token: Some("token".into()),
+                refresh_token: Some("refresh-token".into()),
+                route: Some(Route::Home),
+            },
+        ));
+
+        let callback =
+            retry_on_token_expired::<(), _>(HomeEvent::ListPodcastEpisodesRequest, |_| {
+                unreachable!("the expired token should be handled")
+            });
+        let expired = callback(Err(crux_http::HttpError::Http {
+            code: crux_http::http::StatusCode::Unauthorized,
+            message: "authorization token expired".into(),
+            body: None,
+        }));
+        assert_eq!(
+            expired,
+            SessionEvent::Expired(Box::new(HomeEvent::ListPodcastEpisodesRequest.into())).into()
+        );
+        let mut cmd = model.update(expired);
+        let mut effects: Vec<_> = cmd.effects().collect();
+        assert_eq!(effects.len(), 1);
+        let crate::effect::Effect::Http(request) = effects.pop().unwrap() else {
+            panic!("expected http request");
+        };
+        assert_eq!(request.operation.url, "http://localhost/api/auth/refresh");
+        assert!(model.session.as_ref().unwrap().refreshing);
+
+        let mut cmd = model.update(
+            SessionEvent::RefreshSuccess(AuthenticationSuccess {
+                token: "new-token".into(),
+                refresh_token: "new-refresh-token".into(),
+            })
+            .into(),
+        );
+        let session = model.session.as_ref().unwrap();
+        assert_eq!(session.token, "new-token");
+        assert!(!session.refreshing);
+        let events: Vec<_> = cmd.events().collect();
+        assert_eq!(
+            events,
+            vec![crate::application::ApplicationEvent::Home(
+                HomeEvent::ListPodcastEpisodesRequest
+            )]
+        );
+    }
 }
@@ -9,6 +9,8 @@ pub enum Route {
     Home,
     PodcastSubscribe,
     PodcastDashboard,
+    PodcastDiscover,
+    PodcastQueue,
 }
 
 impl Route {
@@ -40,6 +42,16 @@ impl super::ApplicationModel {
                     super::authenticated::AuthenticatedModel::PodcastDashboard(Default::default()),
                 );
             }
+            Route::PodcastDiscover => {
+                self.state = ApplicationState::Authenticated(
+                    super::authenticated::AuthenticatedModel::PodcastDiscover(Default::default()),
+                );
+            }
+            Route::PodcastQueue => {
+                self.state = ApplicationState::Authenticated(
+                    super::authenticated::AuthenticatedModel::PodcastQueue(Default::default()),
+                );
+            }
             Route::PodcastSubscribe => {
                 self.state = ApplicationState::Authenticated(
                     super::authenticated::AuthenticatedModel::PodcastSubscribe(Default::default()),
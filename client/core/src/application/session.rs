@@ -1,4 +0,0 @@
-#[derive(Debug)]
-pub struct Session {
-    pub token: String,
-}
@@ -0,0 +1,32 @@
+use crux_http::command::Http;
+use entertainarr_adapter_http::entity::{
+    ApiResource,
+    auth::{AuthenticationRefreshDocument, AuthenticationTokenDocument},
+};
+
+use crate::application::authentication::AuthenticationSuccess;
+use crate::effect::http::HttpError;
+
+pub fn refresh(base_url: &str, refresh_token: &str) -> crate::ApplicationCommand {
+    let url = format!("{base_url}/api/auth/refresh");
+    Http::post(url)
+        .body_json(&ApiResource::new(AuthenticationRefreshDocument::new(
+            refresh_token,
+        )))
+        .expect("json body")
+        .expect_json::<ApiResource<AuthenticationTokenDocument>>()
+        .build()
+        .then_send(|res| {
+            match res {
+                Ok(mut res) => {
+                    let payload = res.take_body().unwrap();
+                    super::SessionEvent::RefreshSuccess(AuthenticationSuccess {
+                        token: payload.data.id,
+                        refresh_token: payload.data.attributes.refresh_token,
+                    })
+                }
+                Err(err) => super::SessionEvent::RefreshError(HttpError::from(err)),
+            }
+            .into()
+        })
+}
@@ -0,0 +1,111 @@
+use crux_core::render::render;
+
+use crate::application::ApplicationEvent;
+use crate::application::authentication::{AuthenticationEvent, AuthenticationSuccess};
+use crate::effect::http::HttpError;
+use crate::effect::persistence::Persistence;
+
+mod execute;
+
+#[derive(Debug)]
+pub struct Session {
+    pub token: String,
+    pub refresh_token: Option<String>,
+    /// Whether a refresh request is in flight
+    pub refreshing: bool,
+    /// Events to replay once the access token is refreshed
+    pub pending: Vec<ApplicationEvent>,
+}
+
+impl Session {
+    pub fn new(token: String, refresh_token: Option<String>) -> Self {
+        Self {
+            token,
+            refresh_token,
+            refreshing: false,
+            pending: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, facet::Facet, serde::Serialize, serde::Deserialize)]
+#[repr(C)]
+pub enum SessionEvent {
+    /// An authenticated request was rejected because the access token expired
+    Expired(Box<ApplicationEvent>),
+    RefreshSuccess(AuthenticationSuccess),
+    RefreshError(HttpError),
+}
+
+/// Wraps the callback of an authenticated request, so that when the access token expired,
+/// the request is replayed once the token is refreshed instead of failing
+pub(crate) fn retry_on_token_expired<B, F>(
+    retry: impl Into<ApplicationEvent>,
+    callback: F,
+) -> impl FnOnce(crux_http::Result<crux_http::Response<B>>) -> ApplicationEvent + Send + 'static
+where
+    B: Send + 'static,
+    F: FnOnce(crux_http::Result<crux_http::Response<B>>) -> ApplicationEvent + Send + 'static,
+{
+    let retry = retry.into();
+    move |res| match res {
+        Err(crux_http::HttpError::Http {
+            code: crux_http::http::StatusCode::Unauthorized,
+            ..
+        }) => SessionEvent::Expired(Box::new(retry)).into(),
+        other => callback(other),
+    }
+}
+
+impl crate::application::ApplicationModel {
+    /// Refreshes the access token, then replays the event
+    pub(crate) fn refresh_session(&mut self, retry: ApplicationEvent) -> crate::ApplicationCommand {
+        let Some(server_url) = self.server_url.as_deref() else {
+            return render();
+        };
+        let Some(session) = self.session.as_mut() else {
+            return crate::ApplicationCommand::event(AuthenticationEvent::Logout.into());
+        };
+        let Some(refresh_token) = session.refresh_token.as_deref() else {
+            return crate::ApplicationCommand::event(AuthenticationEvent::Logout.into());
+        };
+        session.pending.push(retry);
+        if session.refreshing {
+            return render();
+        }
+        session.refreshing = true;
+        execute::refresh(server_url, refresh_token)
+    }
+
+    pub(crate) fn handle_session_event(
+        &mut self,
+        event: SessionEvent,
+    ) -> crate::ApplicationCommand {
+        match event {
+            SessionEvent::Expired(retry) => self.refresh_session(*retry),
+            SessionEvent::RefreshSuccess(res) => {
+                let Some(session) = self.session.as_mut() else {
+                    return render();
+                };
+                session.token = res.token.clone();
+                session.refresh_token = Some(res.refresh_token.clone());
+                session.refreshing = false;
+                let replay = std::mem::take(&mut session.pending)
+                    .into_iter()
+                    .map(crate::ApplicationCommand::event);
+                crate::ApplicationCommand::all(
+                    [
+                        Persistence::store("authentication-token", res.token),
+                        Persistence::store("refresh-token", res.refresh_token),
+                    ]
+                    .into_iter()
+                    .chain(replay),
+                )
+            }
+            SessionEvent::RefreshError(err) => {
+                tracing::warn!(error = ?err, "unable to refresh session");
+                crate::ApplicationCommand::event(AuthenticationEvent::Logout.into())
+            }
+        }
+    }
+}
@@ -43,15 +43,6 @@ impl From<crux_http::HttpError> for HttpError {
     }
 }
 
-impl HttpError {
-    pub const fn is_token_expired(&self) -> bool {
-        matches!(
-            self,
-            HttpError::Api(crux_http::http::StatusCode::Unauthorized, _)
-        )
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq, facet::Facet, serde::Serialize, serde::Deserialize)]
 #[repr(C)]
 pub struct ApiError {
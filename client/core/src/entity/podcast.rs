@@ -1,4 +1,7 @@
-use entertainarr_adapter_http::entity::{ApiResource, podcast::PodcastDocument};
+use entertainarr_adapter_http::entity::{
+    ApiResource,
+    podcast::{PodcastDirectoryDocument, PodcastDocument},
+};
 
 #[derive(Clone, Debug, Eq, PartialEq, facet::Facet, serde::Serialize, serde::Deserialize)]
 pub struct Podcast {
@@ -32,3 +35,34 @@ impl Podcast {
         res.data.into_iter().map(Self::from_document).collect()
     }
 }
+
+#[derive(Clone, Debug, Eq, PartialEq, facet::Facet, serde::Serialize, serde::Deserialize)]
+pub struct PodcastDirectoryEntry {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub feed_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+}
+
+impl PodcastDirectoryEntry {
+    pub fn from_document(item: PodcastDirectoryDocument) -> Self {
+        Self {
+            title: item.attributes.title,
+            author: item.attributes.author,
+            description: item.attributes.description,
+            feed_url: item.attributes.feed_url,
+            image_url: item.attributes.image_url,
+            website: item.attributes.website,
+        }
+    }
+
+    pub fn from_document_list(res: ApiResource<Vec<PodcastDirectoryDocument>>) -> Vec<Self> {
+        res.data.into_iter().map(Self::from_document).collect()
+    }
+}
@@ -38,7 +38,7 @@ impl PodcastEpisode {
     pub fn from_episode_document_list(
         res: ApiResource<Vec<PodcastEpisodeDocument>, PodcastEpisodeRelation>,
     ) -> Vec<Self> {
-        let ApiResource { data, includes } = res;
+        let ApiResource { data, includes, .. } = res;
         let podcasts: HashMap<u64, entertainarr_adapter_http::entity::podcast::PodcastDocument> =
             HashMap::from_iter(
                 includes
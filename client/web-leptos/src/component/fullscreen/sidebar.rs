@@ -17,7 +17,9 @@ pub fn Sidebar(visible: ReadSignal<bool>, on_close: impl Fn() + 'static) -> impl
                 </header>
                 <section>
                     <a href="#/">{"Home"}</a>
+                    <a href="#/queue">{"Up Next"}</a>
                     <a href="#/podcasts">{"Podcasts"}</a>
+                    <a href="#/podcasts/discover">{"Discover"}</a>
                 </section>
             </nav>
         </>
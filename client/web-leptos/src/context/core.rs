@@ -22,6 +22,7 @@ pub(crate) fn CoreContext(children: Children) -> impl IntoView {
             authentication_token: crate::service::storage::get_local_storage(
                 "authentication-token",
             ),
+            refresh_token: crate::service::storage::get_local_storage("refresh-token"),
             route: Some(route),
         }
         .into(),
@@ -14,7 +14,9 @@ fn path(route: Route) -> Cow<'static, str> {
         Route::Home => Cow::Borrowed("#/"),
         Route::Authentication => Cow::Borrowed("#/authentication"),
         Route::PodcastDashboard => Cow::Borrowed("#/podcasts"),
+        Route::PodcastDiscover => Cow::Borrowed("#/podcasts/discover"),
         Route::PodcastSubscribe => Cow::Borrowed("#/podcasts/subscribe"),
+        Route::PodcastQueue => Cow::Borrowed("#/queue"),
     }
 }
 
@@ -23,7 +25,9 @@ pub(crate) fn parse_route(path: &str) -> Route {
         "" | "#/" => Route::Home,
         "#/authentication" => Route::Authentication,
         "#/podcasts" => Route::PodcastDashboard,
+        "#/podcasts/discover" => Route::PodcastDiscover,
         "#/podcasts/subscribe" => Route::PodcastSubscribe,
+        "#/queue" => Route::PodcastQueue,
         _ => Route::Home,
     }
 }
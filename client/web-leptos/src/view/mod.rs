@@ -7,6 +7,8 @@ use crate::context::core::use_view_model;
 pub mod authentication;
 pub mod home;
 pub mod podcast_dashboard;
+pub mod podcast_discover;
+pub mod podcast_queue;
 pub mod podcast_subscribe;
 
 #[component]
@@ -26,6 +28,12 @@ pub fn RouterView() -> impl IntoView {
             ApplicationView::PodcastDashboard(view) => {
                 view! {<podcast_dashboard::View model={view} />}.into_any()
             }
+            ApplicationView::PodcastDiscover(view) => {
+                view! { <podcast_discover::View model=view /> }.into_any()
+            }
+            ApplicationView::PodcastQueue(view) => {
+                view! { <podcast_queue::View model=view /> }.into_any()
+            }
             ApplicationView::PodcastSubscribe(view) => {
                 view! { <podcast_subscribe::View model=view /> }.into_any()
             }
@@ -0,0 +1,73 @@
+use entertainarr_client_core::application::authenticated::podcast::discover::{
+    PodcastDiscoverEvent, PodcastDiscoverModel,
+};
+use entertainarr_client_core::effect::http::Operation;
+use js_sys::wasm_bindgen::JsCast;
+use leptos::prelude::*;
+use web_sys::SubmitEvent;
+
+use crate::component::form::error_message::ErrorMessage;
+use crate::context::core::use_events;
+
+stylance::import_style!(style, "podcast_discover.module.scss");
+
+#[component]
+pub fn View(model: PodcastDiscoverModel) -> impl IntoView {
+    let (_, on_change) = use_events();
+
+    let handle_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let form = event_target::<web_sys::HtmlFormElement>(&ev);
+
+        let query_input = form
+            .get_with_name("query")
+            .expect("query input")
+            .dyn_into::<web_sys::HtmlInputElement>()
+            .expect("input element");
+
+        let req = PodcastDiscoverEvent::Search(Operation::Request(query_input.value()));
+        on_change.set(req.into());
+    };
+
+    let loading = model.loading;
+    let subscribed = model.subscribed;
+
+    view! {
+        <crate::component::fullscreen::layout::FullscreenLayout>
+            <h1>{"Discover Podcasts"}</h1>
+            <form class={style::search_form} novalidate on:submit=handle_submit>
+                <input type="search" name="query" placeholder="Show name" value={model.query} />
+                <button type="submit" disabled={loading}>{"Search"}</button>
+            </form>
+            {model.error.map(|_| view! {
+                <ErrorMessage>{"Unable to reach the podcast directory"}</ErrorMessage>
+            })}
+            <div class={style::result_list}>
+                {model.data.into_iter().map(|entry| {
+                    let is_subscribed = subscribed.contains(&entry.feed_url);
+                    let feed_url = entry.feed_url.clone();
+                    let on_subscribe = move |_| {
+                        on_change.set(
+                            PodcastDiscoverEvent::Subscribe(Operation::Request(feed_url.clone())).into(),
+                        );
+                    };
+                    view! {
+                        <div class={style::result}>
+                            <img src={entry.image_url.clone()} alt={entry.title.clone()} />
+                            <div class={style::result_content}>
+                                <h3>{entry.title}</h3>
+                                {entry.author.map(|value| view! { <small>{value}</small> })}
+                            </div>
+                            <button
+                                disabled={loading || is_subscribed}
+                                on:click=on_subscribe
+                            >
+                                {if is_subscribed { "Subscribed" } else { "Subscribe" }}
+                            </button>
+                        </div>
+                    }
+                }).collect_view()}
+            </div>
+        </crate::component::fullscreen::layout::FullscreenLayout>
+    }
+}
@@ -0,0 +1,66 @@
+use entertainarr_client_core::application::authenticated::podcast::queue::{
+    PodcastQueueEvent, PodcastQueueModel, PodcastQueueMove,
+};
+use entertainarr_client_core::effect::http::Operation;
+use leptos::prelude::*;
+
+use crate::component::form::error_message::ErrorMessage;
+use crate::context::core::use_events;
+
+stylance::import_style!(style, "podcast_queue.module.scss");
+
+#[component]
+pub fn View(model: PodcastQueueModel) -> impl IntoView {
+    let (_, on_change) = use_events();
+
+    let loading = model.loading;
+    let last = model.data.len().saturating_sub(1);
+
+    view! {
+        <crate::component::fullscreen::layout::FullscreenLayout>
+            <h1>{"Up Next"}</h1>
+            {model.error.map(|_| view! {
+                <ErrorMessage>{"Unable to update the queue"}</ErrorMessage>
+            })}
+            <div class={style::entry_list}>
+                {model.data.into_iter().enumerate().map(|(index, episode)| {
+                    let id = episode.id;
+                    let position = index as u32;
+                    let on_play_next = move |_| {
+                        on_change.set(PodcastQueueEvent::PlayNext(Operation::Request(id)).into());
+                    };
+                    let on_move_down = move |_| {
+                        on_change.set(
+                            PodcastQueueEvent::Move(Operation::Request(PodcastQueueMove {
+                                podcast_episode_id: id,
+                                position: position + 1,
+                            }))
+                            .into(),
+                        );
+                    };
+                    let on_remove = move |_| {
+                        on_change.set(PodcastQueueEvent::Dequeue(Operation::Request(id)).into());
+                    };
+                    view! {
+                        <div class={style::entry}>
+                            <img src={episode.podcast_image_url.clone()} alt={episode.title.clone()} />
+                            <div class={style::entry_content}>
+                                <h3>{episode.title}</h3>
+                                {episode.podcast_title.map(|value| view! { <small>{value}</small> })}
+                            </div>
+                            <div class={style::entry_actions}>
+                                <button disabled={loading || index == 0} on:click=on_play_next>
+                                    {"Play next"}
+                                </button>
+                                <button disabled={loading || index == last} on:click=on_move_down>
+                                    {"Move down"}
+                                </button>
+                                <button disabled={loading} on:click=on_remove>{"Remove"}</button>
+                            </div>
+                        </div>
+                    }
+                }).collect_view()}
+            </div>
+        </crate::component::fullscreen::layout::FullscreenLayout>
+    }
+}
@@ -16,3 +16,48 @@ pub struct Password(String);
 pub struct Profile {
     pub id: u64,
 }
+
+/// Password hash stored for a user, verified when logging in
+#[derive(Clone, Debug)]
+pub struct Credentials {
+    pub profile: Profile,
+    pub password_hash: String,
+}
+
+/// Identity carried by an access token
+#[derive(Clone, Debug, PartialEq)]
+pub struct Principal {
+    pub user_id: u64,
+    pub session_id: u64,
+}
+
+/// Access to the file of an episode, carried in its url for the players that can't send headers
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediaGrant {
+    pub user_id: u64,
+    /// Session the token has been issued for, the token is refused once it's revoked
+    pub session_id: u64,
+    pub podcast_episode_id: u64,
+}
+
+/// Session opened when logging in, kept alive by rotating its refresh token
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub id: u64,
+    pub user_id: u64,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub device: DeviceInfo,
+    /// Last time an access token of the session was used
+    pub last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Device a session was opened from
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeviceInfo {
+    pub name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
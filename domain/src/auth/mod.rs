@@ -1,16 +1,67 @@
+use std::time::Duration;
+
 pub mod entity;
+mod password;
 pub mod prelude;
+mod refresh_token;
 
 #[derive(Clone, Debug, bon::Builder)]
-pub struct AuthenticationService<AR, TR> {
+pub struct AuthenticationService<AR, SR, TR, FTR> {
     authentication_repository: AR,
+    feed_token_repository: FTR,
+    session_repository: SR,
     token_repository: TR,
+    /// Lifetime of a refresh token, extended every time it's rotated
+    refresh_token_duration: Duration,
+    /// Minimum delay between two records of the activity of a session, so every request
+    /// doesn't write to the database
+    #[builder(default = Duration::from_secs(60))]
+    session_activity_interval: Duration,
+}
+
+impl<AR, SR, TR, FTR> AuthenticationService<AR, SR, TR, FTR>
+where
+    AR: prelude::AuthenticationRepository,
+    SR: prelude::SessionRepository,
+    TR: prelude::TokenRepository,
+    FTR: prelude::FeedTokenRepository,
+{
+    async fn open_session(
+        &self,
+        profile: &entity::Profile,
+        device: &entity::DeviceInfo,
+    ) -> anyhow::Result<prelude::LoginSuccess> {
+        let refresh_token = refresh_token::generate();
+        let expires_at = chrono::Utc::now() + self.refresh_token_duration;
+        let session = self
+            .session_repository
+            .create(
+                profile.id,
+                &refresh_token::hash(&refresh_token),
+                expires_at,
+                device,
+            )
+            .await?;
+        let token = self
+            .token_repository
+            .create_token(&entity::Principal {
+                user_id: session.user_id,
+                session_id: session.id,
+            })
+            .await?;
+        Ok(prelude::LoginSuccess {
+            token,
+            refresh_token,
+        })
+    }
 }
 
-impl<AR, TR> prelude::AuthenticationService for AuthenticationService<AR, TR>
+impl<AR, SR, TR, FTR> prelude::AuthenticationService for AuthenticationService<AR, SR, TR, FTR>
 where
     AR: prelude::AuthenticationRepository,
+    SR: prelude::SessionRepository,
     TR: prelude::TokenRepository,
+    FTR: prelude::FeedTokenRepository,
 {
     async fn login(
         &self,
@@ -18,15 +69,36 @@ where
     ) -> Result<prelude::LoginSuccess, prelude::LoginError> {
         let email = req.email.into_inner();
         let password = req.password.into_inner();
-        let password_hash = hash_password(&email, &password);
-        let profile = self
+        let Some(credentials) = self
             .authentication_repository
-            .find_by_credentials(email.as_str(), password_hash.as_str())
+            .find_by_email(email.as_str())
             .await?
-            .ok_or(prelude::LoginError::InvalidCredentials)?;
+        else {
+            // not to tell which emails are registered from the time it takes
+            password::verify(email, password, password::DUMMY_HASH.to_string()).await?;
+            return Err(prelude::LoginError::InvalidCredentials);
+        };
+        let verification =
+            password::verify(email, password.clone(), credentials.password_hash).await?;
+        let profile = match verification {
+            password::Verification::Invalid => {
+                return Err(prelude::LoginError::InvalidCredentials);
+            }
+            password::Verification::Valid => credentials.profile,
+            password::Verification::Outdated => {
+                let password_hash = password::hash(password).await?;
+                if let Err(err) = self
+                    .authentication_repository
+                    .update_password(credentials.profile.id, &password_hash)
+                    .await
+                {
+                    tracing::warn!(user.id = credentials.profile.id, error = ?err, "unable to rehash password");
+                }
+                credentials.profile
+            }
+        };
 
-        let token = self.token_repository.create_token(&profile).await?;
-        Ok(prelude::LoginSuccess { token })
+        Ok(self.open_session(&profile, &req.device).await?)
     }
 
     async fn signup(
@@ -35,28 +107,415 @@ where
     ) -> Result<prelude::LoginSuccess, prelude::SignupError> {
         let email = req.email.into_inner();
         let password = req.password.into_inner();
-        let password_hash = hash_password(&email, &password);
+        let password_hash = password::hash(password).await?;
         let profile = self
             .authentication_repository
             .create(email.as_str(), password_hash.as_str())
             .await?;
 
-        let token = self.token_repository.create_token(&profile).await?;
-        Ok(prelude::LoginSuccess { token })
+        Ok(self.open_session(&profile, &req.device).await?)
+    }
+
+    async fn refresh(&self, previous: &str) -> Result<prelude::LoginSuccess, prelude::VerifyError> {
+        let refresh_token = refresh_token::generate();
+        let expires_at = chrono::Utc::now() + self.refresh_token_duration;
+        let session = self
+            .session_repository
+            .rotate(
+                &refresh_token::hash(previous),
+                &refresh_token::hash(&refresh_token),
+                expires_at,
+            )
+            .await?
+            .ok_or(prelude::VerifyError::InvalidToken)?;
+        let token = self
+            .token_repository
+            .create_token(&entity::Principal {
+                user_id: session.user_id,
+                session_id: session.id,
+            })
+            .await?;
+        Ok(prelude::LoginSuccess {
+            token,
+            refresh_token,
+        })
+    }
+
+    async fn logout(&self, principal: &entity::Principal) -> anyhow::Result<()> {
+        self.session_repository
+            .revoke(principal.user_id, principal.session_id)
+            .await?;
+        Ok(())
+    }
+
+    async fn verify(&self, token: &str) -> Result<entity::Principal, prelude::VerifyError> {
+        let principal = self.token_repository.decode_token(token).await?;
+        let Some(session) = self
+            .session_repository
+            .find_active(principal.user_id, principal.session_id)
+            .await?
+        else {
+            return Err(prelude::VerifyError::InvalidToken);
+        };
+        let now = chrono::Utc::now();
+        let seen_recently = session
+            .last_seen_at
+            .is_some_and(|last_seen_at| last_seen_at + self.session_activity_interval > now);
+        if seen_recently
+            || self
+                .session_repository
+                .touch(principal.user_id, principal.session_id)
+                .await?
+        {
+            Ok(principal)
+        } else {
+            Err(prelude::VerifyError::InvalidToken)
+        }
+    }
+
+    async fn list_sessions(&self, user_id: u64) -> anyhow::Result<Vec<entity::Session>> {
+        self.session_repository.list(user_id).await
+    }
+
+    async fn revoke_session(&self, user_id: u64, session_id: u64) -> anyhow::Result<bool> {
+        self.session_repository.revoke(user_id, session_id).await
+    }
+
+    async fn rotate_feed_token(&self, user_id: u64) -> anyhow::Result<String> {
+        let token = refresh_token::generate();
+        self.feed_token_repository
+            .upsert(user_id, &refresh_token::hash(&token))
+            .await?;
+        Ok(token)
+    }
+
+    async fn revoke_feed_token(&self, user_id: u64) -> anyhow::Result<bool> {
+        self.feed_token_repository.delete(user_id).await
+    }
+
+    async fn verify_feed_token(&self, user_id: u64, token: &str) -> anyhow::Result<bool> {
+        self.feed_token_repository
+            .exists(user_id, &refresh_token::hash(token))
+            .await
+    }
+
+    async fn create_media_token(
+        &self,
+        principal: &entity::Principal,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<String> {
+        self.token_repository
+            .create_media_token(&entity::MediaGrant {
+                user_id: principal.user_id,
+                session_id: principal.session_id,
+                podcast_episode_id,
+            })
+            .await
     }
 
-    async fn verify(&self, token: &str) -> Result<entity::Profile, prelude::VerifyError> {
-        self.token_repository.decode_token(token).await
+    async fn verify_media_token(
+        &self,
+        podcast_episode_id: u64,
+        token: &str,
+    ) -> Result<u64, prelude::VerifyError> {
+        let grant = self.token_repository.decode_media_token(token).await?;
+        if grant.podcast_episode_id != podcast_episode_id {
+            return Err(prelude::VerifyError::InvalidToken);
+        }
+        // the token outlives the access token, but not the session
+        self.session_repository
+            .find_active(grant.user_id, grant.session_id)
+            .await?
+            .map(|session| session.user_id)
+            .ok_or(prelude::VerifyError::InvalidToken)
     }
 }
 
-fn hash_password(email: &str, password: &str) -> String {
-    use base64ct::Encoding;
-    use sha2::Digest;
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::entity::{
+        Credentials, DeviceInfo, Email, MediaGrant, Password, Principal, Profile, Session,
+    };
+    use super::prelude::{
+        AuthenticationService as _, LoginError, LoginRequest, SignupError, VerifyError,
+    };
+
+    mockall::mock! {
+        AuthenticationRepository {}
+
+        impl super::prelude::AuthenticationRepository for AuthenticationRepository {
+            fn find_by_email(&self, email: &str) -> impl Future<Output = anyhow::Result<Option<Credentials>>> + Send;
+            fn create(&self, email: &str, password: &str) -> impl Future<Output = Result<Profile, SignupError>> + Send;
+            fn update_password(&self, user_id: u64, password: &str) -> impl Future<Output = anyhow::Result<()>> + Send;
+        }
+    }
+
+    mockall::mock! {
+        SessionRepository {}
+
+        impl super::prelude::SessionRepository for SessionRepository {
+            fn create(
+                &self,
+                user_id: u64,
+                refresh_token: &str,
+                expires_at: chrono::DateTime<chrono::Utc>,
+                device: &DeviceInfo,
+            ) -> impl Future<Output = anyhow::Result<Session>> + Send;
+            fn rotate(
+                &self,
+                previous_refresh_token: &str,
+                refresh_token: &str,
+                expires_at: chrono::DateTime<chrono::Utc>,
+            ) -> impl Future<Output = anyhow::Result<Option<Session>>> + Send;
+            fn revoke(&self, user_id: u64, session_id: u64) -> impl Future<Output = anyhow::Result<bool>> + Send;
+            fn list(&self, user_id: u64) -> impl Future<Output = anyhow::Result<Vec<Session>>> + Send;
+            fn find_active(&self, user_id: u64, session_id: u64) -> impl Future<Output = anyhow::Result<Option<Session>>> + Send;
+            fn touch(&self, user_id: u64, session_id: u64) -> impl Future<Output = anyhow::Result<bool>> + Send;
+        }
+    }
+
+    mockall::mock! {
+        TokenRepository {}
+
+        impl super::prelude::TokenRepository for TokenRepository {
+            fn create_token(&self, principal: &Principal) -> impl Future<Output = anyhow::Result<String>> + Send;
+            fn decode_token(&self, token: &str) -> impl Future<Output = Result<Principal, VerifyError>> + Send;
+            fn create_media_token(&self, grant: &MediaGrant) -> impl Future<Output = anyhow::Result<String>> + Send;
+            fn decode_media_token(&self, token: &str) -> impl Future<Output = Result<MediaGrant, VerifyError>> + Send;
+        }
+    }
+
+    mockall::mock! {
+        FeedTokenRepository {}
+
+        impl super::prelude::FeedTokenRepository for FeedTokenRepository {
+            fn upsert(&self, user_id: u64, token: &str) -> impl Future<Output = anyhow::Result<()>> + Send;
+            fn delete(&self, user_id: u64) -> impl Future<Output = anyhow::Result<bool>> + Send;
+            fn exists(&self, user_id: u64, token: &str) -> impl Future<Output = anyhow::Result<bool>> + Send;
+        }
+    }
+
+    fn session(user_id: u64) -> Session {
+        let now = chrono::Utc::now();
+        Session {
+            id: 1,
+            user_id,
+            expires_at: now,
+            revoked_at: None,
+            device: DeviceInfo::default(),
+            last_seen_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
 
-    let mut hasher = sha2::Sha256::new();
-    hasher.update(email.as_bytes());
-    hasher.update(password.as_bytes());
-    let hash = hasher.finalize();
-    base64ct::Base64::encode_string(&hash)
+    #[tokio::test]
+    async fn should_reject_unknown_email() {
+        let mut authentication_repository = MockAuthenticationRepository::new();
+        authentication_repository
+            .expect_find_by_email()
+            .return_once(|_| Box::pin(async { Ok(None) }));
+        let service = super::AuthenticationService::builder()
+            .authentication_repository(authentication_repository)
+            .feed_token_repository(MockFeedTokenRepository::new())
+            .session_repository(MockSessionRepository::new())
+            .token_repository(MockTokenRepository::new())
+            .refresh_token_duration(Duration::from_secs(3600))
+            .build();
+        let err = service
+            .login(LoginRequest {
+                email: Email::try_new("user@example.com").unwrap(),
+                password: Password::try_new("correct horse battery").unwrap(),
+                device: DeviceInfo::default(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LoginError::InvalidCredentials));
+    }
+
+    #[tokio::test]
+    async fn should_rehash_legacy_password_on_login() {
+        let email = "user@example.com";
+        let password = "correct horse battery";
+
+        let mut authentication_repository = MockAuthenticationRepository::new();
+        let legacy = super::password::legacy_hash(email, password);
+        authentication_repository
+            .expect_find_by_email()
+            .return_once(move |_| {
+                Box::pin(async move {
+                    Ok(Some(Credentials {
+                        profile: Profile { id: 1 },
+                        password_hash: legacy,
+                    }))
+                })
+            });
+        authentication_repository
+            .expect_update_password()
+            .once()
+            .withf(move |user_id, stored| {
+                *user_id == 1
+                    && stored.starts_with("$argon2id$")
+                    && super::password::verify_blocking(email, password, stored).unwrap()
+                        == super::password::Verification::Valid
+            })
+            .return_once(|_, _| Box::pin(async { Ok(()) }));
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_create()
+            .return_once(|user_id, _, _, _| Box::pin(async move { Ok(session(user_id)) }));
+        let mut token_repository = MockTokenRepository::new();
+        token_repository
+            .expect_create_token()
+            .return_once(|_| Box::pin(async { Ok(String::from("token")) }));
+
+        let service = super::AuthenticationService::builder()
+            .authentication_repository(authentication_repository)
+            .feed_token_repository(MockFeedTokenRepository::new())
+            .session_repository(session_repository)
+            .token_repository(token_repository)
+            .refresh_token_duration(Duration::from_secs(3600))
+            .build();
+        let success = service
+            .login(LoginRequest {
+                email: Email::try_new(email).unwrap(),
+                password: Password::try_new(password).unwrap(),
+                device: DeviceInfo::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(success.token, "token");
+    }
+
+    #[tokio::test]
+    async fn should_reject_media_token_of_another_episode() {
+        let mut token_repository = MockTokenRepository::new();
+        token_repository.expect_decode_media_token().returning(|_| {
+            Box::pin(async {
+                Ok(MediaGrant {
+                    user_id: 1,
+                    session_id: 2,
+                    podcast_episode_id: 3,
+                })
+            })
+        });
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_find_active()
+            .return_once(|user_id, session_id| {
+                assert_eq!(session_id, 2);
+                Box::pin(async move { Ok(Some(session(user_id))) })
+            });
+        let service = super::AuthenticationService::builder()
+            .authentication_repository(MockAuthenticationRepository::new())
+            .feed_token_repository(MockFeedTokenRepository::new())
+            .session_repository(session_repository)
+            .token_repository(token_repository)
+            .refresh_token_duration(Duration::from_secs(3600))
+            .build();
+        assert_eq!(service.verify_media_token(3, "token").await.unwrap(), 1);
+        let err = service.verify_media_token(4, "token").await.unwrap_err();
+        assert!(matches!(err, VerifyError::InvalidToken));
+    }
+
+    #[tokio::test]
+    async fn should_reject_media_token_of_revoked_session() {
+        let mut token_repository = MockTokenRepository::new();
+        token_repository.expect_decode_media_token().returning(|_| {
+            Box::pin(async {
+                Ok(MediaGrant {
+                    user_id: 1,
+                    session_id: 2,
+                    podcast_episode_id: 3,
+                })
+            })
+        });
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_find_active()
+            .return_once(|_, _| Box::pin(async { Ok(None) }));
+        let service = super::AuthenticationService::builder()
+            .authentication_repository(MockAuthenticationRepository::new())
+            .feed_token_repository(MockFeedTokenRepository::new())
+            .session_repository(session_repository)
+            .token_repository(token_repository)
+            .refresh_token_duration(Duration::from_secs(3600))
+            .build();
+        let err = service.verify_media_token(3, "token").await.unwrap_err();
+        assert!(matches!(err, VerifyError::InvalidToken));
+    }
+
+    fn verify_service(
+        session_repository: MockSessionRepository,
+    ) -> impl super::prelude::AuthenticationService {
+        let mut token_repository = MockTokenRepository::new();
+        token_repository.expect_decode_token().returning(|_| {
+            Box::pin(async {
+                Ok(Principal {
+                    user_id: 1,
+                    session_id: 1,
+                })
+            })
+        });
+        super::AuthenticationService::builder()
+            .authentication_repository(MockAuthenticationRepository::new())
+            .feed_token_repository(MockFeedTokenRepository::new())
+            .session_repository(session_repository)
+            .token_repository(token_repository)
+            .refresh_token_duration(Duration::from_secs(3600))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn should_verify_without_touching_recently_seen_session() {
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_find_active()
+            .return_once(|user_id, _| {
+                Box::pin(async move {
+                    Ok(Some(Session {
+                        last_seen_at: Some(chrono::Utc::now()),
+                        ..session(user_id)
+                    }))
+                })
+            });
+        session_repository.expect_touch().never();
+        let service = verify_service(session_repository);
+        assert_eq!(service.verify("token").await.unwrap().user_id, 1);
+    }
+
+    #[tokio::test]
+    async fn should_touch_session_not_seen_recently() {
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_find_active()
+            .return_once(|user_id, _| {
+                Box::pin(async move {
+                    Ok(Some(Session {
+                        last_seen_at: Some(chrono::Utc::now() - Duration::from_secs(3600)),
+                        ..session(user_id)
+                    }))
+                })
+            });
+        session_repository
+            .expect_touch()
+            .once()
+            .return_once(|_, _| Box::pin(async { Ok(true) }));
+        let service = verify_service(session_repository);
+        assert_eq!(service.verify("token").await.unwrap().user_id, 1);
+    }
+
+    #[tokio::test]
+    async fn should_reject_token_of_inactive_session() {
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_find_active()
+            .return_once(|_, _| Box::pin(async { Ok(None) }));
+        session_repository.expect_touch().never();
+        let service = verify_service(session_repository);
+        let err = service.verify("token").await.unwrap_err();
+        assert!(matches!(err, VerifyError::InvalidToken));
+    }
 }
@@ -0,0 +1,179 @@
+use anyhow::Context;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Verification {
+    Invalid,
+    Valid,
+    /// Valid, but hashed with an older scheme or parameters
+    Outdated,
+}
+
+/// Hash of a password nobody knows, with the current parameters, verified when the email is
+/// unknown so that answering takes as long as for a wrong password
+pub(super) const DUMMY_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$8KJEPj+WSs8b3i8tmxVD5A$8hwx2znA8hhRi8GwyOAQcW0EiNTNI1+wCPn16QM4fmI";
+
+fn hasher() -> Argon2<'static> {
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, Params::default())
+}
+
+/// Hashes the password on the blocking pool, argon2 being too slow to run on the executor
+pub(super) async fn hash(password: String) -> anyhow::Result<String> {
+    tokio::task::spawn_blocking(move || hash_blocking(&password))
+        .await
+        .context("unable to join password hashing task")?
+}
+
+/// Verifies the password on the blocking pool, argon2 being too slow to run on the executor
+pub(super) async fn verify(
+    email: String,
+    password: String,
+    stored: String,
+) -> anyhow::Result<Verification> {
+    tokio::task::spawn_blocking(move || verify_blocking(&email, &password, &stored))
+        .await
+        .context("unable to join password verification task")?
+}
+
+/// Hashes the password with argon2id and a random salt, in the PHC string format
+fn hash_blocking(password: &str) -> anyhow::Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    hasher()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .context("unable to hash password")
+}
+
+pub(super) fn verify_blocking(
+    email: &str,
+    password: &str,
+    stored: &str,
+) -> anyhow::Result<Verification> {
+    if !stored.starts_with('$') {
+        return Ok(
+            if constant_time_eq(legacy_hash(email, password).as_bytes(), stored.as_bytes()) {
+                Verification::Outdated
+            } else {
+                Verification::Invalid
+            },
+        );
+    }
+
+    let parsed = PasswordHash::new(stored).context("unable to parse password hash")?;
+    match hasher().verify_password(password.as_bytes(), &parsed) {
+        Ok(()) if is_outdated(&parsed) => Ok(Verification::Outdated),
+        Ok(()) => Ok(Verification::Valid),
+        Err(argon2::password_hash::Error::Password) => Ok(Verification::Invalid),
+        Err(err) => Err(anyhow::Error::from(err).context("unable to verify password")),
+    }
+}
+
+fn is_outdated(hash: &PasswordHash<'_>) -> bool {
+    let expected = Params::default();
+    hash.algorithm != Algorithm::Argon2id.ident()
+        || hash.version != Some(Version::V0x13.into())
+        || Params::try_from(hash).map_or(true, |params| {
+            params.m_cost() != expected.m_cost()
+                || params.t_cost() != expected.t_cost()
+                || params.p_cost() != expected.p_cost()
+        })
+}
+
+/// SHA-256 of the email and password, stored before switching to argon2id
+pub(super) fn legacy_hash(email: &str, password: &str) -> String {
+    use base64ct::Encoding;
+    use sha2::Digest;
+
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(email.as_bytes());
+    hasher.update(password.as_bytes());
+    let hash = hasher.finalize();
+    base64ct::Base64::encode_string(&hash)
+}
+
+/// Compares without returning early, so the time taken doesn't tell how many bytes match
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right.iter())
+            .fold(0u8, |acc, (l, r)| acc | (l ^ r))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use argon2::password_hash::rand_core::OsRng;
+    use argon2::password_hash::{PasswordHasher, SaltString};
+    use argon2::{Algorithm, Argon2, Params, Version};
+
+    use super::Verification;
+
+    const EMAIL: &str = "user@example.com";
+    const PASSWORD: &str = "correct horse battery";
+
+    #[test]
+    fn should_verify_hashed_password() {
+        let stored = super::hash_blocking(PASSWORD).unwrap();
+        assert!(stored.starts_with("$argon2id$"));
+        assert_eq!(
+            super::verify_blocking(EMAIL, PASSWORD, &stored).unwrap(),
+            Verification::Valid
+        );
+    }
+
+    #[test]
+    fn should_reject_wrong_password() {
+        let stored = super::hash_blocking(PASSWORD).unwrap();
+        assert_eq!(
+            super::verify_blocking(EMAIL, "wrong password", &stored).unwrap(),
+            Verification::Invalid
+        );
+    }
+
+    #[test]
+    fn should_verify_dummy_hash_like_a_current_one() {
+        let parsed = argon2::PasswordHash::new(super::DUMMY_HASH).unwrap();
+        assert!(!super::is_outdated(&parsed));
+        assert_eq!(
+            super::verify_blocking(EMAIL, PASSWORD, super::DUMMY_HASH).unwrap(),
+            Verification::Invalid
+        );
+    }
+
+    #[test]
+    fn should_flag_legacy_hash_as_outdated() {
+        let stored = super::legacy_hash(EMAIL, PASSWORD);
+        assert_eq!(
+            super::verify_blocking(EMAIL, PASSWORD, &stored).unwrap(),
+            Verification::Outdated
+        );
+        assert_eq!(
+            super::verify_blocking(EMAIL, "wrong password", &stored).unwrap(),
+            Verification::Invalid
+        );
+    }
+
+    #[test]
+    fn should_flag_old_params_as_outdated() {
+        let params = Params::new(8 * 1024, 1, 1, None).unwrap();
+        let salt = SaltString::generate(&mut OsRng);
+        let stored = Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password(PASSWORD.as_bytes(), &salt)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            super::verify_blocking(EMAIL, PASSWORD, &stored).unwrap(),
+            Verification::Outdated
+        );
+    }
+
+    #[test]
+    fn should_compare_in_constant_time() {
+        assert!(super::constant_time_eq(b"abc", b"abc"));
+        assert!(!super::constant_time_eq(b"abc", b"abd"));
+        assert!(!super::constant_time_eq(b"abc", b"abcd"));
+    }
+}
@@ -1,14 +1,16 @@
-use crate::auth::entity::Profile;
+use crate::auth::entity::{Credentials, DeviceInfo, MediaGrant, Principal, Profile, Session};
 
 #[derive(Debug)]
 pub struct LoginRequest {
     pub email: super::entity::Email,
     pub password: super::entity::Password,
+    pub device: DeviceInfo,
 }
 
 #[derive(Debug)]
 pub struct LoginSuccess {
     pub token: String,
+    pub refresh_token: String,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -23,6 +25,7 @@ pub enum LoginError {
 pub struct SignupRequest {
     pub email: super::entity::Email,
     pub password: super::entity::Password,
+    pub device: DeviceInfo,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -52,7 +55,51 @@ pub trait AuthenticationService: Send + Sync + 'static {
         &self,
         request: SignupRequest,
     ) -> impl Future<Output = Result<LoginSuccess, SignupError>> + Send;
-    fn verify(&self, token: &str) -> impl Future<Output = Result<Profile, VerifyError>> + Send;
+    /// Rotates the refresh token and issues a new access token
+    fn refresh(
+        &self,
+        refresh_token: &str,
+    ) -> impl Future<Output = Result<LoginSuccess, VerifyError>> + Send;
+    /// Revokes the session of the access token
+    fn logout(&self, principal: &Principal) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Decodes the access token and ensures its session hasn't been revoked
+    fn verify(&self, token: &str) -> impl Future<Output = Result<Principal, VerifyError>> + Send;
+    /// Lists the active sessions of the user
+    fn list_sessions(
+        &self,
+        user_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Vec<Session>>> + Send;
+    /// Revokes a session of the user, returns `false` when it doesn't exist or is already revoked
+    fn revoke_session(
+        &self,
+        user_id: u64,
+        session_id: u64,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+    /// Issues a new token for the personal feed of the user, invalidating the previous one
+    fn rotate_feed_token(
+        &self,
+        user_id: u64,
+    ) -> impl Future<Output = anyhow::Result<String>> + Send;
+    /// Revokes the token of the personal feed, returns `false` when the user doesn't have one
+    fn revoke_feed_token(&self, user_id: u64) -> impl Future<Output = anyhow::Result<bool>> + Send;
+    /// Checks the token used to access the personal feed of the user
+    fn verify_feed_token(
+        &self,
+        user_id: u64,
+        token: &str,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+    /// Issues a token granting the user access to the file of the episode, put in its url
+    fn create_media_token(
+        &self,
+        principal: &Principal,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<String>> + Send;
+    /// Checks the token grants access to the file of the episode, returns the user it was issued to
+    fn verify_media_token(
+        &self,
+        podcast_episode_id: u64,
+        token: &str,
+    ) -> impl Future<Output = Result<u64, VerifyError>> + Send;
 }
 
 #[cfg(any(test, feature = "mocks"))]
@@ -63,9 +110,48 @@ impl<S: AuthenticationService> AuthenticationService for std::sync::Arc<S> {
     async fn signup(&self, request: SignupRequest) -> Result<LoginSuccess, SignupError> {
         self.as_ref().signup(request).await
     }
-    async fn verify(&self, token: &str) -> Result<Profile, VerifyError> {
+    async fn refresh(&self, refresh_token: &str) -> Result<LoginSuccess, VerifyError> {
+        self.as_ref().refresh(refresh_token).await
+    }
+    async fn logout(&self, principal: &Principal) -> anyhow::Result<()> {
+        self.as_ref().logout(principal).await
+    }
+    async fn verify(&self, token: &str) -> Result<Principal, VerifyError> {
         self.as_ref().verify(token).await
     }
+    async fn list_sessions(&self, user_id: u64) -> anyhow::Result<Vec<Session>> {
+        self.as_ref().list_sessions(user_id).await
+    }
+    async fn revoke_session(&self, user_id: u64, session_id: u64) -> anyhow::Result<bool> {
+        self.as_ref().revoke_session(user_id, session_id).await
+    }
+    async fn rotate_feed_token(&self, user_id: u64) -> anyhow::Result<String> {
+        self.as_ref().rotate_feed_token(user_id).await
+    }
+    async fn revoke_feed_token(&self, user_id: u64) -> anyhow::Result<bool> {
+        self.as_ref().revoke_feed_token(user_id).await
+    }
+    async fn verify_feed_token(&self, user_id: u64, token: &str) -> anyhow::Result<bool> {
+        self.as_ref().verify_feed_token(user_id, token).await
+    }
+    async fn create_media_token(
+        &self,
+        principal: &Principal,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<String> {
+        self.as_ref()
+            .create_media_token(principal, podcast_episode_id)
+            .await
+    }
+    async fn verify_media_token(
+        &self,
+        podcast_episode_id: u64,
+        token: &str,
+    ) -> Result<u64, VerifyError> {
+        self.as_ref()
+            .verify_media_token(podcast_episode_id, token)
+            .await
+    }
 }
 
 #[cfg(any(test, feature = "mocks"))]
@@ -81,30 +167,124 @@ mockall::mock! {
             &self,
             request: SignupRequest,
         ) -> impl Future<Output = Result<LoginSuccess, SignupError>> + Send;
-        fn verify(&self, token: &str) -> impl Future<Output = Result<Profile, VerifyError>> + Send;
+        fn refresh(
+            &self,
+            refresh_token: &str,
+        ) -> impl Future<Output = Result<LoginSuccess, VerifyError>> + Send;
+        fn logout(&self, principal: &Principal) -> impl Future<Output = anyhow::Result<()>> + Send;
+        fn verify(&self, token: &str) -> impl Future<Output = Result<Principal, VerifyError>> + Send;
+        fn list_sessions(
+            &self,
+            user_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Vec<Session>>> + Send;
+        fn revoke_session(
+            &self,
+            user_id: u64,
+            session_id: u64,
+        ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+        fn rotate_feed_token(&self, user_id: u64) -> impl Future<Output = anyhow::Result<String>> + Send;
+        fn revoke_feed_token(&self, user_id: u64) -> impl Future<Output = anyhow::Result<bool>> + Send;
+        fn verify_feed_token(
+            &self,
+            user_id: u64,
+            token: &str,
+        ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+        fn create_media_token(
+            &self,
+            principal: &Principal,
+            podcast_episode_id: u64,
+        ) -> impl Future<Output = anyhow::Result<String>> + Send;
+        fn verify_media_token(
+            &self,
+            podcast_episode_id: u64,
+            token: &str,
+        ) -> impl Future<Output = Result<u64, VerifyError>> + Send;
     }
 }
 
 pub trait AuthenticationRepository: Send + Sync + 'static {
-    fn find_by_credentials(
+    fn find_by_email(
         &self,
         email: &str,
-        password: &str,
-    ) -> impl Future<Output = anyhow::Result<Option<Profile>>> + Send;
+    ) -> impl Future<Output = anyhow::Result<Option<Credentials>>> + Send;
     fn create(
         &self,
         email: &str,
         password: &str,
     ) -> impl Future<Output = Result<Profile, SignupError>> + Send;
+    fn update_password(
+        &self,
+        user_id: u64,
+        password: &str,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+pub trait SessionRepository: Send + Sync + 'static {
+    fn create(
+        &self,
+        user_id: u64,
+        refresh_token: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+        device: &DeviceInfo,
+    ) -> impl Future<Output = anyhow::Result<Session>> + Send;
+    /// Replaces the refresh token of an active session, returns `None` when no active session
+    /// matches the previous token
+    fn rotate(
+        &self,
+        previous_refresh_token: &str,
+        refresh_token: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> impl Future<Output = anyhow::Result<Option<Session>>> + Send;
+    /// Revokes the session, returns `false` when it doesn't exist or is already revoked
+    fn revoke(
+        &self,
+        user_id: u64,
+        session_id: u64,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+    /// Lists the sessions of the user that are neither revoked nor expired
+    fn list(&self, user_id: u64) -> impl Future<Output = anyhow::Result<Vec<Session>>> + Send;
+    /// Finds the session when it's neither revoked nor expired
+    fn find_active(
+        &self,
+        user_id: u64,
+        session_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<Session>>> + Send;
+    /// Records the session activity, returns `false` when it's revoked or expired
+    fn touch(
+        &self,
+        user_id: u64,
+        session_id: u64,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
 }
 
 pub trait TokenRepository: Send + Sync + 'static {
     fn create_token(
         &self,
-        profile: &Profile,
+        principal: &Principal,
     ) -> impl Future<Output = anyhow::Result<String>> + Send;
     fn decode_token(
         &self,
         token: &str,
-    ) -> impl Future<Output = Result<Profile, VerifyError>> + Send;
+    ) -> impl Future<Output = Result<Principal, VerifyError>> + Send;
+    fn create_media_token(
+        &self,
+        grant: &MediaGrant,
+    ) -> impl Future<Output = anyhow::Result<String>> + Send;
+    fn decode_media_token(
+        &self,
+        token: &str,
+    ) -> impl Future<Output = Result<MediaGrant, VerifyError>> + Send;
+}
+
+/// Tokens granting a read access to the personal feed of a user, stored hashed
+pub trait FeedTokenRepository: Send + Sync + 'static {
+    /// Replaces the token of the user
+    fn upsert(&self, user_id: u64, token: &str) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Deletes the token of the user, returns `false` when there was none
+    fn delete(&self, user_id: u64) -> impl Future<Output = anyhow::Result<bool>> + Send;
+    fn exists(
+        &self,
+        user_id: u64,
+        token: &str,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
 }
@@ -0,0 +1,16 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64ct::Encoding;
+use sha2::Digest;
+
+/// Generates a random refresh or feed token, handed over to the client only
+pub(super) fn generate() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    base64ct::Base64UrlUnpadded::encode_string(&bytes)
+}
+
+/// Hashes the refresh token before storing it, its entropy makes a slow hash unnecessary
+pub(super) fn hash(token: &str) -> String {
+    let hash = sha2::Sha256::digest(token.as_bytes());
+    base64ct::Base64::encode_string(&hash)
+}
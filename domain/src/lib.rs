@@ -1,4 +1,5 @@
 pub mod auth;
 pub mod podcast;
+pub mod search;
 
 pub mod prelude;
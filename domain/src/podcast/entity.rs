@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::time::Duration;
 
 #[derive(Debug)]
@@ -9,10 +10,88 @@ pub struct Podcast {
     pub image_url: Option<String>,
     pub language: Option<String>,
     pub website: Option<String>,
+    pub author: Option<String>,
+    pub categories: Vec<String>,
+    pub explicit: Option<bool>,
+    pub persons: Vec<PodcastPerson>,
+    pub funding: Vec<PodcastFunding>,
+    pub last_synced_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_sync_error: Option<String>,
+    pub feed_cache: FeedCache,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Podcast listed by a directory, that can be subscribed with its feed url
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PodcastDirectoryEntry {
+    pub title: String,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub feed_url: String,
+    pub image_url: Option<String>,
+    pub website: Option<String>,
+}
+
+/// Subscription of a user to a podcast
+#[derive(Clone, Debug, PartialEq)]
+pub struct PodcastSubscription {
+    pub user_id: u64,
+    pub podcast_id: u64,
+    /// New episodes are appended to the queue of the user when synchronizing
+    pub auto_queue: bool,
+    pub settings: PodcastSubscriptionSettings,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Preferences of the user for a podcast, the clients are in charge of applying them
+#[derive(Clone, Debug, PartialEq)]
+pub struct PodcastSubscriptionSettings {
+    /// Default playback speed, `1.0` being the normal speed
+    pub playback_speed: f32,
+    /// Seconds skipped at the beginning of the episodes
+    pub skip_start: u32,
+    /// Seconds skipped at the end of the episodes
+    pub skip_end: u32,
+    /// Number of latest episodes kept downloaded on the server, `0` when disabled
+    pub auto_download: u32,
+    /// Episodes are listed in the home feed
+    pub include_in_home: bool,
+    /// The user wants to be notified of the new episodes
+    pub notify: bool,
+}
+
+impl Default for PodcastSubscriptionSettings {
+    fn default() -> Self {
+        Self {
+            playback_speed: 1.0,
+            skip_start: 0,
+            skip_end: 0,
+            auto_download: 0,
+            include_in_home: true,
+            notify: false,
+        }
+    }
+}
+
+/// Settings to change on a subscription, the missing ones are kept
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PodcastSubscriptionSettingsUpdate {
+    pub playback_speed: Option<f32>,
+    pub skip_start: Option<u32>,
+    pub skip_end: Option<u32>,
+    pub auto_download: Option<u32>,
+    pub include_in_home: Option<bool>,
+    pub notify: Option<bool>,
+}
+
+/// Podcast along with the subscription of the user
+#[derive(Debug)]
+pub struct SubscribedPodcast {
+    pub podcast: Podcast,
+    pub subscription: PodcastSubscription,
+}
+
 #[derive(Debug)]
 #[allow(unused, reason = "no methods to list episodes yet")]
 pub struct PodcastEpisode {
@@ -27,10 +106,196 @@ pub struct PodcastEpisode {
     pub file_url: String,
     pub file_size: Option<u64>,
     pub file_type: Option<String>,
+    pub image_url: Option<String>,
+    pub season_number: Option<u32>,
+    pub episode_number: Option<u32>,
+    pub episode_type: Option<PodcastEpisodeType>,
+    pub chapters_url: Option<String>,
+    pub transcript: Option<PodcastTranscriptLink>,
+    pub persons: Vec<PodcastPerson>,
+    /// Set when the episode is not part of the feed anymore
+    pub removed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Set when a copy of the file is stored on the server
+    pub downloaded: bool,
+    /// Playback progress of the user listing the episodes
+    pub progress: Option<PodcastEpisodeProgress>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Kind of episode, as defined by `itunes:episodeType`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PodcastEpisodeType {
+    Full,
+    Trailer,
+    Bonus,
+}
+
+impl PodcastEpisodeType {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Trailer => "trailer",
+            Self::Bonus => "bonus",
+        }
+    }
+}
+
+impl std::str::FromStr for PodcastEpisodeType {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "full" => Ok(Self::Full),
+            "trailer" => Ok(Self::Trailer),
+            "bonus" => Ok(Self::Bonus),
+            other => Err(anyhow::anyhow!("unknown episode type {other:?}")),
+        }
+    }
+}
+
+/// Person taking part in a show or an episode, from `podcast:person`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PodcastPerson {
+    pub name: String,
+    pub role: Option<String>,
+    pub group: Option<String>,
+    pub href: Option<String>,
+    pub image_url: Option<String>,
+}
+
+/// Link to support a show, from `podcast:funding`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PodcastFunding {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+/// Transcript advertised by an episode, from `podcast:transcript`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PodcastTranscriptLink {
+    pub url: String,
+    pub mime_type: String,
+    pub language: Option<String>,
+}
+
+/// Chapter of an episode, read from the JSON chapters document
+#[derive(Clone, Debug, PartialEq)]
+pub struct PodcastChapter {
+    pub start: Duration,
+    pub end: Option<Duration>,
+    pub title: Option<String>,
+    pub image_url: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Timed text of a transcript, read from a SRT, VTT or JSON document
+#[derive(Clone, Debug, PartialEq)]
+pub struct PodcastTranscriptCue {
+    pub start: Duration,
+    pub end: Duration,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PodcastEpisodeProgress {
+    pub user_id: u64,
+    pub podcast_episode_id: u64,
+    pub progress: Duration,
+    pub completed: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Number of latest episodes of a podcast kept downloaded on the server
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PodcastDownloadPolicy {
+    pub podcast_id: u64,
+    pub latest: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PodcastEpisodeDownloadStatus {
+    Pending,
+    Completed,
+    /// Given up after too many failed attempts
+    Failed,
+}
+
+impl PodcastEpisodeDownloadStatus {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for PodcastEpisodeDownloadStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(Self::Pending),
+            "completed" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            other => Err(anyhow::anyhow!("unknown download status {other:?}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PodcastEpisodeDownload {
+    pub podcast_episode_id: u64,
+    pub status: PodcastEpisodeDownloadStatus,
+    pub attempts: u32,
+    /// Path of the file, relative to the media directory
+    pub file_path: Option<String>,
+    pub file_size: Option<u64>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Episode file waiting in the download queue
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingPodcastEpisodeDownload {
+    pub podcast_id: u64,
+    pub podcast_episode_id: u64,
+    pub file_url: String,
+    pub file_type: Option<String>,
+    /// Number of failed attempts so far
+    pub attempts: u32,
+}
+
+/// File of an episode and where to read it from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PodcastEpisodeMedia {
+    pub source: PodcastEpisodeMediaSource,
+    /// File provided by the host of the podcast, used when the local copy went missing
+    pub file_url: String,
+    pub file_type: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PodcastEpisodeMediaSource {
+    /// Copy stored in the media directory
+    Local(std::path::PathBuf),
+    /// File provided by the host of the podcast
+    Remote(String),
+}
+
+/// Episode file written in the media directory
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredMedia {
+    /// Path of the file, relative to the media directory
+    pub file_path: String,
+    pub file_size: u64,
+}
+
 #[derive(Debug)]
 pub struct PodcastInput {
     pub feed_url: String,
@@ -39,9 +304,32 @@ pub struct PodcastInput {
     pub image_url: Option<String>,
     pub language: Option<String>,
     pub website: Option<String>,
+    pub author: Option<String>,
+    pub categories: Vec<String>,
+    pub explicit: Option<bool>,
+    pub persons: Vec<PodcastPerson>,
+    pub funding: Vec<PodcastFunding>,
+    /// Url announced by `itunes:new-feed-url`, only a claim until it's been loaded
+    pub new_feed_url: Option<String>,
+    /// Url reached through permanent redirects only, the feed was loaded from it
+    pub redirected_to: Option<String>,
+    pub feed_cache: FeedCache,
     pub episodes: Vec<PodcastEpisodeInput>,
 }
 
+/// HTTP validators returned when fetching a feed, used to query it conditionally
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeedCache {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum FeedLoad {
+    Modified(Box<PodcastInput>),
+    NotModified,
+}
+
 #[derive(Debug)]
 pub struct PodcastEpisodeInput {
     pub guid: Option<String>,
@@ -53,4 +341,28 @@ pub struct PodcastEpisodeInput {
     pub file_url: String,
     pub file_size: Option<u64>,
     pub file_type: Option<String>,
+    pub image_url: Option<String>,
+    pub season_number: Option<u32>,
+    pub episode_number: Option<u32>,
+    pub episode_type: Option<PodcastEpisodeType>,
+    pub chapters_url: Option<String>,
+    pub transcript: Option<PodcastTranscriptLink>,
+    pub persons: Vec<PodcastPerson>,
+}
+
+impl PodcastEpisodeInput {
+    /// Identifies the episode within its podcast.
+    ///
+    /// Falls back on the enclosure url, title and publication date when the feed doesn't
+    /// provide a guid. The sqlite migration backfilling this value relies on the same format.
+    pub fn identity(&self) -> Cow<'_, str> {
+        if let Some(guid) = self.guid.as_deref() {
+            return Cow::Borrowed(guid);
+        }
+        let published_at = self
+            .published_at
+            .map(|value| value.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, false))
+            .unwrap_or_default();
+        Cow::Owned(format!("{}\n{}\n{published_at}", self.file_url, self.title))
+    }
 }
@@ -4,18 +4,45 @@ pub mod entity;
 pub mod prelude;
 
 #[derive(Clone, Debug, bon::Builder)]
-pub struct PodcastService<RFL, PR, PSR> {
+pub struct PodcastService<PD, RFL, PR, PSR> {
+    podcast_directory: PD,
     rss_feed_loader: RFL,
     podcast_repository: PR,
     podcast_subscription_repository: PSR,
 }
 
-impl<RFL, PR, PSR> PodcastService<RFL, PR, PSR>
+impl<PD, RFL, PR, PSR> PodcastService<PD, RFL, PR, PSR>
 where
+    PD: Clone + prelude::PodcastDirectory,
     RFL: Clone + prelude::RssFeedLoader,
     PR: Clone + prelude::PodcastRepository,
     PSR: Clone + prelude::PodcastSubscriptionRepository,
 {
+    /// Loads the feed announced by `itunes:new-feed-url`, the announcement is only trusted
+    /// when the new url serves a valid feed
+    async fn load_announced_feed(
+        &self,
+        new_feed_url: &str,
+    ) -> Option<Box<self::entity::PodcastInput>> {
+        match self
+            .rss_feed_loader
+            .load(new_feed_url, &self::entity::FeedCache::default())
+            .await
+        {
+            Ok(self::entity::FeedLoad::Modified(mut loaded)) => {
+                loaded.feed_url = new_feed_url.to_string();
+                loaded.new_feed_url = None;
+                loaded.redirected_to = None;
+                Some(loaded)
+            }
+            Ok(self::entity::FeedLoad::NotModified) => None,
+            Err(err) => {
+                tracing::warn!(feed_url = new_feed_url, error = ?err, "unable to load announced feed");
+                None
+            }
+        }
+    }
+
     async fn find_or_sync_by_feed_url(
         &self,
         feed_url: &str,
@@ -24,22 +51,176 @@ where
             return Ok(item);
         }
 
-        let loaded = self.rss_feed_loader.load(feed_url).await?;
+        let mut loaded = match self
+            .rss_feed_loader
+            .load(feed_url, &self::entity::FeedCache::default())
+            .await?
+        {
+            self::entity::FeedLoad::Modified(loaded) => loaded,
+            self::entity::FeedLoad::NotModified => {
+                return Err(anyhow::anyhow!(
+                    "feed reported as not modified without cache"
+                ));
+            }
+        };
+        if let Some(redirected_to) = loaded.redirected_to.take() {
+            if let Some(item) = self
+                .podcast_repository
+                .find_by_feed_url(&redirected_to)
+                .await?
+            {
+                return Ok(item);
+            }
+            loaded.feed_url = redirected_to;
+        }
+        if let Some(new_feed_url) = loaded.new_feed_url.take()
+            && new_feed_url != loaded.feed_url
+            && let Some(announced) = self.load_announced_feed(&new_feed_url).await
+        {
+            if let Some(item) = self
+                .podcast_repository
+                .find_by_feed_url(&new_feed_url)
+                .await?
+            {
+                return Ok(item);
+            }
+            loaded = announced;
+        }
         self.podcast_repository.upsert(&loaded).await
     }
+
+    async fn synchronize_podcast(&self, podcast: &self::entity::Podcast) -> anyhow::Result<()> {
+        let mut loaded = match self
+            .rss_feed_loader
+            .load(&podcast.feed_url, &podcast.feed_cache)
+            .await?
+        {
+            self::entity::FeedLoad::Modified(loaded) => loaded,
+            self::entity::FeedLoad::NotModified => return Ok(()),
+        };
+        // keep the podcast where the user subscribed, even if the feed advertises another url
+        let mut feed_url = podcast.feed_url.clone();
+        // the previous url answered with a permanent redirect, the feed was loaded from the new one
+        if let Some(redirected_to) = loaded.redirected_to.take()
+            && redirected_to != feed_url
+        {
+            tracing::info!(
+                podcast.id = podcast.id,
+                from = feed_url,
+                to = redirected_to,
+                "podcast feed redirected"
+            );
+            feed_url = self
+                .podcast_repository
+                .merge_feed_url(podcast.id, &redirected_to)
+                .await?
+                .feed_url;
+        }
+        // the announcement alone never merges podcasts, the announced feed must be valid
+        // and its url not used by another podcast
+        if let Some(new_feed_url) = loaded.new_feed_url.take()
+            && new_feed_url != feed_url
+            && let Some(announced) = self.load_announced_feed(&new_feed_url).await
+        {
+            match self
+                .podcast_repository
+                .move_feed_url(podcast.id, &new_feed_url)
+                .await?
+            {
+                Some(moved) => {
+                    tracing::info!(
+                        podcast.id = podcast.id,
+                        from = feed_url,
+                        to = moved.feed_url,
+                        "podcast feed moved"
+                    );
+                    feed_url = moved.feed_url;
+                    loaded = announced;
+                }
+                None => tracing::warn!(
+                    podcast.id = podcast.id,
+                    to = new_feed_url,
+                    "announced feed already used by another podcast"
+                ),
+            }
+        }
+        loaded.feed_url = feed_url;
+        self.podcast_repository.upsert(&loaded).await?;
+        Ok(())
+    }
 }
 
-impl<RFL, PR, PSR> prelude::PodcastService for PodcastService<RFL, PR, PSR>
+impl<PD, RFL, PR, PSR> prelude::PodcastSyncService for PodcastService<PD, RFL, PR, PSR>
 where
+    PD: Clone + prelude::PodcastDirectory,
     RFL: Clone + prelude::RssFeedLoader,
     PR: Clone + prelude::PodcastRepository,
     PSR: Clone + prelude::PodcastSubscriptionRepository,
 {
+    async fn synchronize(&self) -> anyhow::Result<()> {
+        let podcasts = self.podcast_repository.list_subscribed().await?;
+        for podcast in podcasts {
+            let error = match self.synchronize_podcast(&podcast).await {
+                Ok(_) => None,
+                Err(err) => {
+                    tracing::warn!(podcast.id = podcast.id, error = ?err, "unable to synchronize podcast");
+                    Some(format!("{err:#}"))
+                }
+            };
+            // the next podcasts are still synchronized
+            if let Err(err) = self
+                .podcast_repository
+                .update_sync_status(podcast.id, error.as_deref())
+                .await
+            {
+                tracing::error!(podcast.id = podcast.id, error = ?err, "unable to update podcast sync status");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<PD, RFL, PR, PSR> prelude::PodcastService for PodcastService<PD, RFL, PR, PSR>
+where
+    PD: Clone + prelude::PodcastDirectory,
+    RFL: Clone + prelude::RssFeedLoader,
+    PR: Clone + prelude::PodcastRepository,
+    PSR: Clone + prelude::PodcastSubscriptionRepository,
+{
+    async fn discover(
+        &self,
+        query: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<entity::PodcastDirectoryEntry>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.podcast_directory.search(query, limit).await
+    }
+
+    async fn find_by_id(&self, podcast_id: u64) -> anyhow::Result<Option<entity::Podcast>> {
+        self.podcast_repository.find_by_id(podcast_id).await
+    }
+
+    async fn subscription(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+    ) -> anyhow::Result<Option<entity::PodcastSubscription>> {
+        self.podcast_subscription_repository
+            .find(user_id, podcast_id)
+            .await
+    }
+
     async fn list_by_ids(&self, podcast_ids: &[u64]) -> anyhow::Result<Vec<entity::Podcast>> {
         self.podcast_repository.list_by_ids(podcast_ids).await
     }
 
-    async fn subscriptions(&self, user_id: u64) -> anyhow::Result<Vec<self::entity::Podcast>> {
+    async fn subscriptions(
+        &self,
+        user_id: u64,
+    ) -> anyhow::Result<Vec<self::entity::SubscribedPodcast>> {
         self.podcast_subscription_repository.list(user_id).await
     }
 
@@ -60,21 +241,398 @@ where
             .delete(user_id, podcast_id)
             .await
     }
+
+    async fn update_subscription(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        update: entity::PodcastSubscriptionSettingsUpdate,
+    ) -> anyhow::Result<Option<entity::PodcastSubscription>> {
+        self.podcast_subscription_repository
+            .update_settings(user_id, podcast_id, &update)
+            .await
+    }
 }
 
 #[derive(Clone, Debug, bon::Builder)]
-pub struct PodcastEpisodeService<PER> {
+pub struct PodcastEpisodeService<PER, PERL> {
     podcast_episode_repository: PER,
+    podcast_episode_resource_loader: PERL,
 }
 
-impl<PER> prelude::PodcastEpisodeService for PodcastEpisodeService<PER>
+impl<PER, PERL> prelude::PodcastEpisodeService for PodcastEpisodeService<PER, PERL>
 where
     PER: PodcastEpisodeRepository,
+    PERL: prelude::PodcastEpisodeResourceLoader,
 {
     async fn list(
         &self,
         params: prelude::ListPodcastEpisodeParams,
-    ) -> anyhow::Result<Vec<self::entity::PodcastEpisode>> {
+    ) -> anyhow::Result<prelude::PodcastEpisodePage> {
         self.podcast_episode_repository.list(params).await
     }
+
+    async fn update_progress(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        progress: std::time::Duration,
+        completed: bool,
+    ) -> anyhow::Result<Option<self::entity::PodcastEpisodeProgress>> {
+        self.podcast_episode_repository
+            .upsert_progress(user_id, podcast_episode_id, progress, completed)
+            .await
+    }
+
+    async fn mark_played(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        played: bool,
+    ) -> anyhow::Result<Option<self::entity::PodcastEpisodeProgress>> {
+        self.podcast_episode_repository
+            .set_completed(user_id, podcast_episode_id, played)
+            .await
+    }
+
+    async fn chapters(
+        &self,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<self::entity::PodcastChapter>>> {
+        let Some(url) = self
+            .podcast_episode_repository
+            .find_by_id(podcast_episode_id)
+            .await?
+            .and_then(|episode| episode.chapters_url)
+        else {
+            return Ok(None);
+        };
+        if let Some(cached) = self
+            .podcast_episode_repository
+            .find_chapters(podcast_episode_id, &url)
+            .await?
+        {
+            return Ok(Some(cached));
+        }
+        let chapters = self
+            .podcast_episode_resource_loader
+            .load_chapters(&url)
+            .await?;
+        self.podcast_episode_repository
+            .save_chapters(podcast_episode_id, &url, &chapters)
+            .await?;
+        Ok(Some(chapters))
+    }
+
+    async fn transcript(
+        &self,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<self::entity::PodcastTranscriptCue>>> {
+        let Some(transcript) = self
+            .podcast_episode_repository
+            .find_by_id(podcast_episode_id)
+            .await?
+            .and_then(|episode| episode.transcript)
+        else {
+            return Ok(None);
+        };
+        if let Some(cached) = self
+            .podcast_episode_repository
+            .find_transcript(podcast_episode_id, &transcript.url)
+            .await?
+        {
+            return Ok(Some(cached));
+        }
+        let cues = self
+            .podcast_episode_resource_loader
+            .load_transcript(&transcript)
+            .await?;
+        self.podcast_episode_repository
+            .save_transcript(podcast_episode_id, &transcript.url, &cues)
+            .await?;
+        Ok(Some(cues))
+    }
+}
+
+#[derive(Clone, Debug, bon::Builder)]
+pub struct PodcastDownloadService<PDR, PER, MS> {
+    podcast_download_repository: PDR,
+    podcast_episode_repository: PER,
+    media_storage: MS,
+    /// Number of attempts before giving up a download
+    #[builder(default = 5)]
+    max_attempts: u32,
+    /// Delay before retrying a failed download, doubled after each attempt
+    #[builder(default = std::time::Duration::from_secs(10 * 60))]
+    retry_delay: std::time::Duration,
+    /// Number of pending downloads loaded at once
+    #[builder(default = 10)]
+    batch_size: u32,
+}
+
+impl<PDR, PER, MS> PodcastDownloadService<PDR, PER, MS>
+where
+    PDR: prelude::PodcastDownloadRepository,
+    PER: PodcastEpisodeRepository,
+    MS: prelude::MediaStorage,
+{
+    fn retry_at(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        attempts: u32,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        if attempts >= self.max_attempts {
+            return None;
+        }
+        let delay = self
+            .retry_delay
+            .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)));
+        chrono::TimeDelta::from_std(delay)
+            .ok()
+            .and_then(|delay| now.checked_add_signed(delay))
+    }
+
+    async fn remove_outdated(&self) -> anyhow::Result<()> {
+        for download in self.podcast_download_repository.list_outdated().await? {
+            if let Some(file_path) = download.file_path.as_deref() {
+                self.media_storage.remove(file_path).await?;
+            }
+            self.podcast_download_repository
+                .delete(download.podcast_episode_id)
+                .await?;
+        }
+        for file_path in self.podcast_download_repository.list_orphan_files().await? {
+            self.media_storage.remove(&file_path).await?;
+            self.podcast_download_repository
+                .delete_orphan_file(&file_path)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn download(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        download: self::entity::PendingPodcastEpisodeDownload,
+    ) -> anyhow::Result<()> {
+        match self.media_storage.store(&download).await {
+            Ok(media) => {
+                self.podcast_download_repository
+                    .complete(download.podcast_episode_id, &media)
+                    .await
+            }
+            Err(err) => {
+                let retry_at = self.retry_at(now, download.attempts + 1);
+                tracing::warn!(
+                    podcast_episode.id = download.podcast_episode_id,
+                    retry = retry_at.is_some(),
+                    error = ?err,
+                    "unable to download podcast episode"
+                );
+                self.podcast_download_repository
+                    .fail(download.podcast_episode_id, &format!("{err:#}"), retry_at)
+                    .await
+            }
+        }
+    }
+}
+
+impl<PDR, PER, MS> prelude::PodcastDownloadQueueService for PodcastDownloadService<PDR, PER, MS>
+where
+    PDR: prelude::PodcastDownloadRepository,
+    PER: PodcastEpisodeRepository,
+    MS: prelude::MediaStorage,
+{
+    async fn process(&self) -> anyhow::Result<()> {
+        let queued = self.podcast_download_repository.enqueue_latest().await?;
+        tracing::debug!(queued, "queued podcast episode downloads");
+        self.remove_outdated().await?;
+        // failed downloads are rescheduled after this instant, so the loop ends
+        let now = chrono::Utc::now();
+        loop {
+            let pending = self
+                .podcast_download_repository
+                .list_pending(now, self.batch_size)
+                .await?;
+            if pending.is_empty() {
+                return Ok(());
+            }
+            for download in pending {
+                self.download(now, download).await?;
+            }
+        }
+    }
+}
+
+impl<PDR, PER, MS> prelude::PodcastDownloadService for PodcastDownloadService<PDR, PER, MS>
+where
+    PDR: prelude::PodcastDownloadRepository,
+    PER: PodcastEpisodeRepository,
+    MS: prelude::MediaStorage,
+{
+    async fn download_policy(
+        &self,
+        podcast_id: u64,
+    ) -> anyhow::Result<Option<self::entity::PodcastDownloadPolicy>> {
+        self.podcast_download_repository
+            .find_policy(podcast_id)
+            .await
+    }
+
+    async fn set_download_policy(
+        &self,
+        podcast_id: u64,
+        latest: u32,
+    ) -> anyhow::Result<Option<self::entity::PodcastDownloadPolicy>> {
+        if latest == 0 {
+            self.podcast_download_repository
+                .delete_policy(podcast_id)
+                .await?;
+            return Ok(None);
+        }
+        let policy = self::entity::PodcastDownloadPolicy { podcast_id, latest };
+        self.podcast_download_repository
+            .upsert_policy(&policy)
+            .await?;
+        Ok(Some(policy))
+    }
+
+    async fn media(
+        &self,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<self::entity::PodcastEpisodeMedia>> {
+        let Some(episode) = self
+            .podcast_episode_repository
+            .find_by_id(podcast_episode_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let local = if episode.downloaded {
+            self.podcast_download_repository
+                .find(podcast_episode_id)
+                .await?
+                .and_then(|download| download.file_path)
+                .map(|file_path| self.media_storage.resolve(&file_path))
+        } else {
+            None
+        };
+        let source = match local {
+            Some(path) => self::entity::PodcastEpisodeMediaSource::Local(path),
+            None => self::entity::PodcastEpisodeMediaSource::Remote(episode.file_url.clone()),
+        };
+        Ok(Some(self::entity::PodcastEpisodeMedia {
+            source,
+            file_url: episode.file_url,
+            file_type: episode.file_type,
+        }))
+    }
+}
+
+#[derive(Clone, Debug, bon::Builder)]
+pub struct PodcastQueueService<PER, PQR> {
+    podcast_episode_repository: PER,
+    podcast_queue_repository: PQR,
+}
+
+impl<PER, PQR> prelude::PodcastQueueSyncService for PodcastQueueService<PER, PQR>
+where
+    PER: PodcastEpisodeRepository,
+    PQR: prelude::PodcastQueueRepository,
+{
+    async fn append_new_episodes(&self) -> anyhow::Result<()> {
+        let queued = self.podcast_queue_repository.append_new_episodes().await?;
+        tracing::debug!(queued, "queued new podcast episodes");
+        Ok(())
+    }
+}
+
+impl<PER, PQR> prelude::PodcastQueueService for PodcastQueueService<PER, PQR>
+where
+    PER: PodcastEpisodeRepository,
+    PQR: prelude::PodcastQueueRepository,
+{
+    async fn queue(&self, user_id: u64) -> anyhow::Result<Vec<self::entity::PodcastEpisode>> {
+        self.podcast_queue_repository.list(user_id).await
+    }
+
+    async fn enqueue(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<self::entity::PodcastEpisode>>> {
+        if self
+            .podcast_episode_repository
+            .find_by_id(podcast_episode_id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        self.podcast_queue_repository
+            .push_back(user_id, podcast_episode_id)
+            .await?;
+        self.podcast_queue_repository.list(user_id).await.map(Some)
+    }
+
+    async fn play_next(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<self::entity::PodcastEpisode>>> {
+        if self
+            .podcast_episode_repository
+            .find_by_id(podcast_episode_id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        self.podcast_queue_repository
+            .push_front(user_id, podcast_episode_id)
+            .await?;
+        self.podcast_queue_repository.list(user_id).await.map(Some)
+    }
+
+    async fn dequeue(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<self::entity::PodcastEpisode>>> {
+        if !self
+            .podcast_queue_repository
+            .remove(user_id, podcast_episode_id)
+            .await?
+        {
+            return Ok(None);
+        }
+        self.podcast_queue_repository.list(user_id).await.map(Some)
+    }
+
+    async fn reorder(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        position: u32,
+    ) -> anyhow::Result<Option<Vec<self::entity::PodcastEpisode>>> {
+        if !self
+            .podcast_queue_repository
+            .move_to(user_id, podcast_episode_id, position)
+            .await?
+        {
+            return Ok(None);
+        }
+        self.podcast_queue_repository.list(user_id).await.map(Some)
+    }
+
+    async fn set_auto_queue(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        enabled: bool,
+    ) -> anyhow::Result<bool> {
+        self.podcast_queue_repository
+            .set_auto_queue(user_id, podcast_id, enabled)
+            .await
+    }
 }
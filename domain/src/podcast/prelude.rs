@@ -1,29 +1,99 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
 use crate::{
-    podcast::entity::PodcastEpisode,
+    podcast::entity::{
+        PendingPodcastEpisodeDownload, PodcastChapter, PodcastDownloadPolicy, PodcastEpisode,
+        PodcastEpisodeDownload, PodcastEpisodeMedia, PodcastEpisodeProgress, PodcastTranscriptCue,
+        PodcastTranscriptLink, StoredMedia,
+    },
     prelude::{Page, Sort},
 };
 
-use super::entity::{Podcast, PodcastInput};
+use super::entity::{
+    FeedCache, FeedLoad, Podcast, PodcastDirectoryEntry, PodcastInput, PodcastSubscription,
+    PodcastSubscriptionSettingsUpdate, SubscribedPodcast,
+};
 
 pub trait RssFeedLoader: Send + Sync + 'static {
-    fn load(&self, feed_url: &str) -> impl Future<Output = anyhow::Result<PodcastInput>> + Send;
+    /// Loads the feed, unless the server reports it didn't change since the provided cache
+    fn load(
+        &self,
+        feed_url: &str,
+        cache: &FeedCache,
+    ) -> impl Future<Output = anyhow::Result<FeedLoad>> + Send;
+}
+
+/// Fetches the documents an episode refers to, like its chapters or its transcript
+pub trait PodcastEpisodeResourceLoader: Send + Sync + 'static {
+    fn load_chapters(
+        &self,
+        url: &str,
+    ) -> impl Future<Output = anyhow::Result<Vec<PodcastChapter>>> + Send;
+    fn load_transcript(
+        &self,
+        transcript: &PodcastTranscriptLink,
+    ) -> impl Future<Output = anyhow::Result<Vec<PodcastTranscriptCue>>> + Send;
+}
+
+/// Catalog of podcasts used to find a feed url from a show name
+pub trait PodcastDirectory: Send + Sync + 'static {
+    fn search(
+        &self,
+        query: &str,
+        limit: u32,
+    ) -> impl Future<Output = anyhow::Result<Vec<PodcastDirectoryEntry>>> + Send;
 }
 
 pub trait PodcastRepository: Send + Sync + 'static {
+    fn find_by_id(
+        &self,
+        podcast_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<Podcast>>> + Send;
+    /// Finds a podcast by its feed url or by one of its previous feed urls
     fn find_by_feed_url(
         &self,
         feed_url: &str,
     ) -> impl Future<Output = anyhow::Result<Option<Podcast>>> + Send;
+    /// Moves a podcast to a new feed url, keeping the previous one as an alias.
+    /// Returns `None` when another podcast already uses that url.
+    fn move_feed_url(
+        &self,
+        podcast_id: u64,
+        feed_url: &str,
+    ) -> impl Future<Output = anyhow::Result<Option<Podcast>>> + Send;
+    /// Moves a podcast to a new feed url like `move_feed_url`, but when another podcast already
+    /// uses that url, the podcast is merged into it, along with its download policy and downloads.
+    /// Only a permanent redirect served by the previous url is trusted enough for this.
+    fn merge_feed_url(
+        &self,
+        podcast_id: u64,
+        feed_url: &str,
+    ) -> impl Future<Output = anyhow::Result<Podcast>> + Send;
     fn list_by_ids(
         &self,
         podcast_ids: &[u64],
     ) -> impl Future<Output = anyhow::Result<Vec<Podcast>>> + Send;
     fn upsert(&self, entity: &PodcastInput)
     -> impl Future<Output = anyhow::Result<Podcast>> + Send;
+    fn list_subscribed(&self) -> impl Future<Output = anyhow::Result<Vec<Podcast>>> + Send;
+    fn update_sync_status(
+        &self,
+        podcast_id: u64,
+        error: Option<&str>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
 }
 
 pub trait PodcastSubscriptionRepository: Send + Sync + 'static {
-    fn list(&self, user_id: u64) -> impl Future<Output = anyhow::Result<Vec<Podcast>>> + Send;
+    fn list(
+        &self,
+        user_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Vec<SubscribedPodcast>>> + Send;
+    fn find(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastSubscription>>> + Send;
     fn create(
         &self,
         user_id: u64,
@@ -34,13 +104,36 @@ pub trait PodcastSubscriptionRepository: Send + Sync + 'static {
         user_id: u64,
         subscription_id: u64,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Returns `None` when the user isn't subscribed to the podcast
+    fn update_settings(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        update: &PodcastSubscriptionSettingsUpdate,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastSubscription>>> + Send;
 }
 
 pub trait PodcastService: Send + Sync + 'static {
+    /// Searches the podcast directory
+    fn discover(
+        &self,
+        query: &str,
+        limit: u32,
+    ) -> impl Future<Output = anyhow::Result<Vec<PodcastDirectoryEntry>>> + Send;
+    fn find_by_id(
+        &self,
+        podcast_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<Podcast>>> + Send;
+    /// Returns the subscription of the user to the podcast, if any
+    fn subscription(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastSubscription>>> + Send;
     fn subscriptions(
         &self,
         user_id: u64,
-    ) -> impl Future<Output = anyhow::Result<Vec<Podcast>>> + Send;
+    ) -> impl Future<Output = anyhow::Result<Vec<SubscribedPodcast>>> + Send;
     fn subscribe(
         &self,
         user_id: u64,
@@ -51,6 +144,13 @@ pub trait PodcastService: Send + Sync + 'static {
         user_id: u64,
         podcast_id: u64,
     ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Updates the settings of the subscription, returns `None` when the user isn't subscribed
+    fn update_subscription(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        update: PodcastSubscriptionSettingsUpdate,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastSubscription>>> + Send;
     fn list_by_ids(
         &self,
         podcast_ids: &[u64],
@@ -59,7 +159,24 @@ pub trait PodcastService: Send + Sync + 'static {
 
 #[cfg(any(test, feature = "mocks"))]
 impl<S: PodcastService> PodcastService for std::sync::Arc<S> {
-    async fn subscriptions(&self, user_id: u64) -> anyhow::Result<Vec<super::entity::Podcast>> {
+    async fn discover(
+        &self,
+        query: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<PodcastDirectoryEntry>> {
+        self.as_ref().discover(query, limit).await
+    }
+    async fn find_by_id(&self, podcast_id: u64) -> anyhow::Result<Option<Podcast>> {
+        self.as_ref().find_by_id(podcast_id).await
+    }
+    async fn subscription(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+    ) -> anyhow::Result<Option<PodcastSubscription>> {
+        self.as_ref().subscription(user_id, podcast_id).await
+    }
+    async fn subscriptions(&self, user_id: u64) -> anyhow::Result<Vec<SubscribedPodcast>> {
         self.as_ref().subscriptions(user_id).await
     }
     async fn subscribe(
@@ -72,6 +189,16 @@ impl<S: PodcastService> PodcastService for std::sync::Arc<S> {
     async fn unsubscribe(&self, user_id: u64, podcast_id: u64) -> anyhow::Result<()> {
         self.as_ref().unsubscribe(user_id, podcast_id).await
     }
+    async fn update_subscription(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        update: PodcastSubscriptionSettingsUpdate,
+    ) -> anyhow::Result<Option<PodcastSubscription>> {
+        self.as_ref()
+            .update_subscription(user_id, podcast_id, update)
+            .await
+    }
     async fn list_by_ids(
         &self,
         podcast_ids: &[u64],
@@ -85,10 +212,24 @@ mockall::mock! {
     pub PodcastService {}
 
     impl PodcastService for PodcastService {
+        fn discover(
+            &self,
+            query: &str,
+            limit: u32,
+        ) -> impl Future<Output = anyhow::Result<Vec<PodcastDirectoryEntry>>> + Send;
+        fn find_by_id(
+            &self,
+            podcast_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<Podcast>>> + Send;
+        fn subscription(
+            &self,
+            user_id: u64,
+            podcast_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<PodcastSubscription>>> + Send;
         fn subscriptions(
             &self,
             user_id: u64,
-        ) -> impl Future<Output = anyhow::Result<Vec<super::entity::Podcast>>> + Send;
+        ) -> impl Future<Output = anyhow::Result<Vec<SubscribedPodcast>>> + Send;
         fn subscribe(
             &self,
             user_id: u64,
@@ -99,6 +240,12 @@ mockall::mock! {
             user_id: u64,
             podcast_id: u64,
         ) -> impl Future<Output = anyhow::Result<()>> + Send;
+        fn update_subscription(
+            &self,
+            user_id: u64,
+            podcast_id: u64,
+            update: PodcastSubscriptionSettingsUpdate,
+        ) -> impl Future<Output = anyhow::Result<Option<PodcastSubscription>>> + Send;
         fn list_by_ids(
             &self,
             podcast_ids: &[u64],
@@ -106,47 +253,223 @@ mockall::mock! {
     }
 }
 
+pub trait PodcastSyncService: Send + Sync + 'static {
+    fn synchronize(&self) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
 pub trait PodcastEpisodeRepository: Send + Sync + 'static {
+    fn find_by_id(
+        &self,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisode>>> + Send;
     fn list(
         &self,
         params: ListPodcastEpisodeParams,
-    ) -> impl Future<Output = anyhow::Result<Vec<PodcastEpisode>>> + Send;
+    ) -> impl Future<Output = anyhow::Result<PodcastEpisodePage>> + Send;
+    /// Returns the chapters cached for the episode, when they were fetched from that url
+    fn find_chapters(
+        &self,
+        podcast_episode_id: u64,
+        url: &str,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastChapter>>>> + Send;
+    fn save_chapters(
+        &self,
+        podcast_episode_id: u64,
+        url: &str,
+        chapters: &[PodcastChapter],
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Returns the transcript cached for the episode, when it was fetched from that url
+    fn find_transcript(
+        &self,
+        podcast_episode_id: u64,
+        url: &str,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastTranscriptCue>>>> + Send;
+    fn save_transcript(
+        &self,
+        podcast_episode_id: u64,
+        url: &str,
+        cues: &[PodcastTranscriptCue],
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Stores the playback position, returns `None` when the episode doesn't exist
+    fn upsert_progress(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        progress: Duration,
+        completed: bool,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeProgress>>> + Send;
+    /// Marks the episode as played, keeping the position, or as unplayed, resetting it
+    fn set_completed(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        completed: bool,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeProgress>>> + Send;
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PodcastEpisodeField {
     PublishedAt,
+    Duration,
+    Title,
+    /// Last time the user made progress on the episode
+    ProgressUpdatedAt,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ListPodcastEpisodeFilter {
+    /// Only the episodes of those podcasts, any podcast when empty
+    pub podcast_ids: Vec<u64>,
     pub subscribed: Option<bool>,
     pub watched: Option<bool>,
+    /// Only the episodes of the subscriptions included in the home feed, or excluded from it
+    pub home: Option<bool>,
+    /// Only the episodes started and not completed, or the other ones
+    pub in_progress: Option<bool>,
+    pub published_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub published_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub min_duration: Option<Duration>,
+    pub max_duration: Option<Duration>,
+    /// Mime type of the episode file, like `audio/mpeg`
+    pub file_type: Option<String>,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct ListPodcastEpisodeParams {
     pub user_id: u64,
     pub filter: ListPodcastEpisodeFilter,
     pub sort: Sort<PodcastEpisodeField>,
     pub page: Page,
+    /// Starts the page right after this episode, the offset of the page is then ignored
+    pub cursor: Option<PodcastEpisodeCursor>,
+}
+
+/// Value of the sorted field of an episode, `None` when the episode doesn't provide it
+#[derive(Clone, Debug, PartialEq)]
+pub enum PodcastEpisodeSortValue {
+    PublishedAt(Option<chrono::DateTime<chrono::Utc>>),
+    Duration(Option<Duration>),
+    Title(String),
+    ProgressUpdatedAt(Option<chrono::DateTime<chrono::Utc>>),
+}
+
+impl PodcastEpisodeSortValue {
+    pub fn field(&self) -> PodcastEpisodeField {
+        match self {
+            Self::PublishedAt(_) => PodcastEpisodeField::PublishedAt,
+            Self::Duration(_) => PodcastEpisodeField::Duration,
+            Self::Title(_) => PodcastEpisodeField::Title,
+            Self::ProgressUpdatedAt(_) => PodcastEpisodeField::ProgressUpdatedAt,
+        }
+    }
+}
+
+/// Position in a sorted list of episodes, the id breaks the ties between equal values
+#[derive(Clone, Debug, PartialEq)]
+pub struct PodcastEpisodeCursor {
+    pub value: PodcastEpisodeSortValue,
+    pub id: u64,
+}
+
+impl PodcastEpisodeCursor {
+    pub fn new(field: PodcastEpisodeField, episode: &PodcastEpisode) -> Self {
+        let value = match field {
+            PodcastEpisodeField::PublishedAt => {
+                PodcastEpisodeSortValue::PublishedAt(episode.published_at)
+            }
+            PodcastEpisodeField::Duration => PodcastEpisodeSortValue::Duration(episode.duration),
+            PodcastEpisodeField::Title => PodcastEpisodeSortValue::Title(episode.title.clone()),
+            PodcastEpisodeField::ProgressUpdatedAt => PodcastEpisodeSortValue::ProgressUpdatedAt(
+                episode
+                    .progress
+                    .as_ref()
+                    .map(|progress| progress.updated_at),
+            ),
+        };
+        Self {
+            value,
+            id: episode.id,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PodcastEpisodePage {
+    pub items: Vec<PodcastEpisode>,
+    /// Number of episodes matching the filter, all pages included
+    pub total: u64,
+    /// Set when more episodes follow the last one of the page
+    pub next_cursor: Option<PodcastEpisodeCursor>,
 }
 
 pub trait PodcastEpisodeService: Send + Sync + 'static {
     fn list(
         &self,
         params: ListPodcastEpisodeParams,
-    ) -> impl Future<Output = anyhow::Result<Vec<super::entity::PodcastEpisode>>> + Send;
+    ) -> impl Future<Output = anyhow::Result<PodcastEpisodePage>> + Send;
+    fn update_progress(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        progress: Duration,
+        completed: bool,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeProgress>>> + Send;
+    fn mark_played(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        played: bool,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeProgress>>> + Send;
+    /// Returns the chapters of the episode, `None` when the episode doesn't provide any
+    fn chapters(
+        &self,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastChapter>>>> + Send;
+    /// Returns the transcript of the episode, `None` when the episode doesn't provide any
+    fn transcript(
+        &self,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastTranscriptCue>>>> + Send;
 }
 
 #[cfg(any(test, feature = "mocks"))]
 impl<S: PodcastEpisodeService> PodcastEpisodeService for std::sync::Arc<S> {
-    async fn list(
-        &self,
-        params: ListPodcastEpisodeParams,
-    ) -> anyhow::Result<Vec<super::entity::PodcastEpisode>> {
+    async fn list(&self, params: ListPodcastEpisodeParams) -> anyhow::Result<PodcastEpisodePage> {
         self.as_ref().list(params).await
     }
+    async fn update_progress(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        progress: Duration,
+        completed: bool,
+    ) -> anyhow::Result<Option<PodcastEpisodeProgress>> {
+        self.as_ref()
+            .update_progress(user_id, podcast_episode_id, progress, completed)
+            .await
+    }
+    async fn mark_played(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        played: bool,
+    ) -> anyhow::Result<Option<PodcastEpisodeProgress>> {
+        self.as_ref()
+            .mark_played(user_id, podcast_episode_id, played)
+            .await
+    }
+    async fn chapters(
+        &self,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<PodcastChapter>>> {
+        self.as_ref().chapters(podcast_episode_id).await
+    }
+    async fn transcript(
+        &self,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<PodcastTranscriptCue>>> {
+        self.as_ref().transcript(podcast_episode_id).await
+    }
 }
 
 #[cfg(any(test, feature = "mocks"))]
@@ -157,6 +480,330 @@ mockall::mock! {
         fn list(
             &self,
             params: ListPodcastEpisodeParams,
-        ) -> impl Future<Output = anyhow::Result<Vec<super::entity::PodcastEpisode>>> + Send;
+        ) -> impl Future<Output = anyhow::Result<PodcastEpisodePage>> + Send;
+        fn update_progress(
+            &self,
+            user_id: u64,
+            podcast_episode_id: u64,
+            progress: Duration,
+            completed: bool,
+        ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeProgress>>> + Send;
+        fn mark_played(
+            &self,
+            user_id: u64,
+            podcast_episode_id: u64,
+            played: bool,
+        ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeProgress>>> + Send;
+        fn chapters(
+            &self,
+            podcast_episode_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastChapter>>>> + Send;
+        fn transcript(
+            &self,
+            podcast_episode_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastTranscriptCue>>>> + Send;
+    }
+}
+
+/// Keeps the episode files in the media directory
+pub trait MediaStorage: Send + Sync + 'static {
+    /// Downloads the episode file, replacing any previous copy
+    fn store(
+        &self,
+        download: &PendingPodcastEpisodeDownload,
+    ) -> impl Future<Output = anyhow::Result<StoredMedia>> + Send;
+    fn remove(&self, file_path: &str) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Returns the location of a file stored in the media directory
+    fn resolve(&self, file_path: &str) -> PathBuf;
+}
+
+pub trait PodcastDownloadRepository: Send + Sync + 'static {
+    fn find_policy(
+        &self,
+        podcast_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastDownloadPolicy>>> + Send;
+    fn upsert_policy(
+        &self,
+        policy: &PodcastDownloadPolicy,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    fn delete_policy(&self, podcast_id: u64) -> impl Future<Output = anyhow::Result<()>> + Send;
+    fn find(
+        &self,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeDownload>>> + Send;
+    /// Queues the latest episodes of the podcasts having a download policy, returns how many were added
+    fn enqueue_latest(&self) -> impl Future<Output = anyhow::Result<u64>> + Send;
+    /// Lists the downloads that are not part of the latest episodes of their podcast anymore
+    fn list_outdated(
+        &self,
+    ) -> impl Future<Output = anyhow::Result<Vec<PodcastEpisodeDownload>>> + Send;
+    fn delete(&self, podcast_episode_id: u64) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Lists the files left in the media directory by downloads that were dropped,
+    /// like the ones of episodes merged into another podcast
+    fn list_orphan_files(&self) -> impl Future<Output = anyhow::Result<Vec<String>>> + Send;
+    fn delete_orphan_file(
+        &self,
+        file_path: &str,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Lists the pending downloads that are due at that time
+    fn list_pending(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> impl Future<Output = anyhow::Result<Vec<PendingPodcastEpisodeDownload>>> + Send;
+    fn complete(
+        &self,
+        podcast_episode_id: u64,
+        media: &StoredMedia,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Records a failed attempt, the download is given up when no retry is scheduled
+    fn fail(
+        &self,
+        podcast_episode_id: u64,
+        error: &str,
+        retry_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+pub trait PodcastDownloadQueueService: Send + Sync + 'static {
+    /// Queues the episodes covered by the download policies, removes the outdated copies
+    /// and downloads the pending files
+    fn process(&self) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+pub trait PodcastDownloadService: Send + Sync + 'static {
+    fn download_policy(
+        &self,
+        podcast_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastDownloadPolicy>>> + Send;
+    /// Defines how many of the latest episodes are downloaded, `0` disables the downloads
+    fn set_download_policy(
+        &self,
+        podcast_id: u64,
+        latest: u32,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastDownloadPolicy>>> + Send;
+    /// Returns the copy of the episode file stored on the server, or the one of the host
+    /// otherwise. `None` when the episode doesn't exist.
+    fn media(
+        &self,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeMedia>>> + Send;
+}
+
+#[cfg(any(test, feature = "mocks"))]
+impl<S: PodcastDownloadService> PodcastDownloadService for std::sync::Arc<S> {
+    async fn download_policy(
+        &self,
+        podcast_id: u64,
+    ) -> anyhow::Result<Option<PodcastDownloadPolicy>> {
+        self.as_ref().download_policy(podcast_id).await
+    }
+    async fn set_download_policy(
+        &self,
+        podcast_id: u64,
+        latest: u32,
+    ) -> anyhow::Result<Option<PodcastDownloadPolicy>> {
+        self.as_ref().set_download_policy(podcast_id, latest).await
+    }
+    async fn media(&self, podcast_episode_id: u64) -> anyhow::Result<Option<PodcastEpisodeMedia>> {
+        self.as_ref().media(podcast_episode_id).await
+    }
+}
+
+#[cfg(any(test, feature = "mocks"))]
+mockall::mock! {
+    pub PodcastDownloadService {}
+
+    impl PodcastDownloadService for PodcastDownloadService {
+        fn download_policy(
+            &self,
+            podcast_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<PodcastDownloadPolicy>>> + Send;
+        fn set_download_policy(
+            &self,
+            podcast_id: u64,
+            latest: u32,
+        ) -> impl Future<Output = anyhow::Result<Option<PodcastDownloadPolicy>>> + Send;
+        fn media(
+            &self,
+            podcast_episode_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<PodcastEpisodeMedia>>> + Send;
+    }
+}
+
+/// Ordered list of the episodes a user plans to listen to next
+pub trait PodcastQueueRepository: Send + Sync + 'static {
+    /// Lists the queued episodes, in order, with the progress of the user
+    fn list(
+        &self,
+        user_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Vec<PodcastEpisode>>> + Send;
+    /// Appends the episode to the queue, keeping its position when already queued
+    fn push_back(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Moves the episode to the head of the queue, queuing it when needed
+    fn push_front(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+    /// Removes the episode from the queue, returns `false` when it wasn't queued
+    fn remove(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+    /// Moves the episode to the position, starting from `0`, or to the end of the queue when
+    /// out of range. Returns `false` when the episode isn't queued.
+    fn move_to(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        position: u32,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+    /// Returns `false` when the user isn't subscribed to the podcast
+    fn set_auto_queue(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        enabled: bool,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+    /// Appends the episodes published since the last call to the queue of the users having
+    /// enabled it on the podcast, returns how many were added
+    fn append_new_episodes(&self) -> impl Future<Output = anyhow::Result<u64>> + Send;
+}
+
+pub trait PodcastQueueSyncService: Send + Sync + 'static {
+    /// Appends the new episodes of the podcasts to the queues, once they are synchronized
+    fn append_new_episodes(&self) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+pub trait PodcastQueueService: Send + Sync + 'static {
+    fn queue(
+        &self,
+        user_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Vec<PodcastEpisode>>> + Send;
+    /// Appends the episode to the queue, returns `None` when the episode doesn't exist
+    fn enqueue(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastEpisode>>>> + Send;
+    /// Moves the episode to the head of the queue, returns `None` when the episode doesn't exist
+    fn play_next(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastEpisode>>>> + Send;
+    /// Removes the episode from the queue, returns `None` when it wasn't queued
+    fn dequeue(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastEpisode>>>> + Send;
+    /// Moves a queued episode, returns `None` when it wasn't queued
+    fn reorder(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        position: u32,
+    ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastEpisode>>>> + Send;
+    /// Enables appending the new episodes of the podcast to the queue, returns `false` when
+    /// the user isn't subscribed to the podcast
+    fn set_auto_queue(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        enabled: bool,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send;
+}
+
+#[cfg(any(test, feature = "mocks"))]
+impl<S: PodcastQueueService> PodcastQueueService for std::sync::Arc<S> {
+    async fn queue(&self, user_id: u64) -> anyhow::Result<Vec<PodcastEpisode>> {
+        self.as_ref().queue(user_id).await
+    }
+    async fn enqueue(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<PodcastEpisode>>> {
+        self.as_ref().enqueue(user_id, podcast_episode_id).await
+    }
+    async fn play_next(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<PodcastEpisode>>> {
+        self.as_ref().play_next(user_id, podcast_episode_id).await
+    }
+    async fn dequeue(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+    ) -> anyhow::Result<Option<Vec<PodcastEpisode>>> {
+        self.as_ref().dequeue(user_id, podcast_episode_id).await
+    }
+    async fn reorder(
+        &self,
+        user_id: u64,
+        podcast_episode_id: u64,
+        position: u32,
+    ) -> anyhow::Result<Option<Vec<PodcastEpisode>>> {
+        self.as_ref()
+            .reorder(user_id, podcast_episode_id, position)
+            .await
+    }
+    async fn set_auto_queue(
+        &self,
+        user_id: u64,
+        podcast_id: u64,
+        enabled: bool,
+    ) -> anyhow::Result<bool> {
+        self.as_ref()
+            .set_auto_queue(user_id, podcast_id, enabled)
+            .await
+    }
+}
+
+#[cfg(any(test, feature = "mocks"))]
+mockall::mock! {
+    pub PodcastQueueService {}
+
+    impl PodcastQueueService for PodcastQueueService {
+        fn queue(
+            &self,
+            user_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Vec<PodcastEpisode>>> + Send;
+        fn enqueue(
+            &self,
+            user_id: u64,
+            podcast_episode_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastEpisode>>>> + Send;
+        fn play_next(
+            &self,
+            user_id: u64,
+            podcast_episode_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastEpisode>>>> + Send;
+        fn dequeue(
+            &self,
+            user_id: u64,
+            podcast_episode_id: u64,
+        ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastEpisode>>>> + Send;
+        fn reorder(
+            &self,
+            user_id: u64,
+            podcast_episode_id: u64,
+            position: u32,
+        ) -> impl Future<Output = anyhow::Result<Option<Vec<PodcastEpisode>>>> + Send;
+        fn set_auto_queue(
+            &self,
+            user_id: u64,
+            podcast_id: u64,
+            enabled: bool,
+        ) -> impl Future<Output = anyhow::Result<bool>> + Send;
     }
 }
@@ -0,0 +1,8 @@
+use crate::podcast::entity::{Podcast, PodcastEpisode};
+
+/// Podcasts and episodes matching a query, each list being ordered by relevance
+#[derive(Debug, Default)]
+pub struct SearchResult {
+    pub podcasts: Vec<Podcast>,
+    pub podcast_episodes: Vec<PodcastEpisode>,
+}
@@ -0,0 +1,31 @@
+pub mod entity;
+pub mod prelude;
+
+#[derive(Clone, Debug, bon::Builder)]
+pub struct SearchService<SR> {
+    search_repository: SR,
+}
+
+impl<SR> prelude::SearchService for SearchService<SR>
+where
+    SR: prelude::SearchRepository,
+{
+    async fn search(&self, params: prelude::SearchParams) -> anyhow::Result<entity::SearchResult> {
+        let query = params.query.trim();
+        if query.is_empty() {
+            return Ok(entity::SearchResult::default());
+        }
+        let podcasts = self
+            .search_repository
+            .search_podcasts(query, params.limit)
+            .await?;
+        let podcast_episodes = self
+            .search_repository
+            .search_podcast_episodes(params.user_id, query, params.limit)
+            .await?;
+        Ok(entity::SearchResult {
+            podcasts,
+            podcast_episodes,
+        })
+    }
+}
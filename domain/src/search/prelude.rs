@@ -0,0 +1,52 @@
+use crate::podcast::entity::{Podcast, PodcastEpisode};
+
+use super::entity::SearchResult;
+
+#[derive(Clone, Debug)]
+pub struct SearchParams {
+    pub user_id: u64,
+    pub query: String,
+    /// Maximum number of items returned for each kind of resource
+    pub limit: u32,
+}
+
+pub trait SearchRepository: Send + Sync + 'static {
+    fn search_podcasts(
+        &self,
+        query: &str,
+        limit: u32,
+    ) -> impl Future<Output = anyhow::Result<Vec<Podcast>>> + Send;
+    /// Searches the episodes that are not removed from their feed, with the user progress
+    fn search_podcast_episodes(
+        &self,
+        user_id: u64,
+        query: &str,
+        limit: u32,
+    ) -> impl Future<Output = anyhow::Result<Vec<PodcastEpisode>>> + Send;
+}
+
+pub trait SearchService: Send + Sync + 'static {
+    fn search(
+        &self,
+        params: SearchParams,
+    ) -> impl Future<Output = anyhow::Result<SearchResult>> + Send;
+}
+
+#[cfg(any(test, feature = "mocks"))]
+impl<S: SearchService> SearchService for std::sync::Arc<S> {
+    async fn search(&self, params: SearchParams) -> anyhow::Result<SearchResult> {
+        self.as_ref().search(params).await
+    }
+}
+
+#[cfg(any(test, feature = "mocks"))]
+mockall::mock! {
+    pub SearchService {}
+
+    impl SearchService for SearchService {
+        fn search(
+            &self,
+            params: SearchParams,
+        ) -> impl Future<Output = anyhow::Result<SearchResult>> + Send;
+    }
+}
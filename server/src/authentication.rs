@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use entertainarr_domain::auth::AuthenticationService;
+use entertainarr_domain::auth::prelude::{
+    AuthenticationRepository, FeedTokenRepository, SessionRepository, TokenRepository,
+};
+
+/// Authentication configuration
+#[derive(serde::Deserialize)]
+pub struct Config {
+    /// Lifetime of a refresh token, in seconds
+    #[serde(default = "Config::default_refresh_token_duration")]
+    pub refresh_token_duration: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            refresh_token_duration: Self::default_refresh_token_duration(),
+        }
+    }
+}
+
+impl Config {
+    pub const fn default_refresh_token_duration() -> u64 {
+        60 * 60 * 24 * 30
+    }
+
+    pub fn build<AR, SR, TR, FTR>(
+        self,
+        authentication_repository: AR,
+        session_repository: SR,
+        token_repository: TR,
+        feed_token_repository: FTR,
+    ) -> anyhow::Result<AuthenticationService<AR, SR, TR, FTR>>
+    where
+        AR: AuthenticationRepository,
+        SR: SessionRepository,
+        TR: TokenRepository,
+        FTR: FeedTokenRepository,
+    {
+        if self.refresh_token_duration == 0 {
+            anyhow::bail!("refresh token duration should be greater than 0");
+        }
+        Ok(AuthenticationService::builder()
+            .authentication_repository(authentication_repository)
+            .session_repository(session_repository)
+            .token_repository(token_repository)
+            .feed_token_repository(feed_token_repository)
+            .refresh_token_duration(Duration::from_secs(self.refresh_token_duration))
+            .build())
+    }
+}
@@ -1,20 +1,47 @@
 use anyhow::Context;
-use entertainarr_domain::{
-    auth::AuthenticationService,
-    podcast::{PodcastEpisodeService, PodcastService},
-};
+use entertainarr_domain::podcast::{PodcastEpisodeService, PodcastQueueService, PodcastService};
+use entertainarr_domain::search::SearchService;
 
+pub mod authentication;
 mod client;
+pub mod podcast_download;
+pub mod podcast_sync;
 pub mod tracing;
 
+type PodcastServiceImpl = PodcastService<
+    entertainarr_adapter_directory::DirectoryClient,
+    entertainarr_adapter_rss::RssClient,
+    entertainarr_adapter_sqlite::Pool,
+    entertainarr_adapter_sqlite::Pool,
+>;
+
+type PodcastQueueServiceImpl =
+    PodcastQueueService<entertainarr_adapter_sqlite::Pool, entertainarr_adapter_sqlite::Pool>;
+
+type PodcastDownloadServiceImpl = entertainarr_domain::podcast::PodcastDownloadService<
+    entertainarr_adapter_sqlite::Pool,
+    entertainarr_adapter_sqlite::Pool,
+    entertainarr_adapter_media::MediaStorage,
+>;
+
 /// Entertainarr main configuration
 #[derive(serde::Deserialize)]
 pub struct Config {
+    #[serde(default)]
+    pub authentication: crate::authentication::Config,
+    #[serde(default)]
+    pub directory: entertainarr_adapter_directory::Config,
     #[serde(default)]
     pub http_server: entertainarr_adapter_http::server::Config,
     #[serde(default)]
     pub jsonwebtoken: entertainarr_adapter_jsonwebtoken::Config,
     #[serde(default)]
+    pub media: entertainarr_adapter_media::Config,
+    #[serde(default)]
+    pub podcast_download: crate::podcast_download::Config,
+    #[serde(default)]
+    pub podcast_sync: crate::podcast_sync::Config,
+    #[serde(default)]
     pub rss: entertainarr_adapter_rss::Config,
     #[serde(default)]
     pub sqlite: entertainarr_adapter_sqlite::Config,
@@ -28,39 +55,84 @@ impl Config {
     }
 
     pub async fn build(self) -> anyhow::Result<Application> {
+        let directory_client = self.directory.build()?;
         let http_server = self.http_server.builder()?;
         let jsonwebtoken = self.jsonwebtoken.build()?;
+        let media_storage = self.media.build()?;
         let rss_client = self.rss.build()?;
         let sqlite_pool = self.sqlite.build().await?;
-        let authentication_service = AuthenticationService::builder()
-            .authentication_repository(sqlite_pool.clone())
-            .token_repository(jsonwebtoken)
-            .build();
+        let authentication_service = self.authentication.build(
+            sqlite_pool.clone(),
+            sqlite_pool.clone(),
+            jsonwebtoken,
+            sqlite_pool.clone(),
+        )?;
         let podcast_service = PodcastService::builder()
-            .rss_feed_loader(rss_client)
+            .podcast_directory(directory_client)
+            .rss_feed_loader(rss_client.clone())
             .podcast_repository(sqlite_pool.clone())
             .podcast_subscription_repository(sqlite_pool.clone())
             .build();
+        let podcast_queue_service = PodcastQueueService::builder()
+            .podcast_episode_repository(sqlite_pool.clone())
+            .podcast_queue_repository(sqlite_pool.clone())
+            .build();
+        let podcast_sync = self
+            .podcast_sync
+            .build(podcast_service.clone(), podcast_queue_service.clone())?;
+        let podcast_download_service = self.podcast_download.service(
+            sqlite_pool.clone(),
+            sqlite_pool.clone(),
+            media_storage,
+        )?;
+        let podcast_download = self
+            .podcast_download
+            .build(podcast_download_service.clone())?;
         let podcast_episode_service = PodcastEpisodeService::builder()
-            .podcast_episode_repository(sqlite_pool)
+            .podcast_episode_repository(sqlite_pool.clone())
+            .podcast_episode_resource_loader(rss_client)
+            .build();
+        let search_service = SearchService::builder()
+            .search_repository(sqlite_pool)
             .build();
         let http_server = http_server
             .with_authentication_service(authentication_service)
             .with_client_service(crate::client::ClientService)
             .with_podcast_service(podcast_service)
+            .with_podcast_download_service(podcast_download_service)
             .with_podcast_episode_service(podcast_episode_service)
+            .with_podcast_queue_service(podcast_queue_service)
+            .with_search_service(search_service)
             .build()?;
-        Ok(Application { http_server })
+        Ok(Application {
+            http_server,
+            podcast_download,
+            podcast_sync,
+        })
     }
 }
 
 /// Entertainarr application
 pub struct Application {
     http_server: entertainarr_adapter_http::server::HttpServer,
+    podcast_download:
+        Option<crate::podcast_download::PodcastDownloader<PodcastDownloadServiceImpl>>,
+    podcast_sync: Option<
+        crate::podcast_sync::PodcastSynchronizer<PodcastServiceImpl, PodcastQueueServiceImpl>,
+    >,
 }
 
 impl Application {
     pub async fn run(self) -> anyhow::Result<()> {
-        self.http_server.run().await
+        let podcast_download = self.podcast_download.map(|inner| tokio::spawn(inner.run()));
+        let podcast_sync = self.podcast_sync.map(|inner| tokio::spawn(inner.run()));
+        let result = self.http_server.run().await;
+        if let Some(handler) = podcast_download {
+            handler.abort();
+        }
+        if let Some(handler) = podcast_sync {
+            handler.abort();
+        }
+        result
     }
 }
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use entertainarr_domain::podcast::PodcastDownloadService;
+use entertainarr_domain::podcast::prelude::{
+    MediaStorage, PodcastDownloadQueueService, PodcastDownloadRepository, PodcastEpisodeRepository,
+};
+
+/// Podcast download configuration, the episodes are only streamed from their feed by default
+#[derive(serde::Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Interval between two runs of the download queue, in seconds
+    #[serde(default = "Config::default_interval")]
+    pub interval: u64,
+    /// Number of attempts before giving up a download
+    #[serde(default = "Config::default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before retrying a failed download, in seconds, doubled after each attempt
+    #[serde(default = "Config::default_retry_delay")]
+    pub retry_delay: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Self::default_interval(),
+            max_attempts: Self::default_max_attempts(),
+            retry_delay: Self::default_retry_delay(),
+        }
+    }
+}
+
+impl Config {
+    pub const fn default_interval() -> u64 {
+        15 * 60
+    }
+
+    pub const fn default_max_attempts() -> u32 {
+        5
+    }
+
+    pub const fn default_retry_delay() -> u64 {
+        10 * 60
+    }
+
+    pub fn service<PDR, PER, MS>(
+        &self,
+        podcast_download_repository: PDR,
+        podcast_episode_repository: PER,
+        media_storage: MS,
+    ) -> anyhow::Result<PodcastDownloadService<PDR, PER, MS>>
+    where
+        PDR: PodcastDownloadRepository,
+        PER: PodcastEpisodeRepository,
+        MS: MediaStorage,
+    {
+        if self.max_attempts == 0 {
+            anyhow::bail!("podcast download attempts should be greater than 0");
+        }
+        Ok(PodcastDownloadService::builder()
+            .podcast_download_repository(podcast_download_repository)
+            .podcast_episode_repository(podcast_episode_repository)
+            .media_storage(media_storage)
+            .max_attempts(self.max_attempts)
+            .retry_delay(Duration::from_secs(self.retry_delay))
+            .build())
+    }
+
+    pub fn build<S>(self, service: S) -> anyhow::Result<Option<PodcastDownloader<S>>>
+    where
+        S: PodcastDownloadQueueService,
+    {
+        if !self.enabled {
+            return Ok(None);
+        }
+        if self.interval == 0 {
+            anyhow::bail!("podcast download interval should be greater than 0");
+        }
+        Ok(Some(PodcastDownloader {
+            interval: Duration::from_secs(self.interval),
+            service,
+        }))
+    }
+}
+
+/// Background task downloading the latest episodes of the podcasts having a download policy
+pub struct PodcastDownloader<S> {
+    interval: Duration,
+    service: S,
+}
+
+impl<S> PodcastDownloader<S>
+where
+    S: PodcastDownloadQueueService,
+{
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            tracing::info!("processing podcast downloads");
+            if let Err(err) = self.service.process().await {
+                tracing::error!(error = ?err, "unable to process podcast downloads");
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use entertainarr_domain::podcast::prelude::{PodcastQueueSyncService, PodcastSyncService};
+
+/// Podcast synchronization configuration
+#[derive(serde::Deserialize)]
+pub struct Config {
+    #[serde(default = "Config::default_enabled")]
+    pub enabled: bool,
+    /// Interval between two synchronizations, in seconds
+    #[serde(default = "Config::default_interval")]
+    pub interval: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            interval: Self::default_interval(),
+        }
+    }
+}
+
+impl Config {
+    pub const fn default_enabled() -> bool {
+        true
+    }
+
+    pub const fn default_interval() -> u64 {
+        60 * 60
+    }
+
+    pub fn build<S, Q>(
+        self,
+        service: S,
+        queue_service: Q,
+    ) -> anyhow::Result<Option<PodcastSynchronizer<S, Q>>>
+    where
+        S: PodcastSyncService,
+        Q: PodcastQueueSyncService,
+    {
+        if !self.enabled {
+            return Ok(None);
+        }
+        if self.interval == 0 {
+            anyhow::bail!("podcast synchronization interval should be greater than 0");
+        }
+        Ok(Some(PodcastSynchronizer {
+            interval: Duration::from_secs(self.interval),
+            service,
+            queue_service,
+        }))
+    }
+}
+
+/// Background task refreshing every subscribed podcast, then feeding the queues
+pub struct PodcastSynchronizer<S, Q> {
+    interval: Duration,
+    service: S,
+    queue_service: Q,
+}
+
+impl<S, Q> PodcastSynchronizer<S, Q>
+where
+    S: PodcastSyncService,
+    Q: PodcastQueueSyncService,
+{
+    pub async fn run(self) {
+        // feeds are loaded when subscribing, no need to synchronize right away
+        let start = tokio::time::Instant::now() + self.interval;
+        let mut ticker = tokio::time::interval_at(start, self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            tracing::info!("synchronizing podcasts");
+            if let Err(err) = self.service.synchronize().await {
+                tracing::error!(error = ?err, "unable to synchronize podcasts");
+            }
+            if let Err(err) = self.queue_service.append_new_episodes().await {
+                tracing::error!(error = ?err, "unable to append new episodes to queues");
+            }
+        }
+    }
+}
@@ -10,11 +10,22 @@ impl Server {
     pub async fn new() -> Self {
         let tmpdir = tempfile::tempdir().unwrap();
         let config = entertainarr::Config {
+            authentication: Default::default(),
+            directory: Default::default(),
             http_server: entertainarr_adapter_http::server::Config {
                 address: std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
                 port: 3000,
+                allow_private_media_hosts: false,
+                trusted_proxies: Vec::new(),
             },
             jsonwebtoken: Default::default(),
+            media: entertainarr_adapter_media::Config {
+                path: tmpdir.path().join("media"),
+                allow_private_hosts: false,
+                max_file_size: entertainarr_adapter_media::Config::default_max_file_size(),
+            },
+            podcast_download: Default::default(),
+            podcast_sync: Default::default(),
             rss: Default::default(),
             sqlite: entertainarr_adapter_sqlite::Config {
                 url: Cow::Owned(